@@ -1,25 +1,31 @@
 use crate::ClippyConfiguration;
 use crate::types::{
-    DisallowedPath, DisallowedPathWithoutReplacement, InherentImplLintScope, MacroMatcher, MatchLintBehaviour,
-    PubUnderscoreFieldsBehaviour, Rename, SourceItemOrdering, SourceItemOrderingCategory,
-    SourceItemOrderingModuleItemGroupings, SourceItemOrderingModuleItemKind, SourceItemOrderingTraitAssocItemKind,
-    SourceItemOrderingTraitAssocItemKinds, SourceItemOrderingWithinModuleItemGroupings, TraitImplItemOrder,
+    ApiVersion, Budget, DisallowedPath, DisallowedPathWithoutReplacement, HeldAcrossCall, InherentImplLintScope,
+    MacroMatcher, MatchLintBehaviour, PubUnderscoreFieldsBehaviour, Rename, SourceItemOrdering,
+    SourceItemOrderingCategory, SourceItemOrderingModuleItemGroupings, SourceItemOrderingModuleItemKind,
+    SourceItemOrderingTraitAssocItemKind, SourceItemOrderingTraitAssocItemKinds,
+    SourceItemOrderingWithinModuleItemGroupings, TraitImplItemOrder,
 };
+use clippy_utils::allowed_lints::{self, AllowedInFiles, AllowedLints};
+use clippy_utils::baseline;
 use clippy_utils::msrvs::Msrv;
+use clippy_utils::str_utils::glob_matches;
 use itertools::Itertools as _;
+use rustc_data_structures::fx::FxHashMap;
 use rustc_errors::Applicability;
 use rustc_session::Session;
 use rustc_span::edit_distance::edit_distance;
-use rustc_span::{BytePos, Pos as _, SourceFile, Span, SyntaxContext};
-use serde::de::{IgnoredAny, IntoDeserializer as _, MapAccess, Visitor};
-use serde::{Deserialize, Deserializer as _, Serialize as _};
-use std::collections::HashMap;
+use rustc_span::source_map::SourceMap;
+use rustc_span::{BytePos, Pos as _, SourceFile, Span, StableSourceFileId, SyntaxContext};
+use serde::de::{DeserializeSeed, IgnoredAny, IntoDeserializer as _, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize as _};
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::{Debug, Display, Formatter};
 use std::ops::Range;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use std::sync::OnceLock;
-use std::{cmp, env, fmt, fs, io};
+use std::sync::{Mutex, OnceLock};
+use std::{cmp, env, fmt, fs, io, mem};
 
 #[rustfmt::skip]
 const DEFAULT_DOC_VALID_IDENTS: &[&str] = &[
@@ -84,18 +90,50 @@ const DEFAULT_SOURCE_ITEM_ORDERING: &[SourceItemOrderingCategory] = {
 #[derive(Default)]
 struct TryConf {
     conf: Conf,
-    value_spans: HashMap<String, Range<usize>>,
+    /// Spans of the values set explicitly, keyed by field name. When a configuration file extends
+    /// another one, the spans point into the file each value was read from.
+    value_spans: HashMap<&'static str, Span>,
+    /// The path given by the `extends` key, along with its span.
+    extends: Option<(String, Span)>,
+    /// The code this configuration applies to, if it is an `[[overrides]]` entry.
+    selector: Option<OverrideSelector>,
+    /// The lints allowed by the `allow` key of an `[[overrides]]` entry, each with the span of its
+    /// name, along with the span of the key.
+    allow: Option<(Vec<(String, Span)>, Span)>,
+    /// The `[[overrides]]` entries, in the order they must be applied.
+    overrides: Vec<TryConf>,
     errors: Vec<ConfError>,
     warnings: Vec<ConfError>,
 }
 
+/// Selects the code an `[[overrides]]` entry of the configuration file applies to. The lints of
+/// the `allow` key are allowed in the files matching `paths` and in the test code, while the
+/// configuration values of the entries with `paths` are looked up per file with [`Conf::in_file`].
+struct OverrideSelector {
+    /// Globs matched against the path of the files, relative to the package directory.
+    paths: Vec<String>,
+    /// Whether the entry only applies to crates compiled with `--test`. The lints of its `allow`
+    /// key are only allowed in the test code of these crates.
+    cfg_test: bool,
+    /// The span of the first selecting key, used for diagnostics.
+    span: Span,
+}
+
+impl OverrideSelector {
+    fn new(span: Span) -> Self {
+        Self {
+            paths: Vec::new(),
+            cfg_test: false,
+            span,
+        }
+    }
+}
+
 impl TryConf {
     fn from_toml_error(file: &SourceFile, error: &toml::de::Error) -> Self {
         Self {
-            conf: Conf::default(),
-            value_spans: HashMap::default(),
             errors: vec![ConfError::from_toml(file, error)],
-            warnings: vec![],
+            ..Self::default()
         }
     }
 }
@@ -192,17 +230,17 @@ macro_rules! deserialize {
         (value, value_span)
     }};
 
-    ($map:expr, $ty:ty, $errors:expr, $file:expr, $replacements_allowed:expr) => {{
+    // Deserializes an array of `$elem`s, setting the span of each element with `set_span`
+    ($map:expr, $ty:ty, $errors:expr, $file:expr, spanned $elem:ty) => {{
         let array = $map.next_value::<Vec<toml::Spanned<toml::Value>>>()?;
-        let mut disallowed_paths_span = Range {
+        let mut elements_span = Range {
             start: usize::MAX,
             end: usize::MIN,
         };
-        let mut disallowed_paths = Vec::new();
+        let mut elements = Vec::new();
         for raw_value in array {
             let value_span = raw_value.span();
-            let mut disallowed_path = match DisallowedPath::<$replacements_allowed>::deserialize(raw_value.into_inner())
-            {
+            let mut element = match <$elem>::deserialize(raw_value.into_inner()) {
                 Err(e) => {
                     $errors.push(ConfError::spanned(
                         $file,
@@ -212,14 +250,44 @@ macro_rules! deserialize {
                     ));
                     continue;
                 },
-                Ok(disallowed_path) => disallowed_path,
+                Ok(element) => element,
             };
-            disallowed_paths_span = union(&disallowed_paths_span, &value_span);
-            disallowed_path.set_span(span_from_toml_range($file, value_span));
-            disallowed_paths.push(disallowed_path);
+            elements_span = union(&elements_span, &value_span);
+            element.set_span(span_from_toml_range($file, value_span));
+            elements.push(element);
         }
-        (disallowed_paths, disallowed_paths_span)
+        (elements, elements_span)
     }};
+
+    // Deserializes a table of `$value`s, setting the span of each entry with `set_span`
+    ($map:expr, $ty:ty, $errors:expr, $file:expr, spanned_values $value:ty) => {{
+        let raw_table =
+            $map.next_value::<toml::Spanned<BTreeMap<toml::Spanned<String>, toml::Spanned<toml::Value>>>>()?;
+        let table_span = raw_table.span();
+        let mut table = BTreeMap::new();
+        for (key, raw_value) in raw_table.into_inner() {
+            let value_span = raw_value.span();
+            let mut value = match <$value>::deserialize(raw_value.into_inner()) {
+                Err(e) => {
+                    $errors.push(ConfError::spanned(
+                        $file,
+                        e.to_string().replace('\n', " ").trim(),
+                        None,
+                        value_span,
+                    ));
+                    continue;
+                },
+                Ok(value) => value,
+            };
+            value.set_span(span_from_toml_range($file, union(&key.span(), &value_span)));
+            table.insert(key.into_inner(), value);
+        }
+        (table, table_span)
+    }};
+
+    ($map:expr, $ty:ty, $errors:expr, $file:expr, $replacements_allowed:expr) => {
+        deserialize!($map, $ty, $errors, $file, spanned DisallowedPath<$replacements_allowed>)
+    };
 }
 
 macro_rules! define_Conf {
@@ -228,6 +296,8 @@ macro_rules! define_Conf {
         $(#[conf_deprecated($dep:literal, $new_conf:ident)])?
         $(#[default_text = $default_text:expr])?
         $(#[disallowed_paths_allow_replacements = $replacements_allowed:expr])?
+        $(#[spanned_elements = $elem:ty])?
+        $(#[spanned_values = $value:ty])?
         $(#[lints($($for_lints:ident),* $(,)?)])?
         $name:ident: $ty:ty = $default:expr,
     )*) => {
@@ -250,10 +320,18 @@ macro_rules! define_Conf {
         #[derive(Deserialize)]
         #[serde(field_identifier, rename_all = "kebab-case")]
         #[expect(non_camel_case_types)]
-        enum Field { $($name,)* third_party, }
+        enum Field { $($name,)* extends, overrides, paths, cfg_test, allow, third_party, }
 
         struct ConfVisitor<'a>(&'a SourceFile);
 
+        impl<'de> DeserializeSeed<'de> for ConfVisitor<'_> {
+            type Value = TryConf;
+
+            fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+                deserializer.deserialize_map(self)
+            }
+        }
+
         impl<'de> Visitor<'de> for ConfVisitor<'_> {
             type Value = TryConf;
 
@@ -263,6 +341,10 @@ macro_rules! define_Conf {
 
             fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error> where V: MapAccess<'de> {
                 let mut value_spans = HashMap::new();
+                let mut extends = None;
+                let mut selector: Option<OverrideSelector> = None;
+                let mut allow = None;
+                let mut overrides = Vec::new();
                 let mut errors = Vec::new();
                 let mut warnings = Vec::new();
 
@@ -285,14 +367,17 @@ macro_rules! define_Conf {
                             // Is this a deprecated field, i.e., is `$dep` set? If so, push a warning.
                             $(warnings.push(ConfError::spanned(self.0, format!("deprecated field `{}`. {}", name.get_ref(), $dep), None, name.span()));)?
                             let (value, value_span) =
-                                deserialize!(map, $ty, errors, self.0 $(, $replacements_allowed)?);
+                                deserialize!(
+                                    map, $ty, errors, self.0
+                                    $(, $replacements_allowed)? $(, spanned $elem)? $(, spanned_values $value)?
+                                );
                             // Was this field set previously?
                             if $name.is_some() {
                                 errors.push(ConfError::spanned(self.0, format!("duplicate field `{}`", name.get_ref()), None, name.span()));
                                 continue;
                             }
                             $name = Some(value);
-                            value_spans.insert(name.get_ref().as_str().to_string(), value_span);
+                            value_spans.insert(stringify!($name), span_from_toml_range(self.0, value_span.clone()));
                             // If this is a deprecated field, was the new field (`$new_conf`) set previously?
                             // Note that `$new_conf` is one of the defined `$name`s.
                             $(match $new_conf {
@@ -300,15 +385,77 @@ macro_rules! define_Conf {
                                     "duplicate field `", stringify!($new_conf),
                                     "` (provided as `", stringify!($name), "`)"
                                 ), None, name.span())),
-                                None => $new_conf = $name.clone(),
+                                None => {
+                                    $new_conf = $name.clone();
+                                    value_spans.insert(stringify!($new_conf), span_from_toml_range(self.0, value_span));
+                                },
                             })?
                         })*
+                        Field::extends => {
+                            let path = map.next_value::<toml::Spanned<String>>()?;
+                            let span = span_from_toml_range(self.0, path.span());
+                            extends = Some((path.into_inner(), span));
+                        },
+                        Field::overrides => {
+                            let entries = map.next_value_seed(OverridesVisitor(self.0))?;
+                            overrides.extend(entries.into_iter().filter_map(|entry| {
+                                check_override(self.0, name.span(), entry, &mut errors, &mut warnings)
+                            }));
+                        },
+                        Field::paths => {
+                            let paths = map.next_value::<toml::Spanned<Vec<String>>>()?;
+                            if paths.get_ref().is_empty() {
+                                errors.push(ConfError::spanned(self.0, "`paths` cannot be empty", None, paths.span()));
+                            }
+                            selector
+                                .get_or_insert_with(|| OverrideSelector::new(span_from_toml_range(self.0, name.span())))
+                                .paths = paths.into_inner();
+                        },
+                        Field::cfg_test => {
+                            let cfg_test = map.next_value::<bool>()?;
+                            selector
+                                .get_or_insert_with(|| OverrideSelector::new(span_from_toml_range(self.0, name.span())))
+                                .cfg_test = cfg_test;
+                        },
+                        Field::allow => {
+                            let lints = map.next_value::<Vec<toml::Spanned<String>>>()?;
+                            allow = Some((lint_names(self.0, lints), span_from_toml_range(self.0, name.span())));
+                        },
                         // ignore contents of the third_party key
                         Field::third_party => drop(map.next_value::<IgnoredAny>())
                     }
                 }
                 let conf = Conf { $($name: $name.unwrap_or_else(defaults::$name),)* };
-                Ok(TryConf { conf, value_spans, errors, warnings })
+                Ok(TryConf { conf, value_spans, extends, selector, allow, overrides, errors, warnings })
+            }
+        }
+
+        impl TryConf {
+            /// Merges `parent`, the configuration named by the `extends` key, into `self`. Values set in
+            /// `self` take precedence, except for lists which are appended to the inherited ones and
+            /// tables which are merged with them.
+            fn inherit(&mut self, parent: TryConf) {
+                let TryConf {
+                    conf: parent_conf,
+                    value_spans: parent_spans,
+                    overrides,
+                    errors,
+                    warnings,
+                    ..
+                } = parent;
+                $(
+                    if let Some(&span) = parent_spans.get(stringify!($name)) {
+                        if self.value_spans.contains_key(stringify!($name)) {
+                            Inherit::inherit(&mut self.conf.$name, parent_conf.$name);
+                        } else {
+                            self.conf.$name = parent_conf.$name;
+                            self.value_spans.insert(stringify!($name), span);
+                        }
+                    }
+                )*
+                self.overrides.splice(0..0, overrides);
+                self.errors.splice(0..0, errors);
+                self.warnings.splice(0..0, warnings);
             }
         }
 
@@ -326,6 +473,147 @@ macro_rules! define_Conf {
     };
 }
 
+/// Moves the errors of the `[[overrides]]` entry `entry`, given by the key at `key_span`, to
+/// `errors` and `warnings`, and returns the entry if it is valid.
+fn check_override(
+    file: &SourceFile,
+    key_span: Range<usize>,
+    mut entry: TryConf,
+    errors: &mut Vec<ConfError>,
+    warnings: &mut Vec<ConfError>,
+) -> Option<TryConf> {
+    errors.append(&mut entry.errors);
+    warnings.append(&mut entry.warnings);
+    if let Some((_, span)) = &entry.extends {
+        errors.push(ConfError {
+            message: "`extends` cannot be set in `[[overrides]]` entries".into(),
+            suggestion: None,
+            span: *span,
+        });
+    }
+    match &entry.selector {
+        None => errors.push(ConfError::spanned(
+            file,
+            "`[[overrides]]` entries must set `paths` or `cfg-test`",
+            None,
+            key_span,
+        )),
+        Some(selector) if !entry.overrides.is_empty() => errors.push(ConfError {
+            message: "`[[overrides]]` entries cannot be nested".into(),
+            suggestion: None,
+            span: selector.span,
+        }),
+        Some(selector) if selector.cfg_test && !entry.value_spans.is_empty() => {
+            // The test code is only known once the configuration is read
+            let mut spans = entry.value_spans.into_values().collect::<Vec<_>>();
+            spans.sort();
+            errors.extend(spans.into_iter().map(|span| {
+                ConfError {
+                    message:
+                        "configuration values cannot be set in `[[overrides]]` entries setting `cfg-test`, only `allow`"
+                            .into(),
+                    suggestion: None,
+                    span,
+                }
+            }));
+        },
+        Some(_) if entry.value_spans.keys().any(|name| !FILE_SCOPED_OPTIONS.contains(name)) => {
+            // The other options are read once for the whole crate
+            let mut values = entry
+                .value_spans
+                .into_iter()
+                .filter(|(name, _)| !FILE_SCOPED_OPTIONS.contains(name))
+                .map(|(name, span)| (span, name))
+                .collect::<Vec<_>>();
+            values.sort();
+            errors.extend(values.into_iter().map(|(span, name)| ConfError {
+                message: format!(
+                    "`{}` cannot be set per file in `[[overrides]]` entries",
+                    name.replace('_', "-")
+                ),
+                suggestion: None,
+                span,
+            }));
+        },
+        Some(_) => return Some(entry),
+    }
+    None
+}
+
+/// Deserializes the `[[overrides]]` array of tables.
+struct OverridesVisitor<'a>(&'a SourceFile);
+
+impl<'de> DeserializeSeed<'de> for OverridesVisitor<'_> {
+    type Value = Vec<TryConf>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for OverridesVisitor<'_> {
+    type Value = Vec<TryConf>;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter.write_str("an array of tables")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut entries = Vec::new();
+        while let Some(mut entry) = seq.next_element_seed(ConfVisitor(self.0))? {
+            postprocess(&mut entry, self.0);
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+}
+
+/// How a value set in a configuration file is combined with the one inherited through `extends`.
+trait Inherit {
+    fn inherit(&mut self, parent: Self);
+}
+
+impl<T> Inherit for Vec<T> {
+    fn inherit(&mut self, mut parent: Self) {
+        parent.append(self);
+        *self = parent;
+    }
+}
+
+impl<K: Ord, V> Inherit for BTreeMap<K, V> {
+    fn inherit(&mut self, mut parent: Self) {
+        parent.append(self);
+        *self = parent;
+    }
+}
+
+macro_rules! inherit_by_overriding {
+    ($($ty:ty),* $(,)?) => {
+        $(impl Inherit for $ty {
+            fn inherit(&mut self, _: Self) {}
+        })*
+    };
+}
+
+inherit_by_overriding!(
+    bool,
+    u64,
+    usize,
+    Option<u64>,
+    Msrv,
+    InherentImplLintScope,
+    MatchLintBehaviour,
+    PubUnderscoreFieldsBehaviour,
+    SourceItemOrdering,
+    SourceItemOrderingModuleItemGroupings,
+    SourceItemOrderingTraitAssocItemKinds,
+    SourceItemOrderingWithinModuleItemGroupings,
+    TraitImplItemOrder,
+);
+
 fn union(x: &Range<usize>, y: &Range<usize>) -> Range<usize> {
     Range {
         start: cmp::min(x.start, y.start),
@@ -351,10 +639,10 @@ define_Conf! {
     #[lints(absolute_paths)]
     absolute_paths_max_segments: u64 = 2,
     /// Whether to accept a safety comment to be placed above the attributes for the `unsafe` block
-    #[lints(undocumented_unsafe_blocks)]
+    #[lints(incomplete_safety_comment, undocumented_unsafe_blocks)]
     accept_comment_above_attributes: bool = true,
     /// Whether to accept a safety comment to be placed above the statement containing the `unsafe` block
-    #[lints(undocumented_unsafe_blocks)]
+    #[lints(incomplete_safety_comment, undocumented_unsafe_blocks)]
     accept_comment_above_statement: bool = true,
     /// Don't lint when comparing the result of a modulo operation to zero.
     #[lints(modulo_arithmetic)]
@@ -475,6 +763,25 @@ define_Conf! {
     /// are already allowed by default.
     #[lints(wildcard_imports)]
     allowed_wildcard_imports: Vec<String> = Vec::new(),
+    /// The versions of the dependencies in which their items were added, checked against the
+    /// minimum versions allowed by the requirements of `Cargo.toml`.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// api-versions = [
+    ///     { path = "tokio::task::JoinSet::try_join_next", since = "1.38.0" },
+    ///     # An item applies to all the items it contains, like the methods of a type
+    ///     { path = "tokio::sync::SetOnce", since = "1.47.0" },
+    /// ]
+    /// ```
+    ///
+    /// **Fields:**
+    /// - `path` (required): the fully qualified path to the item, which can contain `*` wildcards
+    ///   in its last segment
+    /// - `since` (required): the version of the crate of the item which added it
+    #[lints(incompatible_dependency_version)]
+    api_versions: Vec<ApiVersion> = Vec::new(),
     /// Suppress checking of the passed type names in all types of operations.
     ///
     /// If a specific operation is desired, consider using `arithmetic_side_effects_allowed_binary` or `arithmetic_side_effects_allowed_unary` instead.
@@ -551,6 +858,19 @@ define_Conf! {
     /// Use the Disallowed Names lint instead
     #[conf_deprecated("Please use `disallowed-names` instead", disallowed_names)]
     blacklisted_names: Vec<String> = Vec::new(),
+    /// The maximum number of warnings of each lint, as a table mapping the names of the lints to
+    /// their budget. Clippy fails when a lint emits more warnings than its budget. `cargo clippy`
+    /// counts the warnings of all the crates checked with the configuration file together.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// [budgets]
+    /// "clippy::unwrap_used" = 120
+    /// expect_used = 40
+    /// ```
+    #[spanned_values = Budget]
+    budgets: BTreeMap<String, Budget> = BTreeMap::new(),
     /// For internal testing only, ignores the current `publish` settings in the Cargo manifest.
     #[lints(cargo_common_metadata)]
     cargo_ignore_publish: bool = false,
@@ -630,6 +950,8 @@ define_Conf! {
     ///
     /// **Fields:**
     /// - `path` (required): the fully qualified path to the macro that should be disallowed
+    ///   A path to a module, or one whose last segment contains `*` wildcards, disallows all the
+    ///   matching macros, e.g. `std::debug_*`
     /// - `reason` (optional): explanation why this macro is disallowed
     /// - `replacement` (optional): suggested alternative macro
     /// - `allow-invalid` (optional, `false` by default): when set to `true`, it will ignore this entry
@@ -641,8 +963,17 @@ define_Conf! {
     ///
     /// **Fields:**
     /// - `path` (required): the fully qualified path to the method that should be disallowed
+    ///   A path to a module, or one whose last segment contains `*` wildcards, disallows all the
+    ///   matching methods, e.g. `std::process::*` or `slice::*_unchecked`
     /// - `reason` (optional): explanation why this method is disallowed
     /// - `replacement` (optional): suggested alternative method
+    /// - `self-type` (optional): only disallow the method when its `Self` type is the given type, e.g.
+    ///   `std::fs::File`. Generic arguments can be given, with `_` matching any type: `std::vec::Vec<u8>`.
+    ///   With a `self-type`, `path` can also name a method of a trait impl through the type of the impl,
+    ///   e.g. `std::fs::File::flush`
+    /// - `args` (optional): only disallow the calls where the arguments have one of the given values, e.g.
+    ///   `{ 0 = ["sh", "bash"] }`. Arguments are given by their index, `self` included, and values can be
+    ///   strings, integers, booleans or paths to constants and unit variants: `{ path = "std::sync::atomic::Ordering::Relaxed" }`
     /// - `allow-invalid` (optional, `false` by default): when set to `true`, it will ignore this entry
     ///   if the path doesn't exist, instead of emitting an error
     #[disallowed_paths_allow_replacements = true]
@@ -653,10 +984,24 @@ define_Conf! {
     /// default configuration of Clippy. By default, any configuration will replace the default value.
     #[lints(disallowed_names)]
     disallowed_names: Vec<String> = DEFAULT_DISALLOWED_NAMES.iter().map(ToString::to_string).collect(),
+    /// The list of traits which may not be implemented, written as fully qualified paths.
+    ///
+    /// **Fields:**
+    /// - `path` (required): the fully qualified path to the trait that should not be implemented
+    /// - `reason` (optional): explanation why this trait may not be implemented
+    /// - `self-type` (optional): only disallow the implementations for the given `Self` type, e.g.
+    ///   `std::vec::Vec<u8>`, with `_` matching any type
+    /// - `allow-invalid` (optional, `false` by default): when set to `true`, it will ignore this entry
+    ///   if the path doesn't exist, instead of emitting an error
+    #[disallowed_paths_allow_replacements = false]
+    #[lints(disallowed_trait_impls)]
+    disallowed_trait_impls: Vec<DisallowedPathWithoutReplacement> = Vec::new(),
     /// The list of disallowed types, written as fully qualified paths.
     ///
     /// **Fields:**
     /// - `path` (required): the fully qualified path to the type that should be disallowed
+    ///   A path to a module, or one whose last segment contains `*` wildcards, disallows all the
+    ///   matching types, e.g. `std::collections::Hash*`
     /// - `reason` (optional): explanation why this type is disallowed
     /// - `replacement` (optional): suggested alternative type
     /// - `allow-invalid` (optional, `false` by default): when set to `true`, it will ignore this entry
@@ -708,6 +1053,17 @@ define_Conf! {
     /// The maximum byte size a `Future` can have, before it triggers the `clippy::large_futures` lint
     #[lints(large_futures)]
     future_size_threshold: u64 = 16 * 1024,
+    /// The list of types which may not be held across calls to some functions, e.g. lock guards
+    /// across blocking functions.
+    ///
+    /// **Fields:**
+    /// - `path` (required): the fully qualified path to the type
+    /// - `callees` (required): the fully qualified paths to the functions, which can contain `*`
+    ///   wildcards in their last segment, e.g. `std::sync::mpsc::Receiver::recv*`
+    /// - `reason` (optional): explanation why the type may not be held across these calls
+    #[spanned_elements = HeldAcrossCall]
+    #[lints(held_across_call)]
+    held_across_call: Vec<HeldAcrossCall> = Vec::new(),
     /// A list of paths to types that should be treated as if they do not contain interior mutability
     #[lints(borrow_interior_mutable_const, declare_interior_mutable_const, ifs_same_cond, mutable_key_type)]
     ignore_interior_mutability: Vec<String> = Vec::from(["bytes::Bytes".into()]),
@@ -766,6 +1122,10 @@ define_Conf! {
     /// crate. For example, `pub(crate)` items.
     #[lints(missing_docs_in_private_items)]
     missing_docs_in_crate_items: bool = false,
+    /// The number of nested calls to the functions of the crate followed to find the panics of a
+    /// function. `0` only finds the panics in the body of the function.
+    #[lints(missing_panics_doc)]
+    missing_panics_doc_depth: u64 = 0,
     /// The named groupings of different source item kinds within modules.
     #[lints(arbitrary_source_item_ordering)]
     module_item_order_groupings: SourceItemOrderingModuleItemGroupings = DEFAULT_MODULE_ITEM_ORDERING_GROUPS.into(),
@@ -797,6 +1157,7 @@ define_Conf! {
         from_over_into,
         if_then_some_else_none,
         implicit_saturating_sub,
+        incompatible_dependency_rust_version,
         index_refutable_slice,
         inefficient_to_string,
         io_other_error,
@@ -1020,63 +1381,319 @@ pub fn lookup_conf_file() -> io::Result<(Option<PathBuf>, Vec<String>)> {
     }
 }
 
+/// Checks if the configuration file at `path`, found by [`lookup_conf_file`], may be shared by
+/// several packages: it was found in a parent directory of the one it was looked up from, or it is
+/// next to the manifest of a workspace.
+pub fn is_shared_conf_file(path: &Path) -> bool {
+    let Some(dir) = path.parent() else {
+        return false;
+    };
+    let lookup_dir = env::var_os("CLIPPY_CONF_DIR")
+        .or_else(|| env::var_os("CARGO_MANIFEST_DIR"))
+        .map_or_else(|| PathBuf::from("."), PathBuf::from)
+        .canonicalize();
+    lookup_dir.is_ok_and(|lookup_dir| lookup_dir != dir)
+        || fs::read_to_string(dir.join("Cargo.toml")).is_ok_and(|manifest| {
+            manifest
+                .parse::<toml::Table>()
+                .is_ok_and(|manifest| manifest.contains_key("workspace"))
+        })
+}
+
 fn deserialize(file: &SourceFile) -> TryConf {
     match toml::de::Deserializer::new(file.src.as_ref().unwrap()).deserialize_map(ConfVisitor(file)) {
         Ok(mut conf) => {
-            extend_vec_if_indicator_present(&mut conf.conf.disallowed_names, DEFAULT_DISALLOWED_NAMES);
-            extend_vec_if_indicator_present(&mut conf.conf.allowed_prefixes, DEFAULT_ALLOWED_PREFIXES);
-            extend_vec_if_indicator_present(
-                &mut conf.conf.allow_renamed_params_for,
-                DEFAULT_ALLOWED_TRAITS_WITH_RENAMED_PARAMS,
-            );
-
-            // Confirms that the user has not accidentally configured ordering requirements for groups that
-            // aren't configured.
-            if let SourceItemOrderingWithinModuleItemGroupings::Custom(groupings) =
-                &conf.conf.module_items_ordered_within_groupings
-            {
-                for grouping in groupings {
-                    if !conf.conf.module_item_order_groupings.is_grouping(grouping) {
-                        // Since this isn't fixable by rustfix, don't emit a `Suggestion`. This just adds some useful
-                        // info for the user instead.
-
-                        let names = conf.conf.module_item_order_groupings.grouping_names();
-                        let suggestion = suggest_candidate(grouping, names.iter().map(String::as_str))
-                            .map(|s| format!(" perhaps you meant `{s}`?"))
-                            .unwrap_or_default();
-                        let names = names.iter().map(|s| format!("`{s}`")).join(", ");
-                        let message = format!(
-                            "unknown ordering group: `{grouping}` was not specified in `module-items-ordered-within-groupings`,{suggestion} expected one of: {names}"
-                        );
-
-                        let span = conf
-                            .value_spans
-                            .get("module_item_order_groupings")
-                            .cloned()
-                            .unwrap_or_default();
-                        conf.errors.push(ConfError::spanned(file, message, None, span));
-                    }
-                }
-            }
-
-            // TODO: THIS SHOULD BE TESTED, this comment will be gone soon
-            if conf.conf.allowed_idents_below_min_chars.iter().any(|e| e == "..") {
-                conf.conf
-                    .allowed_idents_below_min_chars
-                    .extend(DEFAULT_ALLOWED_IDENTS_BELOW_MIN_CHARS.iter().map(ToString::to_string));
+            postprocess(&mut conf, file);
+            if let Some(selector) = &conf.selector {
+                conf.errors.push(ConfError {
+                    message: "`paths` and `cfg-test` can only be set in `[[overrides]]` entries".into(),
+                    suggestion: None,
+                    span: selector.span,
+                });
             }
-            if conf.conf.doc_valid_idents.iter().any(|e| e == "..") {
-                conf.conf
-                    .doc_valid_idents
-                    .extend(DEFAULT_DOC_VALID_IDENTS.iter().map(ToString::to_string));
+            if let Some((_, span)) = &conf.allow {
+                conf.errors.push(ConfError {
+                    message: "`allow` can only be set in `[[overrides]]` entries".into(),
+                    suggestion: None,
+                    span: *span,
+                });
             }
-
             conf
         },
         Err(e) => TryConf::from_toml_error(file, &e),
     }
 }
 
+/// Expands the `".."` indicators and validates the values which depend on each other.
+fn postprocess(conf: &mut TryConf, file: &SourceFile) {
+    extend_vec_if_indicator_present(&mut conf.conf.disallowed_names, DEFAULT_DISALLOWED_NAMES);
+    extend_vec_if_indicator_present(&mut conf.conf.allowed_prefixes, DEFAULT_ALLOWED_PREFIXES);
+    extend_vec_if_indicator_present(
+        &mut conf.conf.allow_renamed_params_for,
+        DEFAULT_ALLOWED_TRAITS_WITH_RENAMED_PARAMS,
+    );
+
+    // Confirms that the user has not accidentally configured ordering requirements for groups that
+    // aren't configured.
+    if let SourceItemOrderingWithinModuleItemGroupings::Custom(groupings) =
+        &conf.conf.module_items_ordered_within_groupings
+    {
+        for grouping in groupings {
+            if !conf.conf.module_item_order_groupings.is_grouping(grouping) {
+                // Since this isn't fixable by rustfix, don't emit a `Suggestion`. This just adds some useful
+                // info for the user instead.
+
+                let names = conf.conf.module_item_order_groupings.grouping_names();
+                let suggestion = suggest_candidate(grouping, names.iter().map(String::as_str))
+                    .map(|s| format!(" perhaps you meant `{s}`?"))
+                    .unwrap_or_default();
+                let names = names.iter().map(|s| format!("`{s}`")).join(", ");
+                let message = format!(
+                    "unknown ordering group: `{grouping}` was not specified in `module-items-ordered-within-groupings`,{suggestion} expected one of: {names}"
+                );
+
+                let span = conf
+                    .value_spans
+                    .get("module_item_order_groupings")
+                    .copied()
+                    .unwrap_or_else(|| span_from_toml_range(file, 0..0));
+                conf.errors.push(ConfError {
+                    message,
+                    suggestion: None,
+                    span,
+                });
+            }
+        }
+    }
+
+    // TODO: THIS SHOULD BE TESTED, this comment will be gone soon
+    if conf.conf.allowed_idents_below_min_chars.iter().any(|e| e == "..") {
+        conf.conf
+            .allowed_idents_below_min_chars
+            .extend(DEFAULT_ALLOWED_IDENTS_BELOW_MIN_CHARS.iter().map(ToString::to_string));
+    }
+    if conf.conf.doc_valid_idents.iter().any(|e| e == "..") {
+        conf.conf
+            .doc_valid_idents
+            .extend(DEFAULT_DOC_VALID_IDENTS.iter().map(ToString::to_string));
+    }
+}
+
+/// Deserializes the configuration file `file`, read from `path`, and merges into it the files it
+/// `extends`, recursively. `chain` holds the canonical paths of the files being read to detect
+/// cycles.
+fn deserialize_extended(sess: &Session, path: &Path, file: &SourceFile, chain: &mut Vec<PathBuf>) -> TryConf {
+    let mut conf = deserialize(file);
+    let Some((extends, span)) = conf.extends.take() else {
+        return conf;
+    };
+
+    let parent_path = path.parent().unwrap_or_else(|| Path::new("")).join(&extends);
+    let parent = match parent_path.canonicalize() {
+        Ok(parent_path) if chain.contains(&parent_path) => Err(format!(
+            "`extends` cycle detected, `{}` is already being read",
+            parent_path.display()
+        )),
+        Ok(parent_path) => match sess.source_map().load_file(&parent_path) {
+            Ok(parent_file) => {
+                chain.push(parent_path.clone());
+                let parent = deserialize_extended(sess, &parent_path, &parent_file, chain);
+                chain.pop();
+                Ok(parent)
+            },
+            Err(error) => Err(format!("failed to read `{}`: {error}", parent_path.display())),
+        },
+        Err(error) => Err(format!("failed to read `{}`: {error}", parent_path.display())),
+    };
+
+    match parent {
+        Ok(parent) => conf.inherit(parent),
+        Err(message) => conf.errors.push(ConfError {
+            message,
+            suggestion: None,
+            span,
+        }),
+    }
+    conf
+}
+
+/// Lists the options which `[[overrides]]` entries with `paths` can set for the files they match,
+/// and generates their accessors on [`FileConf`]. The lints reading these options look up their
+/// value for the code they check with [`Conf::in_file`].
+macro_rules! file_scoped_options {
+    ($($name:ident: $ty:ty,)*) => {
+        const FILE_SCOPED_OPTIONS: &[&str] = &[$(stringify!($name),)*];
+
+        impl FileConf {
+            $(
+                pub fn $name(self) -> &'static $ty {
+                    self.get(stringify!($name), |conf| &conf.$name)
+                }
+            )*
+        }
+    };
+}
+
+file_scoped_options! {
+    disallowed_methods: Vec<DisallowedPath>,
+    disallowed_names: Vec<String>,
+    disallowed_types: Vec<DisallowedPath>,
+    too_many_arguments_threshold: u64,
+    too_many_lines_threshold: u64,
+    type_complexity_threshold: u64,
+}
+
+/// The configuration of a file, returned by [`Conf::in_file`].
+#[derive(Clone, Copy)]
+pub struct FileConf {
+    conf: &'static Conf,
+    /// The `[[overrides]]` entries whose `paths` match the file, in order.
+    overrides: &'static [&'static FileOverride],
+}
+
+impl FileConf {
+    /// Returns the value of the option `name`, given by `value`, set by the last entry matching the
+    /// file which sets it, or else by the configuration of the crate.
+    fn get<T>(self, name: &str, value: fn(&'static Conf) -> &'static T) -> &'static T {
+        match self.overrides.iter().rev().find(|entry| entry.names.contains(&name)) {
+            Some(entry) => value(&entry.conf),
+            None => value(self.conf),
+        }
+    }
+}
+
+/// The configuration values set by an `[[overrides]]` entry with `paths`.
+struct FileOverride {
+    paths: Vec<String>,
+    conf: Conf,
+    /// The names of the options set by the entry.
+    names: Vec<&'static str>,
+}
+
+/// The `[[overrides]]` entries with `paths` which set configuration values.
+struct FileOverrides {
+    /// The directory the globs of the entries are relative to, the package directory.
+    root: PathBuf,
+    entries: Vec<FileOverride>,
+    /// The entries matching each file of the crate, found on the first lookup.
+    files: Mutex<FxHashMap<StableSourceFileId, &'static [&'static FileOverride]>>,
+}
+
+static FILE_OVERRIDES: OnceLock<FileOverrides> = OnceLock::new();
+
+impl Conf {
+    /// Returns the configuration of the file of `span`, or of the macro call it is expanded from,
+    /// with the values set by the `[[overrides]]` entries whose `paths` match the file. Only the
+    /// options listed by `file_scoped_options!` can be set by the entries, and have accessors on
+    /// [`FileConf`].
+    pub fn in_file(&'static self, sm: &SourceMap, span: Span) -> FileConf {
+        let Some(overrides) = FILE_OVERRIDES.get() else {
+            return FileConf {
+                conf: self,
+                overrides: &[],
+            };
+        };
+        let span = span.source_callsite();
+        let file = sm.lookup_source_file(span.lo());
+        let matching = *overrides
+            .files
+            .lock()
+            .unwrap()
+            .entry(file.stable_id)
+            .or_insert_with(|| {
+                let path = baseline::file(sm, &overrides.root, span);
+                let matching = overrides
+                    .entries
+                    .iter()
+                    .filter(|entry| entry.paths.iter().any(|glob| glob_matches(glob, &path)))
+                    .collect::<Vec<_>>();
+                // The entries matching a file are kept for the whole compilation
+                if matching.is_empty() { &[] } else { matching.leak() }
+            });
+        FileConf {
+            conf: self,
+            overrides: matching,
+        }
+    }
+}
+
+impl TryConf {
+    /// Passes the lints allowed by the `allow` key of the `[[overrides]]` entries to
+    /// [`allowed_lints::track`], and keeps the values set by the entries with `paths` for
+    /// [`Conf::in_file`].
+    fn track_overrides(&mut self, sess: &Session) {
+        if self.overrides.is_empty() {
+            return;
+        }
+        let root = package_dir().unwrap_or_default();
+        let mut allowed = AllowedLints {
+            root: root.clone(),
+            ..AllowedLints::default()
+        };
+        let mut file_overrides = Vec::new();
+        for entry in mem::take(&mut self.overrides) {
+            let Some(selector) = &entry.selector else {
+                continue;
+            };
+            if !selector.paths.is_empty() && !entry.value_spans.is_empty() {
+                file_overrides.push(FileOverride {
+                    paths: selector.paths.clone(),
+                    conf: entry.conf,
+                    names: entry.value_spans.into_keys().collect(),
+                });
+            }
+            if let Some((lints, _)) = entry.allow {
+                allowed.names.extend(lints.iter().cloned());
+                if selector.cfg_test && !sess.opts.test {
+                    // The test code is only compiled with `--test`
+                } else if !selector.paths.is_empty() {
+                    allowed.in_files.push(AllowedInFiles {
+                        paths: selector.paths.clone(),
+                        lints,
+                        only_tests: selector.cfg_test,
+                    });
+                } else {
+                    allowed.in_tests.extend(lints);
+                }
+            }
+        }
+        allowed_lints::track(allowed);
+        if !file_overrides.is_empty() {
+            let _ = FILE_OVERRIDES.set(FileOverrides {
+                root,
+                entries: file_overrides,
+                files: Mutex::default(),
+            });
+        }
+    }
+}
+
+/// Returns the canonical package directory, `CARGO_MANIFEST_DIR` or, failing that, the current
+/// directory.
+fn package_dir() -> Option<PathBuf> {
+    env::var_os("CARGO_MANIFEST_DIR")
+        .map_or_else(env::current_dir, |dir| Ok(PathBuf::from(dir)))
+        .and_then(|dir| dir.canonicalize())
+        .ok()
+}
+
+/// Returns the names of the lints given in the configuration file as the lowercase name of each
+/// lint with its `clippy::` prefix, which can be omitted in the file, along with their span.
+fn lint_names(file: &SourceFile, lints: Vec<toml::Spanned<String>>) -> Vec<(String, Span)> {
+    lints
+        .into_iter()
+        .map(|lint| {
+            let span = span_from_toml_range(file, lint.span());
+            let name = lint.into_inner().replace('-', "_");
+            if name.starts_with("clippy::") {
+                (name, span)
+            } else {
+                (format!("clippy::{name}"), span)
+            }
+        })
+        .collect()
+}
+
 fn extend_vec_if_indicator_present(vec: &mut Vec<String>, default: &[&str]) {
     if vec.contains(&"..".to_string()) {
         vec.extend(default.iter().map(ToString::to_string));
@@ -1102,14 +1719,13 @@ impl Conf {
             },
         }
 
-        let TryConf {
-            mut conf,
-            value_spans: _,
-            errors,
-            warnings,
-        } = match path {
+        let mut try_conf = match path {
             Ok((Some(path), _)) => match sess.source_map().load_file(path) {
-                Ok(file) => deserialize(&file),
+                Ok(file) => {
+                    // The paths of the chain are canonical, so that a cycle back to this file is detected
+                    let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+                    deserialize_extended(sess, path, &file, &mut vec![canonical])
+                },
                 Err(error) => {
                     sess.dcx().err(format!("failed to read `{}`: {error}", path.display()));
                     TryConf::default()
@@ -1117,6 +1733,13 @@ impl Conf {
             },
             _ => TryConf::default(),
         };
+        try_conf.track_overrides(sess);
+        let TryConf {
+            mut conf,
+            errors,
+            warnings,
+            ..
+        } = try_conf;
 
         conf.msrv.read_cargo(sess);
 
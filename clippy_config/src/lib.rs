@@ -19,5 +19,7 @@ mod conf;
 mod metadata;
 pub mod types;
 
-pub use conf::{Conf, get_configuration_metadata, lookup_conf_file, sanitize_explanation};
+pub use conf::{
+    Conf, FileConf, get_configuration_metadata, is_shared_conf_file, lookup_conf_file, sanitize_explanation,
+};
 pub use metadata::ClippyConfiguration;
@@ -1,14 +1,14 @@
-use clippy_utils::paths::{PathNS, find_crates, lookup_path};
+use clippy_utils::paths::{PathNS, find_crates, lookup_path, lookup_path_glob, lookup_path_str, lookup_trait_impl_fn};
 use rustc_data_structures::fx::FxHashMap;
 use rustc_errors::{Applicability, Diag};
 use rustc_hir::PrimTy;
 use rustc_hir::def::DefKind;
-use rustc_hir::def_id::DefIdMap;
-use rustc_middle::ty::TyCtxt;
+use rustc_hir::def_id::{DefId, DefIdMap};
+use rustc_middle::ty::{self, Ty, TyCtxt};
 use rustc_span::{Span, Symbol};
 use serde::de::{self, Deserializer, Visitor};
 use serde::{Deserialize, Serialize, ser};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 
 #[derive(Debug, Deserialize)]
@@ -18,6 +18,53 @@ pub struct Rename {
     pub rename: String,
 }
 
+/// A type which may not be held across calls to some functions, e.g. a lock guard and blocking
+/// functions.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HeldAcrossCall {
+    /// The path of the type.
+    pub path: String,
+    /// The paths of the functions, which may contain `*` wildcards like in `disallowed-methods`.
+    pub callees: Vec<String>,
+    pub reason: Option<String>,
+    /// The span of the entry, used for diagnostics.
+    #[serde(skip)]
+    pub span: Span,
+}
+
+impl HeldAcrossCall {
+    pub fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+}
+
+/// The maximum number of warnings of a lint, see the `budgets` configuration.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Budget {
+    pub budget: u64,
+    /// The span of the entry, used for diagnostics.
+    #[serde(skip)]
+    pub span: Span,
+}
+
+impl Budget {
+    pub fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+}
+
+/// The version of a dependency in which an item was added.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiVersion {
+    /// The path of the item, which may contain `*` wildcards like in `disallowed-methods`.
+    pub path: String,
+    /// The version of the crate of the item which added it.
+    pub since: String,
+}
+
 pub type DisallowedPathWithoutReplacement = DisallowedPath<false>;
 
 #[derive(Debug, Serialize)]
@@ -25,6 +72,12 @@ pub struct DisallowedPath<const REPLACEMENT_ALLOWED: bool = true> {
     path: String,
     reason: Option<String>,
     replacement: Option<String>,
+    /// Restricts the entry to the uses where the `Self` type is the given type, e.g. to disallow a
+    /// trait method for a single implementor.
+    self_type: Option<String>,
+    /// Restricts the entry to the calls where the arguments at the given indices, `self` included,
+    /// have one of the given values.
+    args: Vec<(usize, Vec<DisallowedArg>)>,
     /// Setting `allow_invalid` to true suppresses a warning if `path` does not refer to an existing
     /// definition.
     ///
@@ -47,10 +100,24 @@ impl<'de, const REPLACEMENT_ALLOWED: bool> Deserialize<'de> for DisallowedPath<R
         if !REPLACEMENT_ALLOWED && enum_.replacement().is_some() {
             return Err(de::Error::custom("replacement not allowed for this configuration"));
         }
+        let args = match enum_ {
+            DisallowedPathEnum::WithReason {
+                args: Some(ref args), ..
+            } => args
+                .iter()
+                .map(|(index, values)| match index.parse() {
+                    Ok(index) => Ok((index, values.clone())),
+                    Err(_) => Err(de::Error::custom(format!("invalid argument index `{index}`"))),
+                })
+                .collect::<Result<_, _>>()?,
+            _ => Vec::new(),
+        };
         Ok(Self {
             path: enum_.path().to_owned(),
             reason: enum_.reason().map(ToOwned::to_owned),
             replacement: enum_.replacement().map(ToOwned::to_owned),
+            self_type: enum_.self_type().map(ToOwned::to_owned),
+            args,
             allow_invalid: enum_.allow_invalid(),
             span: Span::default(),
         })
@@ -67,16 +134,120 @@ enum DisallowedPathEnum {
         path: String,
         reason: Option<String>,
         replacement: Option<String>,
+        #[serde(rename = "self-type")]
+        self_type: Option<String>,
+        args: Option<BTreeMap<String, Vec<DisallowedArg>>>,
         #[serde(rename = "allow-invalid")]
         allow_invalid: Option<bool>,
     },
 }
 
+/// A value an argument must have for a call to a disallowed method to be linted.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged, deny_unknown_fields)]
+pub enum DisallowedArg {
+    Bool(bool),
+    Int(i64),
+    Str(String),
+    /// A path to a constant, a static or a unit variant, e.g.
+    /// `std::sync::atomic::Ordering::Relaxed`.
+    Path {
+        path: String,
+    },
+}
+
+/// The type given as the `self-type` of a disallowed method or trait impl, e.g.
+/// `std::vec::Vec<u8>`.
+pub enum SelfType {
+    /// `_`, matches any type.
+    Any,
+    Path {
+        def_ids: Vec<DefId>,
+        prim_ty: Option<PrimTy>,
+        /// The leading generic arguments of the type, the remaining ones match any type.
+        args: Vec<SelfType>,
+    },
+}
+
+impl SelfType {
+    /// Parses the `self-type` of an entry, resolving the paths of its types.
+    ///
+    /// # Errors
+    ///
+    /// Returns the warning to emit if `input` is not a type or names a type which does not exist
+    pub fn parse(tcx: TyCtxt<'_>, input: &str) -> Result<Self, String> {
+        match Self::parse_prefix(tcx, input)? {
+            (self_type, "") => Ok(self_type),
+            (_, rest) => Err(format!("unexpected `{rest}` in `self-type`")),
+        }
+    }
+
+    /// Parses the type at the start of `input`, returning it along with the rest of `input`.
+    fn parse_prefix<'a>(tcx: TyCtxt<'_>, input: &'a str) -> Result<(Self, &'a str), String> {
+        let input = input.trim_start();
+        let (path, rest) = input.split_at(input.find(['<', '>', ',']).unwrap_or(input.len()));
+        let path = path.trim_end();
+        if path == "_" {
+            return Ok((Self::Any, rest.trim_start()));
+        }
+
+        let mut args = Vec::new();
+        let mut rest = rest.trim_start();
+        if let Some(mut args_input) = rest.strip_prefix('<') {
+            loop {
+                let (arg, args_rest) = Self::parse_prefix(tcx, args_input)?;
+                args.push(arg);
+                if let Some(args_rest) = args_rest.strip_prefix(',') {
+                    args_input = args_rest;
+                } else if let Some(args_rest) = args_rest.strip_prefix('>') {
+                    rest = args_rest.trim_start();
+                    break;
+                } else {
+                    return Err(format!("expected `,` or `>` after the generic arguments of `{path}`"));
+                }
+            }
+        }
+
+        let def_ids = lookup_path_str(tcx, PathNS::Type, path);
+        let prim_ty = PrimTy::from_name(Symbol::intern(path));
+        if def_ids.is_empty() && prim_ty.is_none() {
+            return Err(format!("`{path}` does not refer to a reachable type"));
+        }
+        Ok((Self::Path { def_ids, prim_ty, args }, rest))
+    }
+
+    pub fn matches(&self, ty: Ty<'_>) -> bool {
+        let Self::Path { def_ids, prim_ty, args } = self else {
+            return true;
+        };
+        match ty.kind() {
+            ty::Adt(adt, ty_args) => {
+                def_ids.contains(&adt.did())
+                    && args.len() <= ty_args.types().count()
+                    && args.iter().zip(ty_args.types()).all(|(arg, ty)| arg.matches(ty))
+            },
+            ty::Foreign(def_id) => def_ids.contains(def_id),
+            ty::Bool | ty::Char | ty::Int(_) | ty::Uint(_) | ty::Float(_) | ty::Str => {
+                prim_ty.is_some_and(|prim_ty| prim_ty.name_str() == ty.to_string())
+            },
+            _ => false,
+        }
+    }
+}
+
 impl<const REPLACEMENT_ALLOWED: bool> DisallowedPath<REPLACEMENT_ALLOWED> {
     pub fn path(&self) -> &str {
         &self.path
     }
 
+    pub fn self_type(&self) -> Option<&str> {
+        self.self_type.as_deref()
+    }
+
+    pub fn args(&self) -> &[(usize, Vec<DisallowedArg>)] {
+        &self.args
+    }
+
     pub fn diag_amendment(&self, span: Span) -> impl FnOnce(&mut Diag<'_, ()>) {
         move |diag| {
             if let Some(replacement) = &self.replacement {
@@ -122,6 +293,13 @@ impl DisallowedPathEnum {
         }
     }
 
+    fn self_type(&self) -> Option<&str> {
+        match &self {
+            Self::WithReason { self_type, .. } => self_type.as_deref(),
+            Self::Simple(_) => None,
+        }
+    }
+
     fn allow_invalid(&self) -> bool {
         match &self {
             Self::WithReason { allow_invalid, .. } => allow_invalid.unwrap_or_default(),
@@ -130,6 +308,15 @@ impl DisallowedPathEnum {
     }
 }
 
+/// The kinds of entries besides paths to items supported by [`create_disallowed_map`].
+#[derive(Clone, Copy, Default)]
+pub struct DisallowedMapOptions {
+    /// Whether a path may name a primitive type, e.g. `u32`.
+    pub prim_tys: bool,
+    /// Whether an entry may have a `self-type`.
+    pub self_type: bool,
+}
+
 /// Creates a map of disallowed items to the reason they were disallowed.
 #[expect(clippy::type_complexity)]
 pub fn create_disallowed_map<const REPLACEMENT_ALLOWED: bool>(
@@ -138,7 +325,7 @@ pub fn create_disallowed_map<const REPLACEMENT_ALLOWED: bool>(
     ns: PathNS,
     def_kind_predicate: impl Fn(DefKind) -> bool,
     predicate_description: &str,
-    allow_prim_tys: bool,
+    options: DisallowedMapOptions,
 ) -> (
     DefIdMap<(&'static str, &'static DisallowedPath<REPLACEMENT_ALLOWED>)>,
     FxHashMap<PrimTy, (&'static str, &'static DisallowedPath<REPLACEMENT_ALLOWED>)>,
@@ -148,35 +335,51 @@ pub fn create_disallowed_map<const REPLACEMENT_ALLOWED: bool>(
         FxHashMap::default();
     for disallowed_path in disallowed_paths {
         let path = disallowed_path.path();
-        let sym_path: Vec<Symbol> = path.split("::").map(Symbol::intern).collect();
-        let mut resolutions = lookup_path(tcx, ns, &sym_path);
+        if disallowed_path.self_type.is_some() && !options.self_type {
+            tcx.sess.dcx().span_warn(
+                disallowed_path.span(),
+                format!("`self-type` is not supported for a {predicate_description}"),
+            );
+        }
+        if !disallowed_path.args.is_empty() && ns != PathNS::Value {
+            tcx.sess
+                .dcx()
+                .span_warn(disallowed_path.span(), "`args` is only supported for functions");
+        }
+        let mut sym_path: Vec<Symbol> = path.split("::").map(Symbol::intern).collect();
+        let is_glob = path.contains('*');
+        let mut resolutions = lookup_path_glob(tcx, ns, &sym_path);
         resolutions.retain(|&def_id| def_kind_predicate(tcx.def_kind(def_id)));
 
-        let (prim_ty, found_prim_ty) = if let &[name] = sym_path.as_slice()
-            && let Some(prim) = PrimTy::from_name(name)
+        // With a `self-type`, a path can name a method of a trait impl through its `Self` type
+        if resolutions.is_empty() && !is_glob && options.self_type && disallowed_path.self_type.is_some() {
+            resolutions = lookup_trait_impl_fn(tcx, &sym_path);
+        }
+
+        // A path to a module disallows all of its items
+        if resolutions.is_empty()
+            && !is_glob
+            && lookup_path(tcx, PathNS::Type, &sym_path)
+                .into_iter()
+                .any(|def_id| tcx.def_kind(def_id) == DefKind::Mod)
         {
-            (allow_prim_tys.then_some(prim), true)
+            sym_path.push(Symbol::intern("*"));
+            resolutions = lookup_path_glob(tcx, ns, &sym_path);
+            resolutions.retain(|&def_id| def_kind_predicate(tcx.def_kind(def_id)));
+            sym_path.pop();
+        }
+
+        let prim_ty = if let &[name] = sym_path.as_slice() {
+            PrimTy::from_name(name).filter(|_| options.prim_tys)
         } else {
-            (None, false)
+            None
         };
 
         if resolutions.is_empty()
             && prim_ty.is_none()
             && !disallowed_path.allow_invalid
-            // Don't warn about unloaded crates:
-            // https://github.com/rust-lang/rust-clippy/pull/14397#issuecomment-2848328221
-            && (sym_path.len() < 2 || !find_crates(tcx, sym_path[0]).is_empty())
+            && let Some(message) = unresolved_path_message(tcx, path, predicate_description)
         {
-            // Relookup the path in an arbitrary namespace to get a good `expected, found` message
-            let found_def_ids = lookup_path(tcx, PathNS::Arbitrary, &sym_path);
-            let message = if let Some(&def_id) = found_def_ids.first() {
-                let (article, description) = tcx.article_and_description(def_id);
-                format!("expected a {predicate_description}, found {article} {description}")
-            } else if found_prim_ty {
-                format!("expected a {predicate_description}, found a primitive type")
-            } else {
-                format!("`{path}` does not refer to a reachable {predicate_description}")
-            };
             tcx.sess
                 .dcx()
                 .struct_span_warn(disallowed_path.span(), message)
@@ -195,6 +398,31 @@ pub fn create_disallowed_map<const REPLACEMENT_ALLOWED: bool>(
     (def_ids, prim_tys)
 }
 
+/// Returns the warning for the path `path` of a configuration entry, which resolves to no
+/// `predicate_description`, or `None` if the crate of `path` is not loaded.
+pub fn unresolved_path_message(tcx: TyCtxt<'_>, path: &str, predicate_description: &str) -> Option<String> {
+    let sym_path: Vec<Symbol> = path.split("::").map(Symbol::intern).collect();
+    // Don't warn about unloaded crates:
+    // https://github.com/rust-lang/rust-clippy/pull/14397#issuecomment-2848328221
+    if sym_path.len() >= 2 && find_crates(tcx, sym_path[0]).is_empty() {
+        return None;
+    }
+    // Relookup the path in an arbitrary namespace to get a good `expected, found` message
+    let found_def_ids = lookup_path(tcx, PathNS::Arbitrary, &sym_path);
+    Some(if path.contains('*') {
+        format!("`{path}` does not match any {predicate_description}")
+    } else if let Some(&def_id) = found_def_ids.first() {
+        let (article, description) = tcx.article_and_description(def_id);
+        format!("expected a {predicate_description}, found {article} {description}")
+    } else if let &[name] = sym_path.as_slice()
+        && PrimTy::from_name(name).is_some()
+    {
+        format!("expected a {predicate_description}, found a primitive type")
+    } else {
+        format!("`{path}` does not refer to a reachable {predicate_description}")
+    })
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub enum MatchLintBehaviour {
     AllTypes,
@@ -689,6 +917,8 @@ macro_rules! unimplemented_serialize {
 }
 
 unimplemented_serialize! {
+    ApiVersion,
+    HeldAcrossCall,
     Rename,
     MacroMatcher,
 }
@@ -1,5 +1,5 @@
 use clippy_config::Conf;
-use clippy_config::types::{DisallowedPathWithoutReplacement, create_disallowed_map};
+use clippy_config::types::{DisallowedMapOptions, DisallowedPathWithoutReplacement, create_disallowed_map};
 use clippy_utils::diagnostics::span_lint_and_then;
 use clippy_utils::paths::{self, PathNS};
 use clippy_utils::sym;
@@ -190,7 +190,7 @@ impl AwaitHolding {
             PathNS::Type,
             crate::disallowed_types::def_kind_predicate,
             "type",
-            false,
+            DisallowedMapOptions::default(),
         );
         Self { def_ids }
     }
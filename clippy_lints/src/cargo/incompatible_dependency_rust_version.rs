@@ -0,0 +1,63 @@
+use cargo_metadata::{DependencyKind, Metadata, Node, Package};
+use clippy_utils::diagnostics::span_lint;
+use clippy_utils::msrvs::{Msrv, manifest_dependency_span, manifest_rust_version};
+use rustc_hir::RustcVersion;
+use rustc_lint::LateContext;
+use rustc_span::DUMMY_SP;
+use std::env;
+
+use super::INCOMPATIBLE_DEPENDENCY_RUST_VERSION;
+
+pub(super) fn check(cx: &LateContext<'_>, metadata: &Metadata, msrv: Msrv) {
+    let Some(msrv) = msrv.current(cx) else {
+        return;
+    };
+    let Ok(package_name) = env::var("CARGO_PKG_NAME") else {
+        // Not built by Cargo, so there are no dependencies to check
+        return;
+    };
+    if let Some(resolve) = &metadata.resolve
+        && let Some(package) = metadata.packages.iter().find(|package| *package.name == package_name)
+        && let Some(node) = resolve.nodes.iter().find(|node| node.id == package.id)
+    {
+        check_dependencies(cx, metadata, package, node, msrv);
+    }
+}
+
+fn check_dependencies(cx: &LateContext<'_>, metadata: &Metadata, package: &Package, node: &Node, msrv: RustcVersion) {
+    // Only the normal dependencies, the dev-dependencies are not needed to build the crate
+    let max_dependency = node
+        .deps
+        .iter()
+        .filter(|dep| dep.dep_kinds.iter().any(|info| info.kind == DependencyKind::Normal))
+        .filter_map(|dep| metadata.packages.iter().find(|package| package.id == dep.pkg))
+        .filter_map(|dep| {
+            let version = dep.rust_version.as_ref()?;
+            let version = RustcVersion {
+                major: version.major.try_into().ok()?,
+                minor: version.minor.try_into().ok()?,
+                patch: version.patch.try_into().ok()?,
+            };
+            Some((version, dep))
+        })
+        .max_by_key(|&(version, _)| version);
+    if let Some((version, dep)) = max_dependency
+        && version > msrv
+    {
+        // Point at the dependency in the manifest of the crate, or at its `rust-version`
+        let span = package.manifest_path.parent().and_then(|dir| {
+            let dir = dir.as_std_path();
+            manifest_dependency_span(cx.tcx.sess, dir, &dep.name)
+                .or_else(|| Some(manifest_rust_version(cx.tcx.sess, dir)?.1))
+        });
+        span_lint(
+            cx,
+            INCOMPATIBLE_DEPENDENCY_RUST_VERSION,
+            span.unwrap_or(DUMMY_SP),
+            format!(
+                "the MSRV `{msrv}` is lower than the `rust-version` of the dependency `{}`, `{version}`",
+                dep.name
+            ),
+        );
+    }
+}
@@ -1,18 +1,23 @@
 mod common_metadata;
 mod feature_name;
+mod incompatible_dependency_rust_version;
 mod lint_groups_priority;
 mod multiple_crate_versions;
 mod wildcard_dependencies;
 
-use cargo_metadata::MetadataCommand;
+use cargo_metadata::{Metadata, MetadataCommand};
 use clippy_config::Conf;
 use clippy_utils::diagnostics::span_lint;
 use clippy_utils::is_lint_allowed;
+use clippy_utils::msrvs::Msrv;
 use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::def_id::LOCAL_CRATE;
 use rustc_hir::hir_id::CRATE_HIR_ID;
 use rustc_lint::{LateContext, LateLintPass, Lint};
 use rustc_session::impl_lint_pass;
 use rustc_span::DUMMY_SP;
+use std::env;
+use std::sync::OnceLock;
 
 declare_clippy_lint! {
     /// ### What it does
@@ -56,6 +61,43 @@ declare_clippy_lint! {
     "common metadata is defined in `Cargo.toml`"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for normal dependencies whose `rust-version` is newer than the MSRV of the crate.
+    ///
+    /// ### Why is this bad?
+    /// The crate can't be built with the toolchain of its MSRV, as its dependency requires a newer
+    /// one. The MSRV is either wrong, or the dependency must be downgraded.
+    ///
+    /// ### Known problems
+    /// The `rust-version` of the dependencies is read from `cargo metadata`, which is run for each
+    /// crate checked.
+    ///
+    /// ### Example
+    /// ```toml
+    /// [package]
+    /// name = "foo"
+    /// rust-version = "1.56"
+    ///
+    /// [dependencies]
+    /// # `bar` has `rust-version = "1.70"`
+    /// bar = "1.0"
+    /// ```
+    /// Use instead:
+    /// ```toml
+    /// [package]
+    /// name = "foo"
+    /// rust-version = "1.70"
+    ///
+    /// [dependencies]
+    /// bar = "1.0"
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub INCOMPATIBLE_DEPENDENCY_RUST_VERSION,
+    cargo,
+    "a dependency requiring a newer Rust version than the MSRV"
+}
+
 declare_clippy_lint! {
     /// ### What it does
     /// Checks for lint groups with the same priority as lints in the `Cargo.toml`
@@ -215,6 +257,7 @@ declare_clippy_lint! {
 
 impl_lint_pass!(Cargo => [
     CARGO_COMMON_METADATA,
+    INCOMPATIBLE_DEPENDENCY_RUST_VERSION,
     LINT_GROUPS_PRIORITY,
     MULTIPLE_CRATE_VERSIONS,
     NEGATIVE_FEATURE_NAMES,
@@ -225,6 +268,7 @@ impl_lint_pass!(Cargo => [
 pub struct Cargo {
     allowed_duplicate_crates: FxHashSet<String>,
     ignore_publish: bool,
+    msrv: Msrv,
 }
 
 impl Cargo {
@@ -232,6 +276,7 @@ impl Cargo {
         Self {
             allowed_duplicate_crates: conf.allowed_duplicate_crates.iter().cloned().collect(),
             ignore_publish: conf.cargo_ignore_publish,
+            msrv: conf.msrv,
         }
     }
 }
@@ -244,7 +289,6 @@ impl LateLintPass<'_> for Cargo {
             NEGATIVE_FEATURE_NAMES,
             WILDCARD_DEPENDENCIES,
         ];
-        static WITH_DEPS_LINTS: &[&Lint] = &[MULTIPLE_CRATE_VERSIONS];
 
         lint_groups_priority::check(cx);
 
@@ -266,20 +310,44 @@ impl LateLintPass<'_> for Cargo {
             }
         }
 
-        if !WITH_DEPS_LINTS
-            .iter()
-            .all(|&lint| is_lint_allowed(cx, lint, CRATE_HIR_ID))
-        {
-            match MetadataCommand::new().exec() {
+        let check_rust_version = !is_lint_allowed(cx, INCOMPATIBLE_DEPENDENCY_RUST_VERSION, CRATE_HIR_ID)
+            // Only the crates built by Cargo with an MSRV have dependencies to check
+            && self.msrv.current(cx).is_some()
+            // `CARGO_CRATE_NAME` is only set by Cargo when it compiles the crate, unlike `CARGO_PKG_NAME`
+            // which is inherited by the processes run by `cargo run` and `cargo test`
+            && env::var("CARGO_CRATE_NAME").is_ok_and(|name| *name == *cx.tcx.crate_name(LOCAL_CRATE).as_str());
+        let check_crate_versions = !is_lint_allowed(cx, MULTIPLE_CRATE_VERSIONS, CRATE_HIR_ID);
+        if check_rust_version || check_crate_versions {
+            match metadata() {
                 Ok(metadata) => {
-                    multiple_crate_versions::check(cx, &metadata, &self.allowed_duplicate_crates);
+                    if check_rust_version {
+                        incompatible_dependency_rust_version::check(cx, metadata, self.msrv);
+                    }
+                    if check_crate_versions {
+                        multiple_crate_versions::check(cx, metadata, &self.allowed_duplicate_crates);
+                    }
                 },
                 Err(e) => {
-                    for lint in WITH_DEPS_LINTS {
-                        span_lint(cx, lint, DUMMY_SP, format!("could not read cargo metadata: {e}"));
+                    for (lint, checked) in [
+                        (INCOMPATIBLE_DEPENDENCY_RUST_VERSION, check_rust_version),
+                        (MULTIPLE_CRATE_VERSIONS, check_crate_versions),
+                    ] {
+                        if checked {
+                            span_lint(cx, lint, DUMMY_SP, format!("could not read cargo metadata: {e}"));
+                        }
                     }
                 },
             }
         }
     }
 }
+
+/// Returns the metadata of the package along with its dependencies. It is read once and shared by
+/// the lints which need it.
+pub(crate) fn metadata() -> Result<&'static Metadata, &'static str> {
+    static METADATA: OnceLock<Result<Metadata, String>> = OnceLock::new();
+    METADATA
+        .get_or_init(|| MetadataCommand::new().exec().map_err(|e| e.to_string()))
+        .as_ref()
+        .map_err(String::as_str)
+}
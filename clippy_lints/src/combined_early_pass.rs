@@ -14,7 +14,30 @@
 //! late passes via `skippable_lints`, but the early pass runner has
 //! no such filtering, so a plain forward is equivalent and loses nothing.
 //!
+//! When [`pass_times`] are recorded, the pass is built with `TIMED` set and each
+//! field also accumulates the time spent in its `check_*` methods, as in the
+//! late version.
+//!
 //! [`combined_late_pass`]: crate::combined_late_pass
+//! [`pass_times`]: crate::pass_times
+
+use std::time::Duration;
+
+/// A pass paired with the time spent in it.
+pub struct Timed<P> {
+    pub(crate) pass: P,
+    pub(crate) time: Duration,
+}
+
+impl<P> Timed<P> {
+    #[inline]
+    pub fn new(pass: P) -> Self {
+        Timed {
+            pass,
+            time: Duration::ZERO,
+        }
+    }
+}
 
 /// Run one field's `check_*`.
 ///
@@ -24,7 +47,13 @@
 #[macro_export]
 macro_rules! run_combined_early_lint_pass_field {
     ($self:ident, $field:ident, $name:ident, ($($arg:expr),* $(,)?)) => {
-        rustc_lint::EarlyLintPass::$name(&mut $self.$field, $($arg),*);
+        if TIMED {
+            let start = std::time::Instant::now();
+            rustc_lint::EarlyLintPass::$name(&mut $self.$field.pass, $($arg),*);
+            $self.$field.time += start.elapsed();
+        } else {
+            rustc_lint::EarlyLintPass::$name(&mut $self.$field.pass, $($arg),*);
+        }
     };
 }
 
@@ -47,8 +76,8 @@ macro_rules! expand_combined_early_lint_pass_methods {
     )
 }
 
-/// Declare the combined struct (one field per pass) plus its
-/// `LintPass`/`EarlyLintPass` impls. The method list comes from
+/// Declare the combined struct (one [`Timed`] field per pass, timed when `TIMED`
+/// is set) plus its `LintPass`/`EarlyLintPass` impls. The method list comes from
 /// `rustc_lint::early_lint_methods!` so it can't drift from rustc's.
 ///
 /// Each entry is `Field: Type = constructor`; `new`'s params (`conf`, ...) come
@@ -60,32 +89,47 @@ macro_rules! combined_early_lint_pass {
         $methods:tt
     ) => {
         #[allow(non_snake_case)]
-        pub struct $name {
-            $($field: $fty,)*
+        pub struct $name<const TIMED: bool> {
+            $($field: $crate::combined_early_pass::Timed<$fty>,)*
         }
 
-        impl $name {
+        impl<const TIMED: bool> $name<TIMED> {
             pub fn new($($pname: $pty,)*) -> Self {
                 Self {
-                    $($field: $ctor,)*
+                    $($field: $crate::combined_early_pass::Timed::new($ctor),)*
+                }
+            }
+
+            /// Returns the lints of the passes without building them.
+            pub fn lint_vec() -> rustc_lint::LintVec {
+                let mut lints = Vec::new();
+                $(lints.extend(<$fty>::lint_vec());)*
+                lints
+            }
+        }
+
+        impl<const TIMED: bool> Drop for $name<TIMED> {
+            fn drop(&mut self) {
+                if TIMED {
+                    $crate::pass_times::write("early", &[$((stringify!($field), self.$field.time)),*]);
                 }
             }
         }
 
         #[allow(rustc::lint_pass_impl_without_macro)]
-        impl rustc_lint::LintPass for $name {
+        impl<const TIMED: bool> rustc_lint::LintPass for $name<TIMED> {
             fn name(&self) -> &'static str {
                 stringify!($name)
             }
             fn get_lints(&self) -> rustc_lint::LintVec {
                 // Reserve at least one slot per pass up front to skip the early reallocations.
                 let mut lints = Vec::with_capacity([$(stringify!($field)),*].len());
-                $(lints.extend(self.$field.get_lints());)*
+                $(lints.extend(self.$field.pass.get_lints());)*
                 lints
             }
         }
 
-        impl rustc_lint::EarlyLintPass for $name {
+        impl<const TIMED: bool> rustc_lint::EarlyLintPass for $name<TIMED> {
             $crate::expand_combined_early_lint_pass_methods!([$($field),*], $methods);
         }
     };
@@ -9,20 +9,32 @@
 //! [`Gated`] with a precomputed `active` flag (the same "lint still needs to run"
 //! predicate `rustc_lint::late` uses). Disabled passes are skipped by a branch
 //! rather than dropped from a `Vec`, keeping clippy's allow-by-default fast path.
+//!
+//! When [`pass_times`] are recorded, the pass is built with `TIMED` set and each
+//! field also accumulates the time spent in its `check_*` methods. The choice is
+//! made once when the pass is built, so the untimed pass doesn't branch on it.
+//!
+//! [`pass_times`]: crate::pass_times
 
 use rustc_lint::{LintPass, LintVec};
+use std::time::Duration;
 
 /// A pass paired with its precomputed "still needs to run" flag.
 pub struct Gated<P> {
     pub(crate) active: bool,
     pub(crate) pass: P,
+    pub(crate) time: Duration,
 }
 
 impl<P: LintPass> Gated<P> {
     #[inline]
     pub fn new<F: Fn(&LintVec) -> bool>(is_active: &F, pass: P) -> Self {
         let active = is_active(&pass.get_lints());
-        Gated { active, pass }
+        Gated {
+            active,
+            pass,
+            time: Duration::ZERO,
+        }
     }
 }
 
@@ -37,7 +49,13 @@ impl<P: LintPass> Gated<P> {
 macro_rules! run_combined_late_lint_pass_field {
     ($self:ident, $field:ident, $name:ident, ($($arg:expr),* $(,)?)) => {
         if $self.$field.active {
-            rustc_lint::LateLintPass::$name(&mut $self.$field.pass, $($arg),*);
+            if TIMED {
+                let start = std::time::Instant::now();
+                rustc_lint::LateLintPass::$name(&mut $self.$field.pass, $($arg),*);
+                $self.$field.time += start.elapsed();
+            } else {
+                rustc_lint::LateLintPass::$name(&mut $self.$field.pass, $($arg),*);
+            }
         }
     };
 }
@@ -61,8 +79,8 @@ macro_rules! expand_combined_late_lint_pass_methods {
     )
 }
 
-/// Declare the combined struct (one [`Gated`] field per pass) plus its
-/// `LintPass`/`LateLintPass` impls. The method list comes from
+/// Declare the combined struct (one [`Gated`] field per pass, timed when `TIMED`
+/// is set) plus its `LintPass`/`LateLintPass` impls. The method list comes from
 /// `rustc_lint::late_lint_methods!` so it can't drift from rustc's.
 ///
 /// Each entry is `Field: Type = constructor`; `new`'s params (`tcx`, `conf`, ...)
@@ -74,20 +92,35 @@ macro_rules! combined_late_lint_pass {
         $methods:tt
     ) => {
         #[allow(non_snake_case)]
-        pub struct $name<'tcx> {
+        pub struct $name<'tcx, const TIMED: bool> {
             $($field: $crate::combined_late_pass::Gated<$fty>,)*
         }
 
-        impl<'tcx> $name<'tcx> {
+        impl<'tcx, const TIMED: bool> $name<'tcx, TIMED> {
             pub fn new<F: Fn(&rustc_lint::LintVec) -> bool>($($pname: $pty,)* is_active: &F) -> Self {
                 Self {
                     $($field: $crate::combined_late_pass::Gated::new(is_active, $ctor),)*
                 }
             }
+
+            /// Returns the lints of the passes without building them.
+            pub fn lint_vec() -> rustc_lint::LintVec {
+                let mut lints = Vec::new();
+                $(lints.extend(<$fty>::lint_vec());)*
+                lints
+            }
+        }
+
+        impl<const TIMED: bool> Drop for $name<'_, TIMED> {
+            fn drop(&mut self) {
+                if TIMED {
+                    $crate::pass_times::write("late", &[$((stringify!($field), self.$field.time)),*]);
+                }
+            }
         }
 
         #[allow(rustc::lint_pass_impl_without_macro)]
-        impl<'tcx> rustc_lint::LintPass for $name<'tcx> {
+        impl<'tcx, const TIMED: bool> rustc_lint::LintPass for $name<'tcx, TIMED> {
             fn name(&self) -> &'static str {
                 stringify!($name)
             }
@@ -99,7 +132,7 @@ macro_rules! combined_late_lint_pass {
             }
         }
 
-        impl<'tcx> rustc_lint::LateLintPass<'tcx> for $name<'tcx> {
+        impl<'tcx, const TIMED: bool> rustc_lint::LateLintPass<'tcx> for $name<'tcx, TIMED> {
             $crate::expand_combined_late_lint_pass_methods!([$($field),*], $methods);
         }
     };
@@ -1,5 +1,5 @@
 use clippy_config::Conf;
-use clippy_config::types::{DisallowedPath, create_disallowed_map};
+use clippy_config::types::{DisallowedMapOptions, DisallowedPath, create_disallowed_map};
 use clippy_utils::diagnostics::span_lint_and_then;
 use clippy_utils::paths::PathNS;
 use clippy_utils::ty::get_field_def_id_by_name;
@@ -70,7 +70,7 @@ impl DisallowedFields {
             PathNS::Field,
             |def_kind| matches!(def_kind, DefKind::Field),
             "field",
-            false,
+            DisallowedMapOptions::default(),
         );
         Self { disallowed }
     }
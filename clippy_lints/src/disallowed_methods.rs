@@ -1,13 +1,18 @@
 use clippy_config::Conf;
-use clippy_config::types::{DisallowedPath, create_disallowed_map};
+use clippy_config::types::{DisallowedArg, DisallowedMapOptions, DisallowedPath, SelfType, create_disallowed_map};
+use clippy_utils::consts::{ConstEvalCtxt, Constant, FullInt};
 use clippy_utils::diagnostics::span_lint_and_then;
-use clippy_utils::paths::PathNS;
+use clippy_utils::get_parent_expr;
+use clippy_utils::paths::{PathNS, lookup_path_str};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::unord::ExtendUnord as _;
 use rustc_hir::def::{CtorKind, DefKind, Res};
-use rustc_hir::def_id::DefIdMap;
+use rustc_hir::def_id::{DefId, DefIdMap};
 use rustc_hir::{Expr, ExprKind};
 use rustc_lint::{LateContext, LateLintPass};
-use rustc_middle::ty::TyCtxt;
+use rustc_middle::ty::{self, GenericArgsRef, Instance, Ty, TyCtxt};
 use rustc_session::impl_lint_pass;
+use std::{iter, ptr, slice};
 
 declare_clippy_lint! {
     /// ### What it does
@@ -34,9 +39,21 @@ declare_clippy_lint! {
     ///     { path = "std::vec::Vec::leak", reason = "no leaking memory" },
     ///     # Can also add a `replacement` that will be offered as a suggestion.
     ///     { path = "std::sync::Mutex::new", reason = "prefer faster & simpler non-poisonable mutex", replacement = "parking_lot::Mutex::new" },
+    ///     # Can use `*` wildcards in the last segment, or the path of a module, to disallow
+    ///     # all the matching functions.
+    ///     "std::process::*",
+    ///     "core::slice::<impl [T]>::*_unchecked",
     ///     # This would normally error if the path is incorrect, but with `allow-invalid` = `true`,
     ///     # it will be silently ignored
     ///     { path = "std::fs::InvalidPath", reason = "use alternative instead", allow-invalid = true },
+    ///     # Can restrict a method to the calls where `Self` is a given type, e.g. to disallow a trait
+    ///     # method for a single implementor.
+    ///     { path = "std::io::Read::read", self-type = "std::fs::File", reason = "use buffered reads" },
+    ///     { path = "std::io::Write::write", self-type = "std::net::TcpStream", reason = "use the connection pool" },
+    ///     # Can restrict a method to the calls where arguments, given by their index with `self`
+    ///     # included, have one of the given values.
+    ///     { path = "std::process::Command::new", args = { 0 = ["sh", "bash"] }, reason = "no shell" },
+    ///     { path = "std::sync::atomic::fence", args = { 0 = [{ path = "std::sync::atomic::Ordering::SeqCst" }] } },
     /// ]
     /// ```
     ///
@@ -64,34 +81,228 @@ declare_clippy_lint! {
 impl_lint_pass!(DisallowedMethods => [DISALLOWED_METHODS]);
 
 pub struct DisallowedMethods {
-    disallowed: DefIdMap<(&'static str, &'static DisallowedPath)>,
+    conf: &'static Conf,
+    /// The methods disallowed by each value of `disallowed-methods` used so far, as it can be set
+    /// per file.
+    disallowed: FxHashMap<*const Vec<DisallowedPath>, Disallowed>,
 }
 
 impl DisallowedMethods {
     pub fn new(tcx: TyCtxt<'_>, conf: &'static Conf) -> Self {
-        let (disallowed, _) = create_disallowed_map(
-            tcx,
-            &conf.disallowed_methods,
-            PathNS::Value,
-            |def_kind| {
-                matches!(
-                    def_kind,
-                    DefKind::Fn | DefKind::Ctor(_, CtorKind::Fn) | DefKind::AssocFn
-                )
+        // The paths of the crate are resolved upfront, to report the ones which don't resolve
+        let disallowed = Disallowed::new(tcx, &conf.disallowed_methods);
+        Self {
+            conf,
+            disallowed: FxHashMap::from_iter([(ptr::from_ref(&conf.disallowed_methods), disallowed)]),
+        }
+    }
+}
+
+/// The methods disallowed by a value of `disallowed-methods`.
+struct Disallowed {
+    disallowed: DefIdMap<(&'static str, &'static DisallowedPath)>,
+    /// The entries with a `self-type` or `args`, kept apart as the same method can be disallowed
+    /// under several conditions.
+    conditional: Vec<(Conditions, DefIdMap<(&'static str, &'static DisallowedPath)>)>,
+}
+
+impl Disallowed {
+    fn new(tcx: TyCtxt<'_>, paths: &'static [DisallowedPath]) -> Self {
+        let mut disallowed = DefIdMap::default();
+        let mut conditional = Vec::new();
+        for entry in paths {
+            let (def_ids, _) = create_disallowed_map(
+                tcx,
+                slice::from_ref(entry),
+                PathNS::Value,
+                |def_kind| {
+                    matches!(
+                        def_kind,
+                        DefKind::Fn | DefKind::Ctor(_, CtorKind::Fn) | DefKind::AssocFn
+                    )
+                },
+                "function",
+                DisallowedMapOptions {
+                    self_type: true,
+                    ..DisallowedMapOptions::default()
+                },
+            );
+            match Conditions::new(tcx, entry) {
+                Ok(None) => disallowed.extend_unord(def_ids.into_items()),
+                Ok(Some(conditions)) => conditional.push((conditions, def_ids)),
+                Err(message) => tcx.sess.dcx().span_warn(entry.span(), message),
+            }
+        }
+        Self {
+            disallowed,
+            conditional,
+        }
+    }
+
+    /// Finds the entry disallowing a call to `def_id` with the generic arguments `args` and the
+    /// arguments `call_args`, if known. Calls to trait methods are also checked against the method
+    /// of the impl they resolve to.
+    fn find<'tcx>(
+        &self,
+        cx: &LateContext<'tcx>,
+        def_id: DefId,
+        args: GenericArgsRef<'tcx>,
+        call_args: Option<&[&Expr<'_>]>,
+    ) -> Option<(&'static str, &'static DisallowedPath)> {
+        let find_in = |def_id: DefId, args: GenericArgsRef<'tcx>| {
+            let self_ty = self_ty(cx.tcx, def_id, args).map(Ty::peel_refs);
+            self.conditional
+                .iter()
+                .filter(|(conditions, _)| conditions.hold(cx, self_ty, call_args))
+                .find_map(|(_, disallowed)| disallowed.get(&def_id))
+                .or_else(|| self.disallowed.get(&def_id))
+                .copied()
+        };
+
+        find_in(def_id, args).or_else(|| {
+            if cx.tcx.def_kind(def_id) == DefKind::AssocFn
+                && cx.tcx.trait_of_assoc(def_id).is_some()
+                && let Ok(Some(instance)) = Instance::try_resolve(cx.tcx, cx.typing_env(), def_id, args)
+                && instance.def_id() != def_id
+            {
+                find_in(instance.def_id(), instance.args)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// The conditions under which a call to the method of an entry with a `self-type` or `args` is
+/// linted.
+struct Conditions {
+    self_type: Option<SelfType>,
+    /// For each constrained argument, its index and the values it must have one of.
+    args: Vec<(usize, Vec<ArgValue>)>,
+}
+
+impl Conditions {
+    fn new(tcx: TyCtxt<'_>, entry: &'static DisallowedPath) -> Result<Option<Self>, String> {
+        if entry.self_type().is_none() && entry.args().is_empty() {
+            return Ok(None);
+        }
+        let self_type = entry
+            .self_type()
+            .map(|self_type| SelfType::parse(tcx, self_type))
+            .transpose()?;
+        let args = entry
+            .args()
+            .iter()
+            .map(|(index, values)| {
+                let values = values
+                    .iter()
+                    .map(|value| ArgValue::new(tcx, value))
+                    .collect::<Result<_, _>>()?;
+                Ok((*index, values))
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Some(Self { self_type, args }))
+    }
+
+    fn hold(&self, cx: &LateContext<'_>, self_ty: Option<Ty<'_>>, call_args: Option<&[&Expr<'_>]>) -> bool {
+        self.self_type
+            .as_ref()
+            .is_none_or(|self_type| self_ty.is_some_and(|ty| self_type.matches(ty)))
+            && (self.args.is_empty()
+                || call_args.is_some_and(|call_args| {
+                    self.args.iter().all(|(index, values)| {
+                        call_args
+                            .get(*index)
+                            .is_some_and(|arg| values.iter().any(|value| value.matches(cx, arg)))
+                    })
+                }))
+    }
+}
+
+/// A value given in the `args` of a disallowed method.
+enum ArgValue {
+    /// A literal value, compared to the evaluated argument.
+    Const(&'static DisallowedArg),
+    /// The resolutions of a path to a constant, a static or a unit variant.
+    Path(Vec<DefId>),
+}
+
+impl ArgValue {
+    fn new(tcx: TyCtxt<'_>, value: &'static DisallowedArg) -> Result<Self, String> {
+        let DisallowedArg::Path { path } = value else {
+            return Ok(Self::Const(value));
+        };
+        // Enum variants may only be found in the type namespace
+        let mut def_ids = lookup_path_str(tcx, PathNS::Value, path);
+        if def_ids.is_empty() {
+            def_ids = lookup_path_str(tcx, PathNS::Type, path);
+        }
+        if def_ids.is_empty() {
+            Err(format!("`{path}` does not refer to a reachable value"))
+        } else {
+            Ok(Self::Path(def_ids))
+        }
+    }
+
+    fn matches(&self, cx: &LateContext<'_>, arg: &Expr<'_>) -> bool {
+        match self {
+            Self::Const(value) => ConstEvalCtxt::new(cx)
+                .eval(arg)
+                .is_some_and(|constant| const_matches(cx.tcx, value, &constant, cx.typeck_results().expr_ty(arg))),
+            Self::Path(def_ids) => {
+                if let ExprKind::Path(qpath) = &arg.peel_borrows().kind
+                    && let Res::Def(def_kind, def_id) = cx.qpath_res(qpath, arg.hir_id)
+                {
+                    def_ids.contains(&def_id)
+                        || (matches!(def_kind, DefKind::Ctor(..)) && def_ids.contains(&cx.tcx.parent(def_id)))
+                } else {
+                    false
+                }
             },
-            "function",
-            false,
-        );
-        Self { disallowed }
+        }
+    }
+}
+
+fn const_matches(tcx: TyCtxt<'_>, value: &DisallowedArg, constant: &Constant, ty: Ty<'_>) -> bool {
+    match (value, constant) {
+        (_, Constant::Ref(constant)) => match ty.kind() {
+            ty::Ref(_, ty, _) => const_matches(tcx, value, constant, *ty),
+            _ => false,
+        },
+        (DisallowedArg::Bool(value), Constant::Bool(constant)) => value == constant,
+        (DisallowedArg::Int(value), Constant::Int(_)) => {
+            constant.int_value(tcx, ty) == Some(FullInt::S(i128::from(*value)))
+        },
+        (DisallowedArg::Str(value), Constant::Str(constant)) => value == constant,
+        _ => false,
+    }
+}
+
+/// Returns the `Self` type of the trait or impl method `def_id` called with the generic arguments
+/// `args`.
+fn self_ty<'tcx>(tcx: TyCtxt<'tcx>, def_id: DefId, args: GenericArgsRef<'tcx>) -> Option<Ty<'tcx>> {
+    let parent = tcx.opt_parent(def_id)?;
+    match tcx.def_kind(parent) {
+        DefKind::Trait => args.types().next(),
+        DefKind::Impl { .. } => Some(tcx.type_of(parent).instantiate(tcx, args).skip_norm_wip()),
+        _ => None,
     }
 }
 
 impl<'tcx> LateLintPass<'tcx> for DisallowedMethods {
     fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
-        if self.disallowed.is_empty() {
+        if expr.span.desugaring_kind().is_some() {
             return;
         }
-        if expr.span.desugaring_kind().is_some() {
+        let paths = self
+            .conf
+            .in_file(cx.tcx.sess.source_map(), expr.span)
+            .disallowed_methods();
+        let disallowed = self
+            .disallowed
+            .entry(ptr::from_ref(paths))
+            .or_insert_with(|| Disallowed::new(cx.tcx, paths));
+        if disallowed.disallowed.is_empty() && disallowed.conditional.is_empty() {
             return;
         }
         let (id, span) = match &expr.kind {
@@ -101,7 +312,20 @@ impl<'tcx> LateLintPass<'tcx> for DisallowedMethods {
             },
             _ => return,
         };
-        if let Some(&(path, disallowed_path)) = self.disallowed.get(&id) {
+        let call_args = match &expr.kind {
+            ExprKind::MethodCall(_, receiver, args, _) => {
+                Some(iter::once(*receiver).chain(args.iter()).collect::<Vec<_>>())
+            },
+            _ => match get_parent_expr(cx, expr) {
+                Some(Expr {
+                    kind: ExprKind::Call(callee, args),
+                    ..
+                }) if callee.hir_id == expr.hir_id => Some(args.iter().collect()),
+                _ => None,
+            },
+        };
+        let args = cx.typeck_results().node_args(expr.hir_id);
+        if let Some((path, disallowed_path)) = disallowed.find(cx, id, args, call_args.as_deref()) {
             span_lint_and_then(
                 cx,
                 DISALLOWED_METHODS,
@@ -1,11 +1,12 @@
 use clippy_config::Conf;
 use clippy_utils::diagnostics::span_lint;
 use clippy_utils::{is_from_proc_macro, is_in_test};
-use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_hir::{Pat, PatKind};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_session::impl_lint_pass;
-use rustc_span::Symbol;
+use rustc_span::{Span, Symbol};
+use std::ptr;
 
 declare_clippy_lint! {
     /// ### What it does
@@ -29,22 +30,33 @@ declare_clippy_lint! {
 impl_lint_pass!(DisallowedNames => [DISALLOWED_NAMES]);
 
 pub struct DisallowedNames {
-    disallow: FxHashSet<Symbol>,
+    conf: &'static Conf,
+    /// The names of each value of `disallowed-names` used so far, as it can be set per file.
+    disallow: FxHashMap<*const Vec<String>, FxHashSet<Symbol>>,
 }
 
 impl DisallowedNames {
     pub fn new(conf: &'static Conf) -> Self {
         Self {
-            disallow: conf.disallowed_names.iter().map(|x| Symbol::intern(x)).collect(),
+            conf,
+            disallow: FxHashMap::default(),
         }
     }
+
+    fn is_disallowed(&mut self, cx: &LateContext<'_>, name: Symbol, span: Span) -> bool {
+        let names = self.conf.in_file(cx.tcx.sess.source_map(), span).disallowed_names();
+        self.disallow
+            .entry(ptr::from_ref(names))
+            .or_insert_with(|| names.iter().map(|x| Symbol::intern(x)).collect())
+            .contains(&name)
+    }
 }
 
 impl<'tcx> LateLintPass<'tcx> for DisallowedNames {
     fn check_pat(&mut self, cx: &LateContext<'tcx>, pat: &'tcx Pat<'_>) {
         if let PatKind::Binding(.., ident, _) = pat.kind
             && !ident.span.from_expansion()
-            && self.disallow.contains(&ident.name)
+            && self.is_disallowed(cx, ident.name, ident.span)
             && !is_in_test(cx.tcx, pat.hir_id)
             && !is_from_proc_macro(cx, &ident)
         {
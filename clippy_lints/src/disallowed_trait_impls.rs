@@ -0,0 +1,139 @@
+use clippy_config::Conf;
+use clippy_config::types::{DisallowedMapOptions, DisallowedPathWithoutReplacement, SelfType, create_disallowed_map};
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::paths::PathNS;
+use rustc_ast::ImplPolarity;
+use rustc_data_structures::unord::ExtendUnord as _;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::{DefId, DefIdMap};
+use rustc_hir::{Item, ItemKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::{Ty, TyCtxt};
+use rustc_session::impl_lint_pass;
+use std::slice;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Denies implementing the traits configured in clippy.toml.
+    ///
+    /// Note: Even though this lint is warn-by-default, it will only trigger if
+    /// traits are defined in the clippy.toml file.
+    ///
+    /// ### Why is this bad?
+    /// Some traits should not be implemented by hand in certain contexts, e.g. `Send` and `Sync`
+    /// whose manual implementations need to uphold invariants the compiler cannot check, or
+    /// `Drop` in a crate that must not run code on drop.
+    ///
+    /// ### Example:
+    /// An example clippy.toml configuration:
+    /// ```toml
+    /// # clippy.toml
+    /// disallowed-trait-impls = [
+    ///     # Can use a string as the path of the disallowed trait.
+    ///     "std::marker::Send",
+    ///     # Can also use an inline table with a `path` key.
+    ///     { path = "std::marker::Sync" },
+    ///     # When using an inline table, can add a `reason` for why the trait
+    ///     # is disallowed.
+    ///     { path = "std::ops::Drop", reason = "no code may run on drop in this crate" },
+    ///     # Can restrict the entry to the impls for a given `Self` type.
+    ///     { path = "std::ops::Deref", self-type = "crate::Meters" },
+    ///     # This would normally error if the path is incorrect, but with `allow-invalid` = `true`,
+    ///     # it will be silently ignored
+    ///     { path = "std::invalid::Trait", reason = "use alternative instead", allow-invalid = true }
+    /// ]
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// struct Handle(*mut u8);
+    /// unsafe impl Send for Handle {}
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// // A wrapper whose `Send` implementation has been reviewed
+    /// struct Handle(SendPtr<u8>);
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub DISALLOWED_TRAIT_IMPLS,
+    style,
+    "implementation of a disallowed trait"
+}
+
+impl_lint_pass!(DisallowedTraitImpls => [DISALLOWED_TRAIT_IMPLS]);
+
+pub struct DisallowedTraitImpls {
+    disallowed: DefIdMap<(&'static str, &'static DisallowedPathWithoutReplacement)>,
+    disallowed_for_self_type: Vec<(
+        SelfType,
+        DefIdMap<(&'static str, &'static DisallowedPathWithoutReplacement)>,
+    )>,
+}
+
+impl DisallowedTraitImpls {
+    pub fn new(tcx: TyCtxt<'_>, conf: &'static Conf) -> Self {
+        let mut disallowed = DefIdMap::default();
+        let mut disallowed_for_self_type = Vec::new();
+        for entry in &conf.disallowed_trait_impls {
+            let (def_ids, _) = create_disallowed_map(
+                tcx,
+                slice::from_ref(entry),
+                PathNS::Type,
+                |def_kind| matches!(def_kind, DefKind::Trait),
+                "trait",
+                DisallowedMapOptions {
+                    self_type: true,
+                    ..DisallowedMapOptions::default()
+                },
+            );
+            match entry.self_type().map(|self_type| SelfType::parse(tcx, self_type)) {
+                None => disallowed.extend_unord(def_ids.into_items()),
+                Some(Ok(self_type)) => disallowed_for_self_type.push((self_type, def_ids)),
+                Some(Err(message)) => tcx.sess.dcx().span_warn(entry.span(), message),
+            }
+        }
+        Self {
+            disallowed,
+            disallowed_for_self_type,
+        }
+    }
+
+    fn find(
+        &self,
+        trait_def_id: DefId,
+        self_ty: Ty<'_>,
+    ) -> Option<(&'static str, &'static DisallowedPathWithoutReplacement)> {
+        self.disallowed_for_self_type
+            .iter()
+            .filter(|(self_type, _)| self_type.matches(self_ty))
+            .find_map(|(_, disallowed)| disallowed.get(&trait_def_id))
+            .or_else(|| self.disallowed.get(&trait_def_id))
+            .copied()
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for DisallowedTraitImpls {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        if self.disallowed.is_empty() && self.disallowed_for_self_type.is_empty() {
+            return;
+        }
+        if let ItemKind::Impl(imp) = item.kind
+            && let Some(of_trait) = imp.of_trait
+            // Opting out of a trait is always allowed
+            && of_trait.polarity == ImplPolarity::Positive
+            && let Some(trait_def_id) = of_trait.trait_ref.trait_def_id()
+            && let Some((path, disallowed_path)) = self.find(
+                trait_def_id,
+                cx.tcx.type_of(item.owner_id).instantiate_identity().skip_norm_wip(),
+            )
+        {
+            let span = of_trait.trait_ref.path.span;
+            span_lint_and_then(
+                cx,
+                DISALLOWED_TRAIT_IMPLS,
+                span,
+                format!("implementation of a disallowed trait `{path}`"),
+                disallowed_path.diag_amendment(span),
+            );
+        }
+    }
+}
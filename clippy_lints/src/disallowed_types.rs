@@ -1,5 +1,5 @@
 use clippy_config::Conf;
-use clippy_config::types::{DisallowedPath, create_disallowed_map};
+use clippy_config::types::{DisallowedMapOptions, DisallowedPath, create_disallowed_map};
 use clippy_utils::diagnostics::span_lint_and_then;
 use clippy_utils::paths::PathNS;
 use rustc_data_structures::fx::FxHashMap;
@@ -10,6 +10,7 @@ use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::ty::TyCtxt;
 use rustc_session::impl_lint_pass;
 use rustc_span::Span;
+use std::ptr;
 
 declare_clippy_lint! {
     /// ### What it does
@@ -35,6 +36,9 @@ declare_clippy_lint! {
     ///     { path = "std::net::Ipv4Addr", reason = "no IPv4 allowed" },
     ///     # Can also add a `replacement` that will be offered as a suggestion.
     ///     { path = "std::sync::Mutex", reason = "prefer faster & simpler non-poisonable mutex", replacement = "parking_lot::Mutex" },
+    ///     # Can use `*` wildcards in the last segment, or the path of a module, to disallow
+    ///     # all the matching types.
+    ///     "std::collections::Hash*",
     ///     # This would normally error if the path is incorrect, but with `allow-invalid` = `true`,
     ///     # it will be silently ignored
     ///     { path = "std::invalid::Type", reason = "use alternative instead", allow-invalid = true }
@@ -60,27 +64,54 @@ declare_clippy_lint! {
 impl_lint_pass!(DisallowedTypes => [DISALLOWED_TYPES]);
 
 pub struct DisallowedTypes {
+    conf: &'static Conf,
+    /// The types disallowed by each value of `disallowed-types` used so far, as it can be set per
+    /// file.
+    disallowed: FxHashMap<*const Vec<DisallowedPath>, Disallowed>,
+}
+
+/// The types disallowed by a value of `disallowed-types`.
+struct Disallowed {
     def_ids: DefIdMap<(&'static str, &'static DisallowedPath)>,
     prim_tys: FxHashMap<PrimTy, (&'static str, &'static DisallowedPath)>,
 }
 
-impl DisallowedTypes {
-    pub fn new(tcx: TyCtxt<'_>, conf: &'static Conf) -> Self {
+impl Disallowed {
+    fn new(tcx: TyCtxt<'_>, paths: &'static [DisallowedPath]) -> Self {
         let (def_ids, prim_tys) = create_disallowed_map(
             tcx,
-            &conf.disallowed_types,
+            paths,
             PathNS::Type,
             def_kind_predicate,
             "type",
-            true,
+            DisallowedMapOptions {
+                prim_tys: true,
+                ..DisallowedMapOptions::default()
+            },
         );
         Self { def_ids, prim_tys }
     }
+}
+
+impl DisallowedTypes {
+    pub fn new(tcx: TyCtxt<'_>, conf: &'static Conf) -> Self {
+        // The paths of the crate are resolved upfront, to report the ones which don't resolve
+        let disallowed = Disallowed::new(tcx, &conf.disallowed_types);
+        Self {
+            conf,
+            disallowed: FxHashMap::from_iter([(ptr::from_ref(&conf.disallowed_types), disallowed)]),
+        }
+    }
 
-    fn check_res_emit(&self, cx: &LateContext<'_>, res: &Res, span: Span) {
+    fn check_res_emit(&mut self, cx: &LateContext<'_>, res: &Res, span: Span) {
+        let paths = self.conf.in_file(cx.tcx.sess.source_map(), span).disallowed_types();
+        let disallowed = self
+            .disallowed
+            .entry(ptr::from_ref(paths))
+            .or_insert_with(|| Disallowed::new(cx.tcx, paths));
         let (path, disallowed_path) = match res {
-            Res::Def(_, did) if let Some(&x) = self.def_ids.get(did) => x,
-            Res::PrimTy(prim) if let Some(&x) = self.prim_tys.get(prim) => x,
+            Res::Def(_, did) if let Some(&x) = disallowed.def_ids.get(did) => x,
+            Res::PrimTy(prim) if let Some(&x) = disallowed.prim_tys.get(prim) => x,
             _ => return,
         };
         span_lint_and_then(
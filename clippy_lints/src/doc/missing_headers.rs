@@ -1,16 +1,29 @@
-use super::{DocHeaders, MISSING_ERRORS_DOC, MISSING_PANICS_DOC, MISSING_SAFETY_DOC, UNNECESSARY_SAFETY_DOC};
-use clippy_utils::diagnostics::{span_lint, span_lint_and_note};
+use super::{
+    DocHeaders, MISSING_ERRORS_DOC, MISSING_PANICS_DOC, MISSING_SAFETY_DOC, UNNECESSARY_PANICS_DOC,
+    UNNECESSARY_SAFETY_DOC,
+};
+use clippy_utils::diagnostics::{span_lint, span_lint_and_then};
 use clippy_utils::macros::{is_panic, root_macro_call_first_node};
 use clippy_utils::res::MaybeDef as _;
 use clippy_utils::ty::implements_trait_with_env;
 use clippy_utils::visitors::for_each_expr;
-use clippy_utils::{fulfill_or_allowed, is_doc_hidden, is_inside_always_const_context, method_chain_args, return_ty};
-use rustc_hir::{BodyId, FnSig, OwnerId, Safety};
+use clippy_utils::{
+    fn_has_unsatisfiable_preds, fulfill_or_allowed, is_doc_hidden, is_inside_always_const_context, is_lint_allowed,
+    method_chain_args, return_ty,
+};
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::{BodyId, ExprKind, FnSig, HirId, OwnerId, Safety};
 use rustc_lint::LateContext;
-use rustc_middle::ty;
+use rustc_middle::mir::{BinOp, Rvalue, StatementKind, TerminatorKind, UnOp};
+use rustc_middle::ty::{self, EarlyBinder, GenericArgs, Instance, InstanceKind};
 use rustc_span::{Span, sym};
+use std::collections::VecDeque;
 use std::ops::ControlFlow;
 
+/// Maximum number of functions analyzed to find out whether a function cannot panic.
+const MAX_PANIC_CALLEES: usize = 256;
+
 pub fn check(
     cx: &LateContext<'_>,
     owner_id: OwnerId,
@@ -18,6 +31,7 @@ pub fn check(
     headers: DocHeaders,
     body_id: Option<BodyId>,
     check_private_items: bool,
+    panics_depth: u64,
 ) {
     if !check_private_items && !cx.effective_visibilities.is_exported(owner_id.def_id) {
         return; // Private functions do not require doc comments
@@ -51,15 +65,35 @@ pub fn check(
     }
     if !headers.panics
         && let Some(body_id) = body_id
-        && let Some(panic_span) = find_panic(cx, body_id)
+        && let Some(panic) = find_panic(cx, owner_id.def_id, body_id, panics_depth)
     {
-        span_lint_and_note(
+        span_lint_and_then(
             cx,
             MISSING_PANICS_DOC,
             span,
             "docs for function which may panic missing `# Panics` section",
-            Some(panic_span),
-            "first possible panic found here",
+            |diag| {
+                for &(call_span, callee) in &panic.calls {
+                    diag.span_note(
+                        call_span,
+                        format!("`{}` is called here, which may panic", cx.tcx.def_path_str(callee)),
+                    );
+                }
+                diag.span_note(panic.span, "first possible panic found here");
+            },
+        );
+    }
+    if headers.panics
+        && body_id.is_some()
+        && !is_lint_allowed(cx, UNNECESSARY_PANICS_DOC, owner_id.into())
+        && !fn_has_unsatisfiable_preds(cx, owner_id.to_def_id())
+        && cannot_panic(cx, owner_id.def_id)
+    {
+        span_lint(
+            cx,
+            UNNECESSARY_PANICS_DOC,
+            span,
+            "docs for function which cannot panic have unnecessary `# Panics` section",
         );
     }
     if !headers.errors {
@@ -96,14 +130,75 @@ pub fn check(
     }
 }
 
-fn find_panic(cx: &LateContext<'_>, body_id: BodyId) -> Option<Span> {
+/// A possible panic of a function.
+struct Panic {
+    /// The calls to the functions of the crate leading to the panic, with the called function.
+    calls: Vec<(Span, DefId)>,
+    span: Span,
+}
+
+/// Finds the first possible panic of the body of `owner`, or of the functions of the crate it
+/// calls up to `depth` nested calls. The calls are followed breadth-first, so that the shortest
+/// chain of calls to a panic is found, and each function is only searched once.
+fn find_panic(cx: &LateContext<'_>, owner: LocalDefId, body_id: BodyId, depth: u64) -> Option<Panic> {
+    let mut visited = FxHashSet::from_iter([owner]);
+    let mut queue = VecDeque::from([(body_id, Vec::<(HirId, Span, LocalDefId)>::new(), depth)]);
+    while let Some((body_id, chain, depth)) = queue.pop_front() {
+        let (panic_span, calls) = find_panic_in_body(cx, body_id, depth > 0);
+        if let Some(span) = panic_span {
+            if !fulfill_or_allowed(cx, MISSING_PANICS_DOC, chain.iter().map(|&(hir_id, ..)| hir_id)) {
+                return Some(Panic {
+                    calls: chain
+                        .into_iter()
+                        .map(|(_, call_span, callee)| (call_span, callee.to_def_id()))
+                        .collect(),
+                    span,
+                });
+            }
+            continue;
+        }
+
+        for call @ (_, _, callee) in calls {
+            if visited.insert(callee)
+                && let Some(body) = cx.tcx.hir_maybe_body_owned_by(callee)
+            {
+                let mut chain = chain.clone();
+                chain.push(call);
+                queue.push_back((body.id(), chain, depth - 1));
+            }
+        }
+    }
+    None
+}
+
+/// Finds the first possible panic of the body itself, along with the calls to the functions of
+/// the crate it makes if `follow_calls` is set.
+fn find_panic_in_body(
+    cx: &LateContext<'_>,
+    body_id: BodyId,
+    follow_calls: bool,
+) -> (Option<Span>, Vec<(HirId, Span, LocalDefId)>) {
     let mut panic_span = None;
+    let mut calls: Vec<(HirId, Span, LocalDefId)> = Vec::new();
     let typeck = cx.tcx.typeck_body(body_id);
     for_each_expr(cx.tcx, cx.tcx.hir_body(body_id), |expr| {
         if is_inside_always_const_context(cx.tcx, expr.hir_id) {
             return ControlFlow::<!>::Continue(());
         }
 
+        if follow_calls
+            && let Some(callee) = match expr.kind {
+                ExprKind::Call(func, _) if let ExprKind::Path(qpath) = &func.kind => {
+                    typeck.qpath_res(qpath, func.hir_id).opt_def_id()
+                },
+                ExprKind::MethodCall(..) => typeck.type_dependent_def_id(expr.hir_id),
+                _ => None,
+            }
+            && let Some(callee) = callee.as_local()
+        {
+            calls.push((expr.hir_id, expr.span, callee));
+        }
+
         if let Some(macro_call) = root_macro_call_first_node(cx, expr)
             && (is_panic(cx, macro_call.def_id)
                 || matches!(
@@ -130,5 +225,74 @@ fn find_panic(cx: &LateContext<'_>, body_id: BodyId) -> Option<Span> {
         // Visit all nodes to fulfill any `#[expect]`s after the first linted panic
         ControlFlow::<!>::Continue(())
     });
-    panic_span
+    (panic_span, calls)
+}
+
+/// Checks if the function cannot panic, analyzing the MIR of the functions it calls. The calls
+/// which cannot be resolved to a function with MIR are assumed to panic.
+fn cannot_panic(cx: &LateContext<'_>, def_id: LocalDefId) -> bool {
+    let tcx = cx.tcx;
+    let typing_env = ty::TypingEnv::post_analysis(tcx, def_id);
+    let root = Instance::new_raw(
+        def_id.to_def_id(),
+        GenericArgs::identity_for_item(tcx, def_id.to_def_id()),
+    );
+    let mut visited = FxHashSet::from_iter([root]);
+    let mut stack = vec![root];
+    while let Some(instance) = stack.pop() {
+        let InstanceKind::Item(callee) = instance.def else {
+            return false;
+        };
+        if visited.len() > MAX_PANIC_CALLEES || !tcx.is_mir_available(callee) || fn_has_unsatisfiable_preds(cx, callee)
+        {
+            return false;
+        }
+        let mir = tcx.optimized_mir(callee);
+        for data in mir.basic_blocks.iter() {
+            // Unchecked arithmetic of the crate may overflow, which panics in the builds with overflow
+            // checks, where it is checked by an `Assert` instead.
+            if callee.is_local()
+                && data.statements.iter().any(|statement| {
+                    if let StatementKind::Assign(box (_, rvalue)) = &statement.kind {
+                        match rvalue {
+                            Rvalue::BinaryOp(
+                                BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Shl | BinOp::Shr,
+                                box (lhs, _),
+                            ) => lhs.ty(mir, tcx).is_integral(),
+                            Rvalue::UnaryOp(UnOp::Neg, operand) => operand.ty(mir, tcx).is_signed(),
+                            _ => false,
+                        }
+                    } else {
+                        false
+                    }
+                })
+            {
+                return false;
+            }
+            match &data.terminator().kind {
+                TerminatorKind::Call { func, .. } | TerminatorKind::TailCall { func, .. } => {
+                    let Ok(func_ty) = instance.try_instantiate_mir_and_normalize_erasing_regions(
+                        tcx,
+                        typing_env,
+                        EarlyBinder::bind(tcx, func.ty(mir, tcx)),
+                    ) else {
+                        return false;
+                    };
+                    if let ty::FnDef(def_id, args) = *func_ty.kind()
+                        && let Some(args) = args.no_bound_vars()
+                        && let Ok(Some(callee)) = Instance::try_resolve(tcx, typing_env, def_id, args)
+                    {
+                        if visited.insert(callee) {
+                            stack.push(callee);
+                        }
+                    } else {
+                        return false;
+                    }
+                },
+                TerminatorKind::Assert { .. } | TerminatorKind::InlineAsm { .. } => return false,
+                _ => {},
+            }
+        }
+    }
+    true
 }
@@ -458,6 +458,10 @@ declare_clippy_lint! {
     /// Documenting the scenarios in which panicking occurs
     /// can help callers who do not want to panic to avoid those situations.
     ///
+    /// ### Configuration
+    /// The panics of the functions of the crate called by the function can be found by setting
+    /// `missing-panics-doc-depth` to the number of nested calls to follow.
+    ///
     /// ### Examples
     /// Since the following function may panic it has a `# Panics` section in
     /// its doc comment:
@@ -668,6 +672,44 @@ declare_clippy_lint! {
     "ensure the first documentation paragraph is short"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for the doc comments of functions that have a `# Panics` section although they
+    /// cannot panic: they neither panic themselves, e.g. by indexing or with arithmetic which may
+    /// overflow, nor call a function which may panic. The calls to functions which cannot be
+    /// analyzed, e.g. through a function pointer, are assumed to panic.
+    ///
+    /// ### Why is this bad?
+    /// The `# Panics` section misleads callers into handling panics which cannot occur, and is
+    /// likely outdated.
+    ///
+    /// ### Known problems
+    /// Panics in the `Drop` implementations of the dropped values are ignored.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /// Returns the first byte of `bytes`.
+    /// ///
+    /// /// # Panics
+    /// ///
+    /// /// Panics if `bytes` is empty.
+    /// pub fn first(bytes: &[u8]) -> Option<&u8> {
+    ///     bytes.first()
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// /// Returns the first byte of `bytes`.
+    /// pub fn first(bytes: &[u8]) -> Option<&u8> {
+    ///     bytes.first()
+    /// }
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub UNNECESSARY_PANICS_DOC,
+    restriction,
+    "`pub fn` which cannot panic with `# Panics` docs"
+}
+
 declare_clippy_lint! {
     /// ### What it does
     /// Checks for the doc comments of publicly visible
@@ -724,12 +766,14 @@ impl_lint_pass!(Documentation => [
     SUSPICIOUS_DOC_COMMENTS,
     TEST_ATTR_IN_DOCTEST,
     TOO_LONG_FIRST_DOC_PARAGRAPH,
+    UNNECESSARY_PANICS_DOC,
     UNNECESSARY_SAFETY_DOC,
 ]);
 
 pub struct Documentation {
     valid_idents: FxHashSet<String>,
     check_private_items: bool,
+    missing_panics_doc_depth: u64,
 }
 
 impl Documentation {
@@ -737,6 +781,7 @@ impl Documentation {
         Self {
             valid_idents: conf.doc_valid_idents.iter().cloned().collect(),
             check_private_items: conf.check_private_items,
+            missing_panics_doc_depth: conf.missing_panics_doc_depth,
         }
     }
 }
@@ -785,7 +830,15 @@ impl<'tcx> LateLintPass<'tcx> for Documentation {
                         if !(is_entrypoint_fn(cx, item.owner_id.to_def_id())
                             || item.span.in_external_macro(cx.tcx.sess.source_map())) =>
                     {
-                        missing_headers::check(cx, item.owner_id, sig, headers, Some(body), self.check_private_items);
+                        missing_headers::check(
+                            cx,
+                            item.owner_id,
+                            sig,
+                            headers,
+                            Some(body),
+                            self.check_private_items,
+                            self.missing_panics_doc_depth,
+                        );
                     },
                     ItemKind::Trait { safety, .. } => match (headers.safety, safety) {
                         (false, Safety::Unsafe) => span_lint(
@@ -809,7 +862,15 @@ impl<'tcx> LateLintPass<'tcx> for Documentation {
                 if let TraitItemKind::Fn(sig, ..) = trait_item.kind
                     && !trait_item.span.in_external_macro(cx.tcx.sess.source_map())
                 {
-                    missing_headers::check(cx, trait_item.owner_id, sig, headers, None, self.check_private_items);
+                    missing_headers::check(
+                        cx,
+                        trait_item.owner_id,
+                        sig,
+                        headers,
+                        None,
+                        self.check_private_items,
+                        self.missing_panics_doc_depth,
+                    );
                 }
             },
             Node::ImplItem(impl_item) => {
@@ -824,6 +885,7 @@ impl<'tcx> LateLintPass<'tcx> for Documentation {
                         headers,
                         Some(body_id),
                         self.check_private_items,
+                        self.missing_panics_doc_depth,
                     );
                 }
             },
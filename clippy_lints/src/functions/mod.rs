@@ -503,8 +503,7 @@ impl EarlyLintPass for EarlyFunctions {
 }
 
 pub struct Functions {
-    too_many_arguments_threshold: u64,
-    too_many_lines_threshold: u64,
+    conf: &'static Conf,
     large_error_threshold: u64,
     large_error_ignored: DefIdSet,
     avoid_breaking_exported_api: bool,
@@ -517,8 +516,7 @@ pub struct Functions {
 impl Functions {
     pub fn new(tcx: TyCtxt<'_>, conf: &'static Conf) -> Self {
         Self {
-            too_many_arguments_threshold: conf.too_many_arguments_threshold,
-            too_many_lines_threshold: conf.too_many_lines_threshold,
+            conf,
             large_error_threshold: conf.large_error_threshold,
             large_error_ignored: conf
                 .large_error_ignored
@@ -547,8 +545,9 @@ impl<'tcx> LateLintPass<'tcx> for Functions {
         def_id: LocalDefId,
     ) {
         let hir_id = cx.tcx.local_def_id_to_hir_id(def_id);
-        too_many_arguments::check_fn(cx, kind, decl, hir_id, def_id, self.too_many_arguments_threshold);
-        too_many_lines::check_fn(cx, kind, body, span, def_id, self.too_many_lines_threshold);
+        let conf = self.conf.in_file(cx.tcx.sess.source_map(), span);
+        too_many_arguments::check_fn(cx, kind, decl, hir_id, def_id, *conf.too_many_arguments_threshold());
+        too_many_lines::check_fn(cx, kind, body, span, def_id, *conf.too_many_lines_threshold());
         not_unsafe_ptr_arg_deref::check_fn(cx, kind, decl, body, def_id);
         misnamed_getters::check_fn(cx, kind, decl, body, span);
         impl_trait_in_params::check_fn(cx, &kind, body, hir_id);
@@ -589,7 +588,8 @@ impl<'tcx> LateLintPass<'tcx> for Functions {
     }
 
     fn check_trait_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::TraitItem<'_>) {
-        too_many_arguments::check_trait_item(cx, item, self.too_many_arguments_threshold);
+        let conf = self.conf.in_file(cx.tcx.sess.source_map(), item.span);
+        too_many_arguments::check_trait_item(cx, item, *conf.too_many_arguments_threshold());
         not_unsafe_ptr_arg_deref::check_trait_item(cx, item);
         must_use::check_trait_item(cx, item);
         result::check_trait_item(
@@ -0,0 +1,200 @@
+use clippy_config::Conf;
+use clippy_config::types::{HeldAcrossCall, unresolved_path_message};
+use clippy_utils::diagnostics::span_lint_hir_and_then;
+use clippy_utils::fn_has_unsatisfiable_preds;
+use clippy_utils::mir::{lint_root, live_locals};
+use clippy_utils::paths::{PathNS, lookup_path_glob, lookup_path_str};
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::intravisit::FnKind;
+use rustc_hir::{Body, FnDecl};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::mir::TerminatorKind;
+use rustc_middle::ty::{self, Instance, Ty, TyCtxt};
+use rustc_session::impl_lint_pass;
+use rustc_span::{Span, Symbol};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for calls to the functions configured in `held-across-call` while a value of the
+    /// type they are configured with is alive, e.g. a lock guard while calling a blocking function.
+    ///
+    /// Note: Even though this lint is warn-by-default, it will only trigger if
+    /// types are configured in the clippy.toml file.
+    ///
+    /// ### Why is this bad?
+    /// Holding a lock while blocking keeps the other threads waiting on the lock for as long as the
+    /// call blocks, and holding the guard of a `tracing` span while blocking attributes the time
+    /// spent waiting to that span.
+    ///
+    /// ### Example
+    /// ```toml
+    /// held-across-call = [
+    ///   { path = "std::sync::MutexGuard", callees = ["std::thread::sleep", "std::sync::mpsc::Receiver::recv"] },
+    ///   # Can use `*` wildcards in the last segment of the callees and add a reason
+    ///   { path = "tracing::span::Entered", callees = ["reqwest::blocking::*"], reason = "enter the span in the spawned task" },
+    /// ]
+    /// ```
+    ///
+    /// ```no_run
+    /// # use std::sync::Mutex;
+    /// # use std::time::Duration;
+    /// fn update(state: &Mutex<u32>) {
+    ///     let mut guard = state.lock().unwrap();
+    ///     *guard += 1;
+    ///     std::thread::sleep(Duration::from_secs(1));
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # use std::sync::Mutex;
+    /// # use std::time::Duration;
+    /// fn update(state: &Mutex<u32>) {
+    ///     let mut guard = state.lock().unwrap();
+    ///     *guard += 1;
+    ///     drop(guard);
+    ///     std::thread::sleep(Duration::from_secs(1));
+    /// }
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub HELD_ACROSS_CALL,
+    suspicious,
+    "holding a configured type across a call to a configured function"
+}
+
+impl_lint_pass!(HeldAcrossCallLint => [HELD_ACROSS_CALL]);
+
+pub struct HeldAcrossCallLint {
+    entries: Vec<Entry>,
+}
+
+struct Entry {
+    types: Vec<DefId>,
+    callees: FxHashSet<DefId>,
+    conf: &'static HeldAcrossCall,
+}
+
+impl HeldAcrossCallLint {
+    pub fn new(tcx: TyCtxt<'_>, conf: &'static Conf) -> Self {
+        let mut entries = Vec::new();
+        for conf in &conf.held_across_call {
+            let types = lookup_path_str(tcx, PathNS::Type, &conf.path);
+            if types.is_empty()
+                && let Some(message) = unresolved_path_message(tcx, &conf.path, "type")
+            {
+                tcx.sess.dcx().span_warn(conf.span, message);
+            }
+            let mut callees = FxHashSet::default();
+            for callee in &conf.callees {
+                let path: Vec<Symbol> = callee.split("::").map(Symbol::intern).collect();
+                let resolutions = lookup_path_glob(tcx, PathNS::Value, &path);
+                if resolutions.is_empty()
+                    && let Some(message) = unresolved_path_message(tcx, callee, "function")
+                {
+                    tcx.sess.dcx().span_warn(conf.span, message);
+                }
+                callees.extend(resolutions);
+            }
+            if !types.is_empty() && !callees.is_empty() {
+                entries.push(Entry { types, callees, conf });
+            }
+        }
+        Self { entries }
+    }
+
+    /// Finds the entries for a call to `callee` with the generic arguments `args`. Calls to trait
+    /// methods are also checked against the method of the impl they resolve to.
+    fn entries_for_call<'tcx>(
+        &self,
+        cx: &LateContext<'tcx>,
+        callee: DefId,
+        args: ty::GenericArgsRef<'tcx>,
+    ) -> impl Iterator<Item = &Entry> {
+        let resolved = Instance::try_resolve(cx.tcx, cx.typing_env(), callee, args)
+            .ok()
+            .flatten()
+            .map(|instance| instance.def_id());
+        self.entries.iter().filter(move |entry| {
+            entry.callees.contains(&callee) || resolved.is_some_and(|resolved| entry.callees.contains(&resolved))
+        })
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for HeldAcrossCallLint {
+    fn check_fn(
+        &mut self,
+        cx: &LateContext<'tcx>,
+        _: FnKind<'tcx>,
+        _: &'tcx FnDecl<'_>,
+        _: &'tcx Body<'_>,
+        _: Span,
+        def_id: LocalDefId,
+    ) {
+        // Building MIR for `fn`s with unsatisfiable preds results in ICE.
+        if self.entries.is_empty() || fn_has_unsatisfiable_preds(cx, def_id.to_def_id()) {
+            return;
+        }
+
+        let mir = cx.tcx.optimized_mir(def_id.to_def_id());
+        let mut live = None;
+        let mut linted = FxHashSet::default();
+        for (bb, bbdata) in mir.basic_blocks.iter_enumerated() {
+            let terminator = bbdata.terminator();
+            if let TerminatorKind::Call {
+                func,
+                destination,
+                target: Some(target),
+                fn_span,
+                ..
+            } = &terminator.kind
+                && let Some((callee, args)) = func.const_fn_def()
+                && let mut entries = self.entries_for_call(cx, callee, args).peekable()
+                && entries.peek().is_some()
+            {
+                // The locals which are live once the call returns are held during the call.
+                let live = live.get_or_insert_with(|| live_locals(cx.tcx, mir));
+                live.seek_to_block_start(*target);
+                let held = entries.find_map(|entry| {
+                    live.get()
+                        .iter()
+                        .filter(|&local| local != destination.local)
+                        .find(|&local| holds(mir.local_decls[local].ty, &entry.types))
+                        .map(|local| (entry, local))
+                });
+                if let Some((entry, local)) = held
+                    && linted.insert(*fn_span)
+                {
+                    span_lint_hir_and_then(
+                        cx,
+                        HELD_ACROSS_CALL,
+                        lint_root(mir, mir.terminator_loc(bb)),
+                        *fn_span,
+                        format!(
+                            "calling `{}` while holding a `{}`",
+                            cx.tcx.def_path_str(callee),
+                            entry.conf.path
+                        ),
+                        |diag| {
+                            diag.span_note(mir.local_decls[local].source_info.span, "the value is held here");
+                            if let Some(reason) = &entry.conf.reason {
+                                diag.note(reason.clone());
+                            }
+                        },
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Checks if a value of type `ty` holds a value of one of `types`, ignoring the values behind
+/// references and pointers. The generic arguments of an ADT are assumed to be held by it, as in
+/// `Option<MutexGuard<'_, T>>`.
+fn holds(ty: Ty<'_>, types: &[DefId]) -> bool {
+    match ty.kind() {
+        ty::Adt(adt, args) => types.contains(&adt.did()) || args.types().any(|ty| holds(ty, types)),
+        ty::Array(ty, _) | ty::Slice(ty) => holds(*ty, types),
+        ty::Tuple(tys) => tys.iter().any(|ty| holds(ty, types)),
+        _ => false,
+    }
+}
@@ -0,0 +1,258 @@
+use crate::cargo;
+use cargo_metadata::{Dependency, DependencyKind, Package};
+use clippy_config::Conf;
+use clippy_config::types::ApiVersion;
+use clippy_utils::diagnostics::{span_lint, span_lint_and_help};
+use clippy_utils::paths::{PathNS, lookup_path_glob};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::{CrateNum, DefId};
+use rustc_hir::{self as hir, AmbigArg, Expr, ExprKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext as _};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::impl_lint_pass;
+use rustc_span::{DUMMY_SP, Span, Symbol};
+use semver::{Op, Version, VersionReq};
+use std::env;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for uses of items of dependencies which were added in a version newer than the
+    /// minimum version allowed by the requirement of the dependency in `Cargo.toml`, as configured
+    /// in `api-versions`.
+    ///
+    /// Note: Even though this lint is warn-by-default, it will only trigger if
+    /// items are configured in the clippy.toml file.
+    ///
+    /// ### Why is this bad?
+    /// The crate does not build with the older versions of the dependency allowed by its
+    /// requirement, e.g. when built with `-Z minimal-versions` or when another crate of the
+    /// dependency graph pins the dependency to an older version.
+    ///
+    /// ### Example
+    /// ```toml
+    /// # clippy.toml
+    /// api-versions = [{ path = "tokio::task::JoinSet::try_join_next", since = "1.38.0" }]
+    ///
+    /// # Cargo.toml
+    /// [dependencies]
+    /// tokio = "1.32"
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// fn next(set: &mut tokio::task::JoinSet<()>) {
+    ///     set.try_join_next();
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```toml
+    /// [dependencies]
+    /// tokio = "1.38"
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub INCOMPATIBLE_DEPENDENCY_VERSION,
+    suspicious,
+    "using an item newer than the minimum version of its dependency"
+}
+
+impl_lint_pass!(IncompatibleDependencyVersion => [
+    INCOMPATIBLE_DEPENDENCY_VERSION,
+]);
+
+pub struct IncompatibleDependencyVersion {
+    /// The version which added each configured item.
+    items: FxHashMap<DefId, (&'static ApiVersion, Version)>,
+    /// The requirement of each dependency, by the name of the library of its package, with the
+    /// minimum version it allows.
+    requirements: FxHashMap<Symbol, (VersionReq, Version)>,
+}
+
+impl IncompatibleDependencyVersion {
+    pub fn new(tcx: TyCtxt<'_>, conf: &'static Conf) -> Self {
+        let mut items = FxHashMap::default();
+        for api in &conf.api_versions {
+            let Ok(since) = Version::parse(&api.since) else {
+                tcx.sess
+                    .dcx()
+                    .warn(format!("`{}` is not a valid version for `{}`", api.since, api.path));
+                continue;
+            };
+            let path: Vec<Symbol> = api.path.split("::").map(Symbol::intern).collect();
+            for ns in [PathNS::Type, PathNS::Value] {
+                for def_id in lookup_path_glob(tcx, ns, &path) {
+                    items.insert(def_id, (api, since.clone()));
+                }
+            }
+        }
+        Self {
+            items,
+            requirements: FxHashMap::default(),
+        }
+    }
+
+    /// Emit lint if `def_id`, or an item containing it, was added in a version newer than the
+    /// minimum version of its crate.
+    fn check_use(&self, cx: &LateContext<'_>, def_id: DefId, span: Span) {
+        if def_id.is_local() || span.in_external_macro(cx.sess().source_map()) {
+            return;
+        }
+        let Some((api, since)) = std::iter::successors(Some(def_id), |&def_id| parent(cx.tcx, def_id))
+            .find_map(|def_id| self.items.get(&def_id))
+        else {
+            return;
+        };
+        let krate = cx.tcx.crate_name(def_id.krate);
+        if let Some((req, min)) = self.requirements.get(&krate)
+            && min < since
+        {
+            span_lint_and_help(
+                cx,
+                INCOMPATIBLE_DEPENDENCY_VERSION,
+                span,
+                format!(
+                    "`{}` was added in `{krate} {since}`, but the requirement `{req}` allows `{krate} {min}`",
+                    api.path
+                ),
+                None,
+                format!("require at least `{krate} {since}` in `Cargo.toml`"),
+            );
+        }
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for IncompatibleDependencyVersion {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        if self.items.is_empty() {
+            return;
+        }
+        let Ok(package_name) = env::var("CARGO_PKG_NAME") else {
+            // Not built by Cargo, so there are no requirements to check
+            return;
+        };
+        let metadata = match cargo::metadata() {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                span_lint(
+                    cx,
+                    INCOMPATIBLE_DEPENDENCY_VERSION,
+                    DUMMY_SP,
+                    format!("could not read cargo metadata: {e}"),
+                );
+                return;
+            },
+        };
+        let Some(package) = metadata.packages.iter().find(|package| *package.name == package_name) else {
+            return;
+        };
+        let crates: FxHashMap<Symbol, CrateNum> = cx
+            .tcx
+            .crates(())
+            .iter()
+            .map(|&krate| (cx.tcx.crate_name(krate), krate))
+            .collect();
+        let is_build_script = cx
+            .sess()
+            .opts
+            .crate_name
+            .as_ref()
+            .is_some_and(|crate_name| crate_name == "build_script_build");
+        // Only the sections of the dependencies available to the crate being compiled
+        let kinds = [
+            (DependencyKind::Normal, !is_build_script),
+            (DependencyKind::Development, cx.sess().opts.test),
+            (DependencyKind::Build, is_build_script),
+        ];
+        for dep in package
+            .dependencies
+            .iter()
+            .filter(|dep| kinds.contains(&(dep.kind, true)))
+        {
+            let name = Symbol::intern(&lib_name(&metadata.packages, dep));
+            if !crates.contains_key(&name) {
+                continue;
+            }
+            let min = min_version(&dep.req);
+            // A dependency can be required by several sections, e.g. `[dependencies]` and
+            // `[dev-dependencies]` for tests, the lowest requirement must be met.
+            match self.requirements.get(&name) {
+                Some((_, other)) if *other <= min => {},
+                _ => {
+                    self.requirements.insert(name, (dep.req.clone(), min));
+                },
+            }
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if self.requirements.is_empty() {
+            return;
+        }
+        match expr.kind {
+            ExprKind::MethodCall(segment, ..) => {
+                if let Some(def_id) = cx.typeck_results().type_dependent_def_id(expr.hir_id) {
+                    self.check_use(cx, def_id, segment.ident.span);
+                }
+            },
+            ExprKind::Path(ref qpath) => {
+                if let Some(def_id) = cx.qpath_res(qpath, expr.hir_id).opt_def_id() {
+                    self.check_use(cx, def_id, expr.span);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn check_ty(&mut self, cx: &LateContext<'tcx>, hir_ty: &'tcx hir::Ty<'tcx, AmbigArg>) {
+        if !self.requirements.is_empty()
+            && let hir::TyKind::Path(qpath) = hir_ty.kind
+            && let Some(def_id) = cx.qpath_res(&qpath, hir_ty.hir_id).opt_def_id()
+        {
+            self.check_use(cx, def_id, hir_ty.span);
+        }
+    }
+}
+
+/// Returns the item containing `def_id`, which is the self type for the items of inherent impls.
+fn parent(tcx: TyCtxt<'_>, def_id: DefId) -> Option<DefId> {
+    let parent = tcx.opt_parent(def_id)?;
+    if let DefKind::Impl { of_trait: false } = tcx.def_kind(parent)
+        && let Some(adt) = tcx.type_of(parent).instantiate_identity().skip_norm_wip().ty_adt_def()
+    {
+        Some(adt.did())
+    } else {
+        Some(parent)
+    }
+}
+
+/// Returns the name of the library of the package required by `dep`, which names the crate of
+/// the dependency even if it is renamed in `Cargo.toml`, e.g. `foo2 = { package = "foo" }`.
+fn lib_name(packages: &[Package], dep: &Dependency) -> String {
+    packages
+        .iter()
+        .filter(|package| *package.name == dep.name && dep.req.matches(&package.version))
+        .flat_map(|package| &package.targets)
+        .find(|target| target.is_lib() || target.is_rlib() || target.is_dylib() || target.is_proc_macro())
+        .map_or(&dep.name, |target| &target.name)
+        .replace('-', "_")
+}
+
+/// Returns the minimum version matching all the comparators of `req`, ignoring the upper bounds.
+fn min_version(req: &VersionReq) -> Version {
+    req.comparators
+        .iter()
+        .map(|comparator| {
+            let (major, minor, patch) = (comparator.major, comparator.minor, comparator.patch);
+            match comparator.op {
+                Op::Greater => match (minor, patch) {
+                    (Some(minor), Some(patch)) => Version::new(major, minor, patch + 1),
+                    (Some(minor), None) => Version::new(major, minor + 1, 0),
+                    (None, _) => Version::new(major + 1, 0, 0),
+                },
+                Op::Less | Op::LessEq => Version::new(0, 0, 0),
+                _ => Version::new(major, minor.unwrap_or(0), patch.unwrap_or(0)),
+            }
+        })
+        .max()
+        .unwrap_or(Version::new(0, 0, 0))
+}
@@ -1,10 +1,14 @@
 use clippy_config::Conf;
 use clippy_utils::diagnostics::span_lint_and_then;
-use clippy_utils::msrvs::Msrv;
+use clippy_utils::msrvs::{self, Msrv, MsrvStack};
 use clippy_utils::{is_in_const_context, is_in_test, sym};
+use rustc_ast::ast::{
+    self, AttrKind, BinOpKind, BorrowKind, CoroutineKind, LocalKind, PatKind, RangeEnd, StmtKind, SyntheticAttr,
+};
+use rustc_ast::token;
 use rustc_data_structures::fx::FxHashMap;
 use rustc_hir::{self as hir, AmbigArg, Expr, ExprKind, HirId, RustcVersion, StabilityLevel, StableSince, find_attr};
-use rustc_lint::{LateContext, LateLintPass};
+use rustc_lint::{EarlyContext, EarlyLintPass, LateContext, LateLintPass, LintContext as _};
 use rustc_middle::ty::{self, TyCtxt};
 use rustc_session::impl_lint_pass;
 use rustc_span::def_id::{CrateNum, DefId};
@@ -13,8 +17,8 @@ use rustc_span::{ExpnKind, Span};
 declare_clippy_lint! {
     /// ### What it does
     ///
-    /// This lint checks that no function newer than the defined MSRV (minimum
-    /// supported rust version) is used in the crate.
+    /// This lint checks that no function or syntax newer than the defined MSRV
+    /// (minimum supported rust version) is used in the crate.
     ///
     /// ### Why is this bad?
     ///
@@ -30,8 +34,22 @@ declare_clippy_lint! {
     /// sleep(Duration::new(1, 0));
     /// ```
     ///
+    /// The syntax checked includes `let` chains, `let...else`, `&raw` borrows,
+    /// C string literals, inline `const` blocks, exclusive range patterns,
+    /// async closures and the `?` operator:
+    ///
+    /// ```no_run
+    /// // MSRV of 1.70.0
+    /// fn first(x: &[u8]) -> u8 {
+    ///     // `let...else` was stabilized in `1.65.0`, but inline `const`
+    ///     // blocks in `1.79.0`.
+    ///     let [first, ..] = x else { return const { 1 + 1 } };
+    ///     *first
+    /// }
+    /// ```
+    ///
     /// To fix this problem, either increase your MSRV or use another item
-    /// available in your current MSRV.
+    /// or syntax available in your current MSRV.
     ///
     /// You can also locally change the MSRV that should be checked by Clippy,
     /// for example if a feature in your crate (e.g., `modern_compiler`) should
@@ -73,6 +91,8 @@ declare_clippy_lint! {
 
 impl_lint_pass!(IncompatibleMsrv => [INCOMPATIBLE_MSRV]);
 
+impl_lint_pass!(IncompatibleMsrvSyntax => [INCOMPATIBLE_MSRV]);
+
 #[derive(Clone, Copy)]
 enum Availability {
     FeatureEnabled,
@@ -272,3 +292,127 @@ fn is_under_cfg_attribute(cx: &LateContext<'_>, hir_id: HirId) -> bool {
         .hir_parent_id_iter(hir_id)
         .any(|id| find_attr!(cx.tcx, id, CfgTrace(..) | CfgAttrTrace))
 }
+
+/// Checks the syntax used in the crate, as the language features are not items with a stability
+/// attribute.
+pub struct IncompatibleMsrvSyntax {
+    msrv: MsrvStack,
+    check_in_tests: bool,
+    /// The number of nodes being checked which are under a `#[cfg()]` or `#[cfg_attr()]`
+    /// attribute.
+    cfg_depth: usize,
+}
+
+impl IncompatibleMsrvSyntax {
+    pub fn new(conf: &'static Conf) -> Self {
+        Self {
+            msrv: MsrvStack::new(conf.msrv),
+            check_in_tests: conf.check_incompatible_msrv_in_tests,
+            cfg_depth: 0,
+        }
+    }
+
+    /// Emit lint if the syntax `feature`, stabilized in `version`, is used at `span` while the
+    /// current MSRV is lower.
+    fn check(&self, cx: &EarlyContext<'_>, span: Span, feature: &str, version: RustcVersion) {
+        // The syntax generated by macros is not written by the user, and the `--test` harness is
+        // only built with the toolchain used for development.
+        if let Some(current) = self.msrv.current()
+            && version > current
+            && !span.from_expansion()
+            && (self.check_in_tests || !cx.sess().is_test_crate())
+        {
+            span_lint_and_then(
+                cx,
+                INCOMPATIBLE_MSRV,
+                span,
+                format!(
+                    "current MSRV (Minimum Supported Rust Version) is `{current}` but {feature} are stable since `{version}`"
+                ),
+                |diag| {
+                    if self.cfg_depth > 0 {
+                        diag.note_once("you may want to conditionally increase the MSRV considered by Clippy using the `clippy::msrv` attribute");
+                    }
+                },
+            );
+        }
+    }
+}
+
+impl EarlyLintPass for IncompatibleMsrvSyntax {
+    fn check_expr(&mut self, cx: &EarlyContext<'_>, expr: &ast::Expr) {
+        match &expr.kind {
+            ast::ExprKind::If(cond, ..) | ast::ExprKind::While(cond, ..) if is_let_chain(cond) => {
+                self.check(cx, cond.span, "`let` chains", msrvs::LET_CHAINS);
+            },
+            ast::ExprKind::AddrOf(BorrowKind::Raw, ..) => {
+                self.check(cx, expr.span, "`&raw` borrows", msrvs::RAW_REF_OP);
+            },
+            ast::ExprKind::Lit(lit) if matches!(lit.kind, token::LitKind::CStr | token::LitKind::CStrRaw(_)) => {
+                self.check(cx, expr.span, "C string literals", msrvs::C_STR_LITERALS);
+            },
+            ast::ExprKind::ConstBlock(_) => {
+                self.check(cx, expr.span, "inline `const` blocks", msrvs::CONST_BLOCKS);
+            },
+            ast::ExprKind::Closure(closure) if matches!(closure.coroutine_kind, Some(CoroutineKind::Async { .. })) => {
+                self.check(cx, closure.fn_decl_span, "async closures", msrvs::ASYNC_CLOSURE);
+            },
+            ast::ExprKind::Try(_) => {
+                self.check(cx, expr.span, "`?` operators", msrvs::QUESTION_MARK_OPERATOR);
+            },
+            _ => {},
+        }
+    }
+
+    fn check_stmt(&mut self, cx: &EarlyContext<'_>, stmt: &ast::Stmt) {
+        if let StmtKind::Let(local) = &stmt.kind
+            && let LocalKind::InitElse(..) = local.kind
+        {
+            self.check(cx, stmt.span, "`let...else` statements", msrvs::LET_ELSE);
+        }
+    }
+
+    fn check_pat(&mut self, cx: &EarlyContext<'_>, pat: &ast::Pat) {
+        // Half-open range patterns such as `10..` are stable since 1.55, independently of the
+        // exclusive range patterns with an end
+        if let PatKind::Range(_, Some(_), end) = &pat.kind
+            && let RangeEnd::Excluded = end.node
+        {
+            self.check(cx, pat.span, "exclusive range patterns", msrvs::EXCLUSIVE_RANGE_PATTERN);
+        }
+    }
+
+    fn check_attributes(&mut self, cx: &EarlyContext<'_>, attrs: &[ast::Attribute]) {
+        self.msrv.check_attributes(cx.sess(), attrs);
+        if has_cfg_attribute(attrs) {
+            self.cfg_depth += 1;
+        }
+    }
+
+    fn check_attributes_post(&mut self, cx: &EarlyContext<'_>, attrs: &[ast::Attribute]) {
+        self.msrv.check_attributes_post(cx.sess(), attrs);
+        if has_cfg_attribute(attrs) {
+            self.cfg_depth -= 1;
+        }
+    }
+}
+
+/// Checks if the attributes of a node contain a `#[cfg()]` or `#[cfg_attr()]` attribute.
+fn has_cfg_attribute(attrs: &[ast::Attribute]) -> bool {
+    attrs
+        .iter()
+        .any(|attr| matches!(&attr.kind, AttrKind::Synthetic(synthetic) if matches!(**synthetic, SyntheticAttr::CfgTrace(_) | SyntheticAttr::CfgAttrTrace)))
+}
+
+/// Checks if the condition `cond` of an `if` or `while` is a chain of `let` expressions joined
+/// with `&&`, e.g. `if let Some(x) = y && x > 0`.
+fn is_let_chain(cond: &ast::Expr) -> bool {
+    fn contains_let(expr: &ast::Expr) -> bool {
+        match &expr.kind {
+            ast::ExprKind::Binary(op, lhs, rhs) if op.node == BinOpKind::And => contains_let(lhs) || contains_let(rhs),
+            ast::ExprKind::Let(..) => true,
+            _ => false,
+        }
+    }
+    matches!(&cond.kind, ast::ExprKind::Binary(op, ..) if op.node == BinOpKind::And) && contains_let(cond)
+}
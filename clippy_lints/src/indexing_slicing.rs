@@ -8,6 +8,7 @@ use rustc_hir::{Expr, ExprKind};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::ty::{self, Ty};
 use rustc_session::impl_lint_pass;
+use rustc_span::Span;
 
 declare_clippy_lint! {
     /// ### What it does
@@ -123,112 +124,125 @@ impl<'tcx> LateLintPass<'tcx> for IndexingSlicing {
         {
             let note = "the suggestion might not be applicable in constant blocks";
             let ty = cx.typeck_results().expr_ty(array).peel_refs();
-            let allowed_in_tests = self.allow_indexing_slicing_in_tests && is_in_test(cx.tcx, expr.hir_id);
-            if let Some(range) = higher::Range::hir(cx, index) {
-                // Ranged indexes, i.e., &x[n..m], &x[n..], &x[..n] and &x[..]
-                if let ty::Array(_, s) = ty.kind() {
-                    let size: u128 = if let Some(size) = s.try_to_target_usize(cx.tcx) {
-                        size.into()
-                    } else {
-                        return;
-                    };
-
-                    let const_range = to_const_range(cx, range, size);
-
-                    if let (Some(start), _) = const_range
-                        && start > size
-                    {
-                        span_lint(
-                            cx,
-                            OUT_OF_BOUNDS_INDEXING,
-                            range.start.map_or(expr.span, |start| start.span),
-                            "range is out of bounds",
-                        );
-                        return;
-                    }
-
-                    if let (_, Some(end)) = const_range
-                        && end > size
-                    {
-                        span_lint(
-                            cx,
-                            OUT_OF_BOUNDS_INDEXING,
-                            range.end.map_or(expr.span, |end| end.span),
-                            "range is out of bounds",
-                        );
-                        return;
-                    }
-
-                    if let (Some(_), Some(_)) = const_range {
-                        // early return because both start and end are constants
-                        // and we have proven above that they are in bounds
-                        return;
-                    }
-                }
+            match index_bounds(cx, ty, expr, index) {
+                IndexBounds::InBounds => return,
+                IndexBounds::OutOfBounds(span, msg) => {
+                    span_lint(cx, OUT_OF_BOUNDS_INDEXING, span, msg);
+                    return;
+                },
+                IndexBounds::Unknown => {},
+            }
+            if self.allow_indexing_slicing_in_tests && is_in_test(cx.tcx, expr.hir_id) {
+                return;
+            }
 
+            let (msg, help_msg) = if let Some(range) = higher::Range::hir(cx, index) {
+                // Ranged indexes, i.e., &x[n..m], &x[n..] and &x[..n]
                 let help_msg = match (range.start, range.end) {
-                    (None, Some(_)) => "consider using `.get(..n)`or `.get_mut(..n)` instead",
+                    (None, _) => "consider using `.get(..n)`or `.get_mut(..n)` instead",
                     (Some(_), None) => "consider using `.get(n..)` or .get_mut(n..)` instead",
                     (Some(_), Some(_)) => "consider using `.get(n..m)` or `.get_mut(n..m)` instead",
-                    (None, None) => return, // [..] is ok.
                 };
+                ("slicing may panic", help_msg)
+            } else {
+                // Catchall non-range index, i.e., [n] or [n << m]
+                (
+                    "indexing may panic",
+                    "consider using `.get(n)` or `.get_mut(n)` instead",
+                )
+            };
+            span_lint_and_then(cx, INDEXING_SLICING, expr.span, msg, |diag| {
+                diag.help(help_msg);
 
-                if allowed_in_tests {
-                    return;
+                if cx.tcx.hir_is_inside_const_context(expr.hir_id) {
+                    diag.note(note);
                 }
+            });
+        }
+    }
+}
 
-                span_lint_and_then(cx, INDEXING_SLICING, expr.span, "slicing may panic", |diag| {
-                    diag.help(help_msg);
+/// Whether an indexing or slicing expression is known to be in bounds.
+pub(crate) enum IndexBounds {
+    /// The index is in bounds, or the indexing is checked by rustc.
+    InBounds,
+    /// The index is a constant out of the bounds of an array, with the span and message to lint.
+    OutOfBounds(Span, &'static str),
+    /// The index may be out of bounds.
+    Unknown,
+}
 
-                    if cx.tcx.hir_is_inside_const_context(expr.hir_id) {
-                        diag.note(note);
-                    }
-                });
-            } else {
-                // Catchall non-range index, i.e., [n] or [n << m]
-                if let ty::Array(..) = ty.kind() {
-                    // Index is a const block.
-                    if let ExprKind::ConstBlock(..) = index.kind {
-                        return;
-                    }
-                    // Index is a constant uint.
-                    if let Some(constant) = ConstEvalCtxt::new(cx).eval(index) {
-                        // only `usize` index is legal in rust array index
-                        // leave other type to rustc
-                        if let Constant::Int(off) = constant
-                            && off <= usize::MAX as u128
-                            && let ty::Uint(utype) = cx.typeck_results().expr_ty(index).kind()
-                            && *utype == ty::UintTy::Usize
-                            && let ty::Array(_, s) = ty.kind()
-                            && let Some(size) = s.try_to_target_usize(cx.tcx)
-                        {
-                            // get constant offset and check whether it is in bounds
-                            let off = usize::try_from(off).unwrap();
-                            let size = usize::try_from(size).unwrap();
+/// Checks if the index of the indexing or slicing expression `expr` on a value of type `ty` is in
+/// bounds: either the full range `[..]`, or constants in the bounds of an array.
+pub(crate) fn index_bounds<'tcx>(
+    cx: &LateContext<'tcx>,
+    ty: Ty<'tcx>,
+    expr: &Expr<'_>,
+    index: &'tcx Expr<'_>,
+) -> IndexBounds {
+    if let Some(range) = higher::Range::hir(cx, index) {
+        // Ranged indexes, i.e., &x[n..m], &x[n..], &x[..n] and &x[..]
+        if let ty::Array(_, s) = ty.kind()
+            && let Some(size) = s.try_to_target_usize(cx.tcx)
+        {
+            let size = u128::from(size);
+            let const_range = to_const_range(cx, range, size);
 
-                            if off >= size {
-                                span_lint(cx, OUT_OF_BOUNDS_INDEXING, expr.span, "index is out of bounds");
-                            }
-                        }
-                        // Let rustc's `const_err` lint handle constant `usize` indexing on arrays.
-                        return;
-                    }
-                }
+            if let (Some(start), _) = const_range
+                && start > size
+            {
+                return IndexBounds::OutOfBounds(
+                    range.start.map_or(expr.span, |start| start.span),
+                    "range is out of bounds",
+                );
+            }
 
-                if allowed_in_tests {
-                    return;
-                }
+            if let (_, Some(end)) = const_range
+                && end > size
+            {
+                return IndexBounds::OutOfBounds(range.end.map_or(expr.span, |end| end.span), "range is out of bounds");
+            }
 
-                span_lint_and_then(cx, INDEXING_SLICING, expr.span, "indexing may panic", |diag| {
-                    diag.help("consider using `.get(n)` or `.get_mut(n)` instead");
+            if let (Some(_), Some(_)) = const_range {
+                // both start and end are constants and we have proven above that they are in
+                // bounds
+                return IndexBounds::InBounds;
+            }
+        }
 
-                    if cx.tcx.hir_is_inside_const_context(expr.hir_id) {
-                        diag.note(note);
-                    }
-                });
+        if range.start.is_none() && range.end.is_none() {
+            // [..] is ok.
+            return IndexBounds::InBounds;
+        }
+    } else if let ty::Array(..) = ty.kind() {
+        // Index is a const block.
+        if let ExprKind::ConstBlock(..) = index.kind {
+            return IndexBounds::InBounds;
+        }
+        // Index is a constant uint.
+        if let Some(constant) = ConstEvalCtxt::new(cx).eval(index) {
+            // only `usize` index is legal in rust array index
+            // leave other type to rustc
+            if let Constant::Int(off) = constant
+                && off <= usize::MAX as u128
+                && let ty::Uint(utype) = cx.typeck_results().expr_ty(index).kind()
+                && *utype == ty::UintTy::Usize
+                && let ty::Array(_, s) = ty.kind()
+                && let Some(size) = s.try_to_target_usize(cx.tcx)
+            {
+                // get constant offset and check whether it is in bounds
+                let off = usize::try_from(off).unwrap();
+                let size = usize::try_from(size).unwrap();
+
+                if off >= size {
+                    return IndexBounds::OutOfBounds(expr.span, "index is out of bounds");
+                }
             }
+            // Let rustc's `const_err` lint handle constant `usize` indexing on arrays.
+            return IndexBounds::InBounds;
         }
     }
+    IndexBounds::Unknown
 }
 
 /// Returns a tuple of options with the start and end (exclusive) values of
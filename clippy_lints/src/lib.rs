@@ -57,6 +57,7 @@ mod utils;
 
 mod combined_early_pass;
 mod combined_late_pass;
+mod pass_times;
 
 pub mod declared_lints;
 pub mod deprecated_lints;
@@ -113,6 +114,7 @@ mod disallowed_macros;
 mod disallowed_methods;
 mod disallowed_names;
 mod disallowed_script_idents;
+mod disallowed_trait_impls;
 mod disallowed_types;
 mod doc;
 mod double_parens;
@@ -152,6 +154,7 @@ mod from_raw_with_void_ptr;
 mod from_str_radix_10;
 mod functions;
 mod future_not_send;
+mod held_across_call;
 mod if_let_mutex;
 mod if_not_else;
 mod if_then_some_else_none;
@@ -163,6 +166,7 @@ mod implicit_return;
 mod implicit_saturating_add;
 mod implicit_saturating_sub;
 mod implied_bounds_in_impls;
+mod incompatible_dependency_version;
 mod incompatible_msrv;
 mod inconsistent_struct_constructor;
 mod index_refutable_slice;
@@ -197,6 +201,7 @@ mod let_with_type_underscore;
 mod lifetimes;
 mod literal_representation;
 mod literal_string_with_formatting_args;
+mod locks;
 mod loops;
 mod macro_metavars_in_unsafe;
 mod macro_use;
@@ -289,6 +294,7 @@ mod only_used_in_recursion;
 mod operators;
 mod option_env_unwrap;
 mod option_if_let_else;
+mod panic_in_no_panic_fn;
 mod panic_in_result_fn;
 mod panic_unimplemented;
 mod panicking_overflow_checks;
@@ -418,7 +424,7 @@ mod zombie_processes;
 use clippy_config::{Conf, get_configuration_metadata, sanitize_explanation};
 use clippy_utils::macros::FormatArgsStorage;
 use rustc_data_structures::fx::FxHashSet;
-use rustc_lint::is_lint_pass_required;
+use rustc_lint::{Lint, LintId, is_lint_pass_required};
 use rustc_middle::ty::TyCtxt;
 use utils::attr_collector::AttrStorage;
 
@@ -466,25 +472,65 @@ pub fn register_lint_passes(store: &mut rustc_lint::LintStore, conf: &'static Co
     let format_args_storage = FormatArgsStorage::default();
     let attr_storage = AttrStorage::default();
 
+    // The combined passes only time their passes when asked to, see `pass_times`
+    let timed = pass_times::enabled();
+
     {
         let format_args = format_args_storage.clone();
         let attrs = attr_storage.clone();
-        store.register_early_lint_pass(Box::new(move || {
-            Box::new(CombinedEarlyLintPass::new(conf, format_args.clone(), attrs.clone()))
+        store.register_early_lint_pass(Box::new(move || -> Box<dyn rustc_lint::EarlyLintPass> {
+            if timed {
+                Box::new(CombinedEarlyLintPass::<true>::new(
+                    conf,
+                    format_args.clone(),
+                    attrs.clone(),
+                ))
+            } else {
+                Box::new(CombinedEarlyLintPass::<false>::new(
+                    conf,
+                    format_args.clone(),
+                    attrs.clone(),
+                ))
+            }
         }));
     }
 
-    store.register_late_lint_pass(Box::new(move |tcx: TyCtxt<'_>| {
-        let skippable_lints = tcx.skippable_lints(());
-        let is_active = |lints: &rustc_lint::LintVec| is_lint_pass_required(skippable_lints, lints);
-        Box::new(CombinedLateLintPass::new(
-            tcx,
-            conf,
-            format_args_storage.clone(),
-            attr_storage.clone(),
-            &is_active,
-        ))
-    }));
+    store.register_late_lint_pass(Box::new(
+        move |tcx: TyCtxt<'_>| -> Box<dyn rustc_lint::LateLintPass<'_> + '_> {
+            let skippable_lints = tcx.skippable_lints(());
+            let is_active = |lints: &rustc_lint::LintVec| is_lint_pass_required(skippable_lints, lints);
+            let (format_args, attrs) = (format_args_storage.clone(), attr_storage.clone());
+            if timed {
+                Box::new(CombinedLateLintPass::<true>::new(
+                    tcx,
+                    conf,
+                    format_args,
+                    attrs,
+                    &is_active,
+                ))
+            } else {
+                Box::new(CombinedLateLintPass::<false>::new(
+                    tcx,
+                    conf,
+                    format_args,
+                    attrs,
+                    &is_active,
+                ))
+            }
+        },
+    ));
+}
+
+/// Returns the lints which are only checked by the early lint passes, on the syntax tree.
+///
+/// Used in `./src/driver.rs`.
+pub fn early_lints() -> Vec<&'static Lint> {
+    let late = CombinedLateLintPass::<'_, false>::lint_vec();
+    let mut early = CombinedEarlyLintPass::<false>::lint_vec();
+    early.extend(attrs::EarlyAttributes::lint_vec());
+    early.extend(nonstandard_macro_braces::MacroBraces::lint_vec());
+    early.retain(|&lint| !late.iter().any(|&late| LintId::of(lint) == LintId::of(late)));
+    early
 }
 
 // Fold every early pass into one statically-combined struct (see
@@ -544,6 +590,7 @@ rustc_lint::early_lint_methods!(
         EmptyLineAfter: empty_line_after::EmptyLineAfter = empty_line_after::EmptyLineAfter::new(),
         InlineTraitBounds: inline_trait_bounds::InlineTraitBounds = inline_trait_bounds::InlineTraitBounds::default(),
         DefinitionInModuleRoot: definition_in_module_root::DefinitionInModuleRoot = definition_in_module_root::DefinitionInModuleRoot::default(),
+        IncompatibleMsrvSyntax: incompatible_msrv::IncompatibleMsrvSyntax = incompatible_msrv::IncompatibleMsrvSyntax::new(conf),
         // add early passes here, used by `cargo dev new_lint`
     ]]
 );
@@ -868,6 +915,11 @@ rustc_lint::late_lint_methods!(
         RedundantElse: redundant_else::RedundantElse = redundant_else::RedundantElse,
         RestWhenDestructuringStruct: rest_when_destructuring_struct::RestWhenDestructuringStruct = rest_when_destructuring_struct::RestWhenDestructuringStruct,
         BlockScrutinee: block_scrutinee::BlockScrutinee = block_scrutinee::BlockScrutinee,
+        DisallowedTraitImpls: disallowed_trait_impls::DisallowedTraitImpls = disallowed_trait_impls::DisallowedTraitImpls::new(tcx, conf),
+        HeldAcrossCallLint: held_across_call::HeldAcrossCallLint = held_across_call::HeldAcrossCallLint::new(tcx, conf),
+        IncompatibleDependencyVersion: incompatible_dependency_version::IncompatibleDependencyVersion = incompatible_dependency_version::IncompatibleDependencyVersion::new(tcx, conf),
+        Locks: locks::Locks = <locks::Locks>::default(),
+        PanicInNoPanicFn: panic_in_no_panic_fn::PanicInNoPanicFn = panic_in_no_panic_fn::PanicInNoPanicFn::new(conf),
         // add late passes here, used by `cargo dev new_lint`
     ]]
 );
@@ -0,0 +1,452 @@
+//! The locks acquired by a body: the calls to `Mutex::lock`, `RwLock::read` and `RwLock::write`,
+//! and the calls to the local functions acquiring a lock passed as argument or in a static.
+
+use super::Locks;
+use clippy_utils::mir::{PossibleBorrowerMap, live_locals};
+use clippy_utils::{fn_has_unsatisfiable_preds, sym};
+use rustc_abi::FieldIdx;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::DefId;
+use rustc_index::bit_set::DenseBitSet;
+use rustc_lint::LateContext;
+use rustc_middle::mir::{
+    self, Body, Local, Location, Operand, ProjectionElem, Rvalue, StatementKind, TerminatorKind, UnwindAction,
+};
+use rustc_middle::ty::{AdtDef, Instance, TypeVisitableExt as _};
+use rustc_span::{Span, Spanned};
+use std::iter;
+use std::rc::Rc;
+
+/// Maximum number of assignments followed to find the lock a reference points to.
+const MAX_DEPTH: usize = 16;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub(super) enum LockKind {
+    /// `Mutex::lock` or `RwLock::write`
+    Exclusive,
+    /// `RwLock::read`, which also blocks on a lock locked for reading while a writer waits for it
+    Shared,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub(super) enum Root {
+    Local(Local),
+    Static(DefId),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub(super) enum Projection {
+    /// A dereference, of a reference or through `Deref`, e.g. of an `Arc`
+    Deref,
+    /// A field, with the ADT it is a field of if any
+    Field(Option<DefId>, u32),
+    Downcast(u32),
+}
+
+/// The identity of a lock across the functions of a crate: the field of the ADT storing it, or the
+/// static storing it.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub(super) enum LockId {
+    Field(DefId, u32),
+    Static(DefId),
+}
+
+impl LockId {
+    pub(super) fn name(self, cx: &LateContext<'_>) -> String {
+        match self {
+            Self::Field(adt, field) => {
+                let adt_def = cx.tcx.adt_def(adt);
+                let field = if adt_def.is_enum() {
+                    field.to_string()
+                } else {
+                    adt_def.non_enum_variant().fields[FieldIdx::from_u32(field)]
+                        .name
+                        .to_string()
+                };
+                format!("{}::{field}", cx.tcx.def_path_str(adt))
+            },
+            Self::Static(def_id) => cx.tcx.def_path_str(def_id),
+        }
+    }
+}
+
+/// A lock, identified by the place it is stored at.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub(super) struct LockPlace {
+    pub root: Root,
+    pub projection: Vec<Projection>,
+}
+
+impl LockPlace {
+    fn deref(mut self) -> Self {
+        self.projection.push(Projection::Deref);
+        self
+    }
+
+    /// Returns the identity of the lock across the functions of the crate, if it is stored in a
+    /// field of an ADT or in a static.
+    pub(super) fn id(&self) -> Option<LockId> {
+        let field = self.projection.iter().rev().find_map(|projection| match *projection {
+            Projection::Field(adt, field) => Some(adt.map(|adt| LockId::Field(adt, field))),
+            _ => None,
+        });
+        match (field, &self.root) {
+            (Some(id), _) => id,
+            (None, &Root::Static(def_id)) => Some(LockId::Static(def_id)),
+            (None, Root::Local(_)) => None,
+        }
+    }
+}
+
+/// A lock acquired by a call.
+pub(super) struct Acquisition {
+    pub place: LockPlace,
+    pub kind: LockKind,
+    /// The location of the call.
+    pub location: Location,
+    /// The span of the call.
+    pub span: Span,
+    /// The span of the argument the lock is passed as.
+    pub arg_span: Span,
+    /// The local the guard is returned to, for direct calls.
+    pub guard: Option<Local>,
+    /// The local function acquiring the lock and the span of the call acquiring it, for calls to
+    /// local functions.
+    pub via: Option<(DefId, Span)>,
+}
+
+/// A lock acquired by a function, in one of its parameters or in a static.
+pub(super) struct Summary {
+    pub place: LockPlace,
+    pub kind: LockKind,
+    /// The span of the call acquiring it.
+    pub span: Span,
+}
+
+impl Locks {
+    /// Returns the locks acquired by `body`.
+    pub(super) fn acquisitions<'tcx>(&mut self, cx: &LateContext<'tcx>, body: &Body<'tcx>) -> Vec<Acquisition> {
+        let places = Places::new(cx, body);
+        let mut acquisitions = Vec::new();
+        for (block, data) in body.basic_blocks.iter_enumerated() {
+            let terminator = data.terminator();
+            let TerminatorKind::Call {
+                func,
+                args,
+                destination,
+                fn_span,
+                ..
+            } = &terminator.kind
+            else {
+                continue;
+            };
+            let Some((callee, generic_args)) = func.const_fn_def() else {
+                continue;
+            };
+            let location = Location {
+                block,
+                statement_index: data.statements.len(),
+            };
+            if let Some(kind) = lock_kind(cx, callee) {
+                if let Some(receiver) = args.first()
+                    && let Some(place) = places.referent_of_operand(&receiver.node, 0)
+                {
+                    acquisitions.push(Acquisition {
+                        place,
+                        kind,
+                        location,
+                        span: *fn_span,
+                        arg_span: receiver.span,
+                        guard: destination.as_local(),
+                        via: None,
+                    });
+                }
+                continue;
+            }
+
+            let callee = Instance::try_resolve(cx.tcx, cx.typing_env(), callee, generic_args)
+                .ok()
+                .flatten()
+                .map_or(callee, |instance| instance.def_id());
+            for summary in self.summary(cx, callee).iter() {
+                let (place, arg_span) = match &summary.place.root {
+                    Root::Static(_) => (Some(summary.place.clone()), *fn_span),
+                    Root::Local(param) => {
+                        let Some(arg) = args.get(param.as_usize() - 1) else {
+                            continue;
+                        };
+                        (places.substitute(&arg.node, &summary.place.projection), arg.span)
+                    },
+                };
+                if let Some(place) = place {
+                    acquisitions.push(Acquisition {
+                        place,
+                        kind: summary.kind,
+                        location,
+                        span: *fn_span,
+                        arg_span,
+                        guard: None,
+                        via: Some((callee, summary.span)),
+                    });
+                }
+            }
+        }
+        acquisitions
+    }
+
+    /// Returns the locks acquired by the local function `def_id` in its parameters or in statics,
+    /// directly or through the local functions it calls.
+    fn summary(&mut self, cx: &LateContext<'_>, def_id: DefId) -> Rc<[Summary]> {
+        if let Some(summary) = self.summaries.get(&def_id) {
+            return summary.clone();
+        }
+        let Some(local_def_id) = def_id.as_local() else {
+            return Rc::new([]);
+        };
+        if !matches!(cx.tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+            || !cx.tcx.is_mir_available(def_id)
+            // Building MIR for `fn`s with unsatisfiable preds results in ICE.
+            || fn_has_unsatisfiable_preds(cx, def_id)
+        {
+            return Rc::new([]);
+        }
+
+        // Recursive calls don't acquire more locks
+        self.summaries.insert(def_id, Rc::new([]));
+        let body = cx.tcx.optimized_mir(local_def_id);
+        let summary: Rc<[Summary]> = self
+            .acquisitions(cx, body)
+            .into_iter()
+            .filter(|acquisition| match acquisition.place.root {
+                Root::Local(local) => (1..=body.arg_count).contains(&local.as_usize()),
+                Root::Static(_) => true,
+            })
+            .map(|acquisition| Summary {
+                place: acquisition.place,
+                kind: acquisition.kind,
+                span: acquisition.via.map_or(acquisition.span, |(_, span)| span),
+            })
+            .collect();
+        self.summaries.insert(def_id, summary.clone());
+        summary
+    }
+}
+
+/// Returns the pairs of acquisitions `(first, second)` of `mir` accepted by `filter` such that the
+/// guard of `first` may still be alive when `second` acquires its lock, ordered by `second`.
+pub(super) fn held_pairs<'a, 'tcx>(
+    cx: &LateContext<'tcx>,
+    mir: &Body<'tcx>,
+    acquisitions: &'a [Acquisition],
+    filter: impl Fn(&Acquisition, &Acquisition) -> bool,
+) -> Vec<(&'a Acquisition, &'a Acquisition)> {
+    let candidates: Vec<_> = acquisitions
+        .iter()
+        .flat_map(|second| {
+            acquisitions
+                .iter()
+                .filter(|first| first.guard.is_some() && first.location != second.location && filter(first, second))
+                .map(move |first| (first, second))
+        })
+        .collect();
+    if candidates.is_empty() {
+        return candidates;
+    }
+
+    // A guard is held by the values it may be borrowed by which may be used later. The unwind
+    // paths are ignored as they drop the guards moved out on the other paths, e.g. by
+    // `drop(guard)`, depending on a drop flag.
+    let possible_borrower = PossibleBorrowerMap::new(cx, mir);
+    let mut without_unwind = mir.clone();
+    for data in without_unwind.basic_blocks.as_mut() {
+        if let Some(unwind) = data.terminator_mut().unwind_mut() {
+            *unwind = UnwindAction::Unreachable;
+        }
+    }
+    let mut live = live_locals(cx.tcx, &without_unwind);
+
+    candidates
+        .into_iter()
+        .filter(|(first, second)| {
+            let TerminatorKind::Call {
+                target: Some(target), ..
+            } = mir.basic_blocks[second.location.block].terminator().kind
+            else {
+                return false;
+            };
+            let guard = first.guard.unwrap();
+            // The guards held once the call returns are held during the call.
+            live.seek_to_block_start(target);
+            iter::once(guard)
+                .chain(
+                    possible_borrower
+                        .map
+                        .get(&guard)
+                        .into_iter()
+                        .flat_map(DenseBitSet::iter)
+                        .filter(|&local| mir.local_decls[local].ty.has_erased_regions()),
+                )
+                .any(|local| live.get().contains(local))
+        })
+        .collect()
+}
+
+/// Returns the kind of lock acquired by a call to `def_id`, if it is `Mutex::lock`, `RwLock::read`
+/// or `RwLock::write`.
+fn lock_kind(cx: &LateContext<'_>, def_id: DefId) -> Option<LockKind> {
+    let impl_id = cx.tcx.inherent_impl_of_assoc(def_id)?;
+    let adt = cx
+        .tcx
+        .type_of(impl_id)
+        .instantiate_identity()
+        .skip_norm_wip()
+        .ty_adt_def()?;
+    match (cx.tcx.get_diagnostic_name(adt.did())?, cx.tcx.item_name(def_id)) {
+        (sym::Mutex, sym::lock) | (sym::RwLock, sym::write) => Some(LockKind::Exclusive),
+        (sym::RwLock, sym::read) => Some(LockKind::Shared),
+        _ => None,
+    }
+}
+
+/// Finds the places the references of a body point to, following the locals assigned once.
+struct Places<'a, 'tcx> {
+    cx: &'a LateContext<'tcx>,
+    body: &'a Body<'tcx>,
+    /// The assignment of each local assigned once, or `None` for the locals assigned several times.
+    assignments: FxHashMap<Local, Option<Assignment<'a, 'tcx>>>,
+}
+
+#[derive(Clone, Copy)]
+enum Assignment<'a, 'tcx> {
+    Rvalue(&'a Rvalue<'tcx>),
+    Call(DefId, &'a [Spanned<Operand<'tcx>>]),
+    Other,
+}
+
+impl<'a, 'tcx> Places<'a, 'tcx> {
+    fn new(cx: &'a LateContext<'tcx>, body: &'a Body<'tcx>) -> Self {
+        let mut assignments: FxHashMap<Local, Option<Assignment<'a, 'tcx>>> = FxHashMap::default();
+        let mut assign = |local: Option<Local>, assignment| {
+            if let Some(local) = local {
+                assignments
+                    .entry(local)
+                    .and_modify(|assignment| *assignment = None)
+                    .or_insert(Some(assignment));
+            }
+        };
+        for data in body.basic_blocks.iter() {
+            for statement in &data.statements {
+                if let StatementKind::Assign(box (place, rvalue)) = &statement.kind {
+                    assign(place.as_local(), Assignment::Rvalue(rvalue));
+                }
+            }
+            if let TerminatorKind::Call {
+                func,
+                args,
+                destination,
+                ..
+            } = &data.terminator().kind
+            {
+                let assignment = func
+                    .const_fn_def()
+                    .map_or(Assignment::Other, |(def_id, _)| Assignment::Call(def_id, args));
+                assign(destination.as_local(), assignment);
+            }
+        }
+        Self { cx, body, assignments }
+    }
+
+    /// Returns the place the reference or smart pointer `local` points to.
+    fn referent(&self, local: Local, depth: usize) -> Option<LockPlace> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        let Some(&assignment) = self.assignments.get(&local) else {
+            // Parameters are not assigned
+            return (1..=self.body.arg_count).contains(&local.as_usize()).then(|| {
+                LockPlace {
+                    root: Root::Local(local),
+                    projection: Vec::new(),
+                }
+                .deref()
+            });
+        };
+        match assignment? {
+            Assignment::Rvalue(Rvalue::Ref(_, _, place) | Rvalue::RawPtr(_, place)) => self.place(*place, depth + 1),
+            Assignment::Rvalue(Rvalue::Use(operand, _)) => self.referent_of_operand(operand, depth + 1),
+            Assignment::Rvalue(Rvalue::CopyForDeref(place)) => self.referent_of_place(*place, depth + 1),
+            Assignment::Call(def_id, [arg])
+                if matches!(
+                    self.cx.tcx.get_diagnostic_name(def_id),
+                    Some(sym::deref_method | sym::deref_mut_method)
+                ) =>
+            {
+                Some(self.referent_of_operand(&arg.node, depth + 1)?.deref())
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the place the reference or smart pointer `operand` points to.
+    fn referent_of_operand(&self, operand: &Operand<'tcx>, depth: usize) -> Option<LockPlace> {
+        match operand {
+            Operand::Copy(place) | Operand::Move(place) => self.referent_of_place(*place, depth),
+            Operand::Constant(constant) => constant.check_static_ptr(self.cx.tcx).map(|def_id| LockPlace {
+                root: Root::Static(def_id),
+                projection: Vec::new(),
+            }),
+            Operand::RuntimeChecks(_) => None,
+        }
+    }
+
+    /// Returns the place the reference or smart pointer stored in `place` points to.
+    fn referent_of_place(&self, place: mir::Place<'tcx>, depth: usize) -> Option<LockPlace> {
+        if place.projection.is_empty() {
+            self.referent(place.local, depth)
+        } else {
+            Some(self.place(place, depth)?.deref())
+        }
+    }
+
+    /// Returns `place`, with the references it dereferences replaced by the places they point to.
+    fn place(&self, place: mir::Place<'tcx>, depth: usize) -> Option<LockPlace> {
+        let mut projections = place.iter_projections().peekable();
+        let mut lock_place = if let Some((_, ProjectionElem::Deref)) = projections.peek() {
+            projections.next();
+            self.referent(place.local, depth)?
+        } else {
+            LockPlace {
+                root: Root::Local(place.local),
+                projection: Vec::new(),
+            }
+        };
+        for (base, elem) in projections {
+            lock_place.projection.push(match elem {
+                ProjectionElem::Deref => Projection::Deref,
+                ProjectionElem::Field(field, _) => Projection::Field(
+                    base.ty(self.body, self.cx.tcx).ty.ty_adt_def().map(AdtDef::did),
+                    field.as_u32(),
+                ),
+                ProjectionElem::Downcast(_, variant) => Projection::Downcast(variant.as_u32()),
+                // The place indexed may change
+                _ => return None,
+            });
+        }
+        Some(lock_place)
+    }
+
+    /// Returns the place of a lock acquired by a callee in the `projection` of the parameter
+    /// `arg` is passed as.
+    fn substitute(&self, arg: &Operand<'tcx>, projection: &[Projection]) -> Option<LockPlace> {
+        let (mut place, projection) = match projection {
+            [Projection::Deref, projection @ ..] => (self.referent_of_operand(arg, 0)?, projection),
+            _ => match arg {
+                Operand::Copy(place) | Operand::Move(place) => (self.place(*place, 0)?, projection),
+                Operand::Constant(_) | Operand::RuntimeChecks(_) => return None,
+            },
+        };
+        place.projection.extend_from_slice(projection);
+        Some(place)
+    }
+}
@@ -0,0 +1,164 @@
+use super::LOCK_ORDER_CYCLE;
+use super::acquisitions::{Acquisition, LockId, LockKind};
+use clippy_utils::diagnostics::span_lint_hir_and_then;
+use clippy_utils::mir::lint_root;
+use rustc_data_structures::fx::{FxHashSet, FxIndexMap};
+use rustc_errors::MultiSpan;
+use rustc_hir::HirId;
+use rustc_hir::def_id::DefId;
+use rustc_lint::LateContext;
+use rustc_middle::mir::Body;
+use rustc_span::Span;
+use std::collections::VecDeque;
+
+/// A lock acquired while another lock is held, in the order the locks are acquired.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) struct Order {
+    held: LockId,
+    held_kind: LockKind,
+    acquired: LockId,
+    acquired_kind: LockKind,
+}
+
+impl Order {
+    /// Checks if `next` may be blocked by a thread which has acquired the locks in order `self`.
+    /// Locks acquired for reading by both may block them too, when a writer waits in between.
+    fn precedes(self, next: Self) -> bool {
+        self.acquired == next.held
+    }
+
+    /// Checks if the lock acquired by `self` and held by `next` is locked for reading by both.
+    fn shares(self, next: Self) -> bool {
+        self.acquired_kind == LockKind::Shared && next.held_kind == LockKind::Shared
+    }
+}
+
+/// The first acquisitions of the locks of an [`Order`] in the crate.
+pub(super) struct Edge {
+    /// The function acquiring the locks.
+    fn_def_id: DefId,
+    /// The node to emit the lint at.
+    node: HirId,
+    held_span: Span,
+    acquired_span: Span,
+    /// The local function acquiring the second lock and the span of the call acquiring it.
+    via: Option<(DefId, Span)>,
+}
+
+/// Records the orders the locks are acquired in by the acquisitions of `held`.
+pub(super) fn record(mir: &Body<'_>, held: &[(&Acquisition, &Acquisition)], edges: &mut FxIndexMap<Order, Edge>) {
+    for &(first, second) in held {
+        if let Some(held) = first.place.id()
+            && let Some(acquired) = second.place.id()
+            && held != acquired
+        {
+            let order = Order {
+                held,
+                held_kind: first.kind,
+                acquired,
+                acquired_kind: second.kind,
+            };
+            edges.entry(order).or_insert_with(|| Edge {
+                fn_def_id: mir.source.def_id(),
+                node: lint_root(mir, second.location),
+                held_span: first.span,
+                acquired_span: second.span,
+                via: second.via,
+            });
+        }
+    }
+}
+
+/// Lints the cycles of the orders the locks of the crate are acquired in.
+pub(super) fn check(cx: &LateContext<'_>, edges: &FxIndexMap<Order, Edge>) {
+    let mut reported = FxHashSet::default();
+    for start in 0..edges.len() {
+        let Some(mut cycle) = shortest_cycle(edges, start) else {
+            continue;
+        };
+        let mut key = cycle.clone();
+        key.sort_unstable();
+        if !reported.insert(key) {
+            continue;
+        }
+        // Start with the edge of the cycle found first
+        let first = cycle.iter().enumerate().min_by_key(|&(_, &i)| i).unwrap().0;
+        cycle.rotate_left(first);
+
+        let (order, edge) = edges.get_index(cycle[0]).unwrap();
+        let chain = cycle
+            .iter()
+            .map(|&i| format!("`{}`", edges.get_index(i).unwrap().0.held.name(cx)))
+            .chain([format!("`{}`", order.held.name(cx))])
+            .collect::<Vec<_>>()
+            .join(" -> ");
+        span_lint_hir_and_then(
+            cx,
+            LOCK_ORDER_CYCLE,
+            edge.node,
+            edge.acquired_span,
+            format!("the locks of the crate are acquired in a cycle: {chain}"),
+            |diag| {
+                for &i in &cycle {
+                    let (order, edge) = edges.get_index(i).unwrap();
+                    let held = order.held.name(cx);
+                    let acquired = order.acquired.name(cx);
+                    let mut spans = MultiSpan::from_span(edge.acquired_span);
+                    spans.push_span_label(edge.held_span, format!("`{held}` is locked here"));
+                    let label = match edge.via {
+                        Some((callee, _)) => format!(
+                            "`{acquired}` is locked by `{}` here while `{held}` is held",
+                            cx.tcx.item_name(callee)
+                        ),
+                        None => format!("`{acquired}` is locked here while `{held}` is held"),
+                    };
+                    spans.push_span_label(edge.acquired_span, label);
+                    diag.span_note(
+                        spans,
+                        format!(
+                            "`{held}` then `{acquired}` are locked in `{}`",
+                            cx.tcx.def_path_str(edge.fn_def_id)
+                        ),
+                    );
+                }
+                if (0..cycle.len()).any(|i| {
+                    let order = *edges.get_index(cycle[i]).unwrap().0;
+                    order.shares(*edges.get_index(cycle[(i + 1) % cycle.len()]).unwrap().0)
+                }) {
+                    diag.note(
+                        "locking a `RwLock` for reading blocks while another thread waits to lock it for writing",
+                    );
+                }
+                diag.help("acquire the locks in the same order everywhere");
+            },
+        );
+    }
+}
+
+/// Returns the indices of the edges of the shortest cycle going through the edge at `start`,
+/// starting with it.
+fn shortest_cycle(edges: &FxIndexMap<Order, Edge>, start: usize) -> Option<Vec<usize>> {
+    let orders: Vec<Order> = edges.keys().copied().collect();
+    let mut parents = vec![None; orders.len()];
+    let mut queue = VecDeque::from([start]);
+    while let Some(current) = queue.pop_front() {
+        for next in 0..orders.len() {
+            if !orders[current].precedes(orders[next]) {
+                continue;
+            }
+            if next == start {
+                let mut cycle = vec![current];
+                while let Some(parent) = parents[*cycle.last().unwrap()] {
+                    cycle.push(parent);
+                }
+                cycle.reverse();
+                return Some(cycle);
+            }
+            if parents[next].is_none() && next != start {
+                parents[next] = Some(current);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
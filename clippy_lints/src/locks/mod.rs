@@ -0,0 +1,166 @@
+mod acquisitions;
+mod lock_order;
+mod self_deadlock;
+
+use acquisitions::{Summary, held_pairs};
+use clippy_utils::fn_has_unsatisfiable_preds;
+use lock_order::{Edge, Order};
+use rustc_data_structures::fx::{FxHashMap, FxIndexMap};
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::intravisit::FnKind;
+use rustc_hir::{Body, FnDecl};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_session::impl_lint_pass;
+use rustc_span::Span;
+use std::rc::Rc;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `Mutex`es and `RwLock`s stored in fields or statics which are acquired in
+    /// different orders by the functions of the crate, e.g. `a` is locked while `b` is held in a
+    /// function, and `b` is locked while `a` is held in another one. The locks are identified by
+    /// the field of the struct or the static they are stored in.
+    ///
+    /// ### Why is this bad?
+    /// Two threads acquiring the same locks in different orders can each wait for a lock the other
+    /// holds, deadlocking. This includes `RwLock`s locked for reading by both threads, as a thread
+    /// waiting to lock them for writing in between blocks the readers when `RwLock` prefers writers.
+    ///
+    /// ### Known problems
+    /// All the locks stored in the same field are considered to be the same lock, e.g. the locks
+    /// of two instances of a struct locked in different orders. The guards are tracked as for
+    /// [`self_deadlock`](#self_deadlock). Only the locks acquired by the functions of the crate
+    /// are considered.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use std::sync::Mutex;
+    /// struct Bank {
+    ///     accounts: Mutex<Vec<u64>>,
+    ///     log: Mutex<Vec<String>>,
+    /// }
+    ///
+    /// fn deposit(bank: &Bank, amount: u64) {
+    ///     let mut accounts = bank.accounts.lock().unwrap();
+    ///     accounts[0] += amount;
+    ///     bank.log.lock().unwrap().push(format!("deposit {amount}"));
+    /// }
+    ///
+    /// fn audit(bank: &Bank) {
+    ///     let log = bank.log.lock().unwrap();
+    ///     let accounts = bank.accounts.lock().unwrap();
+    ///     println!("{} entries, {} accounts", log.len(), accounts.len());
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # use std::sync::Mutex;
+    /// # struct Bank {
+    /// #     accounts: Mutex<Vec<u64>>,
+    /// #     log: Mutex<Vec<String>>,
+    /// # }
+    /// fn audit(bank: &Bank) {
+    ///     let accounts = bank.accounts.lock().unwrap();
+    ///     let log = bank.log.lock().unwrap();
+    ///     println!("{} entries, {} accounts", log.len(), accounts.len());
+    /// }
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub LOCK_ORDER_CYCLE,
+    restriction,
+    "locks acquired in different orders by the functions of the crate"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for locking a `Mutex` or `RwLock` while a guard of the same lock is still alive in
+    /// the function, either directly or by calling a local function locking it. This includes
+    /// locking a `RwLock` for reading while it is locked for reading.
+    ///
+    /// ### Why is this bad?
+    /// The locks of the standard library are not reentrant: locking a lock held by the current
+    /// thread deadlocks or panics. Even locking a `RwLock` for reading twice deadlocks when another
+    /// thread waits to lock it for writing in between, as `RwLock` may prefer writers.
+    ///
+    /// ### Known problems
+    /// The guards are tracked through the values they may be borrowed by, which can be
+    /// overestimated, e.g. when a reference to the guard is passed to a function along with a
+    /// mutable reference. A lock locked again by the same call in the next iteration of a loop is
+    /// not detected.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use std::sync::Mutex;
+    /// fn log(messages: &Mutex<Vec<String>>, message: &str) {
+    ///     messages.lock().unwrap().push(message.to_string());
+    /// }
+    ///
+    /// fn drain(messages: &Mutex<Vec<String>>) {
+    ///     let mut guard = messages.lock().unwrap();
+    ///     for message in guard.drain(..) {
+    ///         println!("{message}");
+    ///     }
+    ///     log(messages, "drained");
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # use std::sync::Mutex;
+    /// # fn log(messages: &Mutex<Vec<String>>, message: &str) {
+    /// #     messages.lock().unwrap().push(message.to_string());
+    /// # }
+    /// fn drain(messages: &Mutex<Vec<String>>) {
+    ///     let mut guard = messages.lock().unwrap();
+    ///     for message in guard.drain(..) {
+    ///         println!("{message}");
+    ///     }
+    ///     drop(guard);
+    ///     log(messages, "drained");
+    /// }
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub SELF_DEADLOCK,
+    nursery,
+    "locking a `Mutex` or `RwLock` which is already locked by the current function"
+}
+
+impl_lint_pass!(Locks => [LOCK_ORDER_CYCLE, SELF_DEADLOCK]);
+
+#[derive(Default)]
+pub struct Locks {
+    /// The locks acquired by the local functions, see [`Locks::summary`].
+    summaries: FxHashMap<DefId, Rc<[Summary]>>,
+    /// The orders the locks of the crate are acquired in, with their first acquisitions.
+    orders: FxIndexMap<Order, Edge>,
+}
+
+impl<'tcx> LateLintPass<'tcx> for Locks {
+    fn check_fn(
+        &mut self,
+        cx: &LateContext<'tcx>,
+        _: FnKind<'tcx>,
+        _: &'tcx FnDecl<'_>,
+        _: &'tcx Body<'_>,
+        _: Span,
+        def_id: LocalDefId,
+    ) {
+        // Building MIR for `fn`s with unsatisfiable preds results in ICE.
+        if fn_has_unsatisfiable_preds(cx, def_id.to_def_id()) {
+            return;
+        }
+
+        let mir = cx.tcx.optimized_mir(def_id.to_def_id());
+        let acquisitions = self.acquisitions(cx, mir);
+        if acquisitions.len() > 1 {
+            let held = held_pairs(cx, mir, &acquisitions, |first, second| {
+                first.place == second.place || first.place.id().zip(second.place.id()).is_some_and(|(a, b)| a != b)
+            });
+            self_deadlock::check(cx, mir, &held);
+            lock_order::record(mir, &held, &mut self.orders);
+        }
+    }
+
+    fn check_crate_post(&mut self, cx: &LateContext<'tcx>) {
+        lock_order::check(cx, &self.orders);
+    }
+}
@@ -0,0 +1,46 @@
+use super::SELF_DEADLOCK;
+use super::acquisitions::{Acquisition, LockKind};
+use clippy_utils::diagnostics::span_lint_hir_and_then;
+use clippy_utils::mir::lint_root;
+use clippy_utils::source::snippet;
+use rustc_lint::LateContext;
+use rustc_middle::mir::Body;
+
+/// Lints the acquisitions of `held` locking a lock held by the guard of the acquisition they are
+/// paired with.
+pub(super) fn check(cx: &LateContext<'_>, mir: &Body<'_>, held: &[(&Acquisition, &Acquisition)]) {
+    let mut linted = None;
+    for &(first, second) in held {
+        if linted == Some(second.location) || first.place != second.place {
+            continue;
+        }
+        linted = Some(second.location);
+
+        let lock = snippet(cx, first.arg_span, "..");
+        let msg = match second.via {
+            Some((callee, _)) => format!(
+                "calling `{}`, which locks `{lock}`, while it is already locked",
+                cx.tcx.item_name(callee)
+            ),
+            None => format!("locking `{lock}` while it is already locked"),
+        };
+        span_lint_hir_and_then(
+            cx,
+            SELF_DEADLOCK,
+            lint_root(mir, second.location),
+            second.span,
+            msg,
+            |diag| {
+                diag.span_note(first.span, "it is locked here, and the guard is still alive");
+                if let Some((callee, span)) = second.via {
+                    diag.span_note(span, format!("`{}` locks it here", cx.tcx.item_name(callee)));
+                }
+                if first.kind == LockKind::Shared && second.kind == LockKind::Shared {
+                    diag.note(
+                        "locking a `RwLock` for reading blocks while another thread waits to lock it for writing",
+                    );
+                }
+            },
+        );
+    }
+}
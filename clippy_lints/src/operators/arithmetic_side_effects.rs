@@ -105,38 +105,41 @@ impl ArithmeticSideEffects {
 
     /// Methods like `add_assign` are send to their `BinOps` references.
     fn manage_sugar_methods<'tcx>(
-        &mut self,
+        &self,
         cx: &LateContext<'tcx>,
-        expr: &'tcx hir::Expr<'_>,
-        lhs: &'tcx hir::Expr<'_>,
+        expr: &'tcx hir::Expr<'tcx>,
+        lhs: &'tcx hir::Expr<'tcx>,
         ps: &hir::PathSegment<'_>,
-        rhs: &'tcx hir::Expr<'_>,
-    ) {
-        if ps.ident.name == sym::add || ps.ident.name == sym::add_assign {
-            self.manage_bin_ops(cx, expr, hir::BinOpKind::Add, lhs, rhs);
+        rhs: &'tcx hir::Expr<'tcx>,
+    ) -> Option<&'tcx hir::Expr<'tcx>> {
+        let op = if ps.ident.name == sym::add || ps.ident.name == sym::add_assign {
+            hir::BinOpKind::Add
         } else if ps.ident.name == sym::div || ps.ident.name == sym::div_assign {
-            self.manage_bin_ops(cx, expr, hir::BinOpKind::Div, lhs, rhs);
+            hir::BinOpKind::Div
         } else if ps.ident.name == sym::mul || ps.ident.name == sym::mul_assign {
-            self.manage_bin_ops(cx, expr, hir::BinOpKind::Mul, lhs, rhs);
+            hir::BinOpKind::Mul
         } else if ps.ident.name == sym::rem || ps.ident.name == sym::rem_assign {
-            self.manage_bin_ops(cx, expr, hir::BinOpKind::Rem, lhs, rhs);
+            hir::BinOpKind::Rem
         } else if ps.ident.name == sym::sub || ps.ident.name == sym::sub_assign {
-            self.manage_bin_ops(cx, expr, hir::BinOpKind::Sub, lhs, rhs);
-        }
+            hir::BinOpKind::Sub
+        } else {
+            return None;
+        };
+        self.manage_bin_ops(cx, expr, op, lhs, rhs)
     }
 
     /// Manages when the lint should be triggered. Operations in constant environments, hard coded
     /// types, custom allowed types and non-constant operations that don't overflow are ignored.
     fn manage_bin_ops<'tcx>(
-        &mut self,
+        &self,
         cx: &LateContext<'tcx>,
-        expr: &'tcx hir::Expr<'_>,
+        expr: &'tcx hir::Expr<'tcx>,
         op: hir::BinOpKind,
-        lhs: &'tcx hir::Expr<'_>,
-        rhs: &'tcx hir::Expr<'_>,
-    ) {
+        lhs: &'tcx hir::Expr<'tcx>,
+        rhs: &'tcx hir::Expr<'tcx>,
+    ) -> Option<&'tcx hir::Expr<'tcx>> {
         if ConstEvalCtxt::new(cx).eval_local(expr, expr.span.ctxt()).is_some() {
-            return;
+            return None;
         }
         if !matches!(
             op,
@@ -148,7 +151,7 @@ impl ArithmeticSideEffects {
                 | hir::BinOpKind::Shr
                 | hir::BinOpKind::Sub
         ) {
-            return;
+            return None;
         }
         let (mut actual_lhs, lhs_ref_counter) = peel_hir_expr_refs(lhs);
         let (mut actual_rhs, rhs_ref_counter) = peel_hir_expr_refs(rhs);
@@ -163,7 +166,7 @@ impl ArithmeticSideEffects {
             && is_non_zero_u(cx, receiver_ty)
             && literal_integer(cx, actual_rhs) == Some(1)
         {
-            return;
+            return None;
         }
 
         let lhs_ty = cx.typeck_results().expr_ty(actual_lhs).peel_refs();
@@ -173,22 +176,22 @@ impl ArithmeticSideEffects {
             | is_safe_due_to_smaller_source_type(cx, op, (actual_lhs, lhs_ty), actual_rhs)
             | is_safe_due_to_smaller_source_type(cx, op, (actual_rhs, rhs_ty), actual_lhs)
         {
-            return;
+            return None;
         }
         if is_integer(lhs_ty) && is_integer(rhs_ty) {
             if let hir::BinOpKind::Shl | hir::BinOpKind::Shr = op {
                 // At least for integers, shifts are already handled by the CTFE
-                return;
+                return None;
             }
             match (literal_integer(cx, actual_lhs), literal_integer(cx, actual_rhs)) {
                 (None, Some(n)) => match (&op, n) {
                     // Division and module are always valid if applied to non-zero integers
-                    (hir::BinOpKind::Div | hir::BinOpKind::Rem, local_n) if local_n != 0 => return,
+                    (hir::BinOpKind::Div | hir::BinOpKind::Rem, local_n) if local_n != 0 => return None,
                     // Adding or subtracting zeros is always a no-op
                     (hir::BinOpKind::Add | hir::BinOpKind::Sub, 0)
                     // Multiplication by 1 or 0 will never overflow
                     | (hir::BinOpKind::Mul, 0 | 1)
-                    => return,
+                    => return None,
                     _ => {},
                 },
                 (Some(n), None)
@@ -200,68 +203,84 @@ impl ArithmeticSideEffects {
                         | (hir::BinOpKind::Mul, 0 | 1)
                     ) =>
                 {
-                    return;
+                    return None;
                 },
-                (Some(_), Some(_)) if matches!((lhs_ref_counter, rhs_ref_counter), (0, 0)) => return,
+                (Some(_), Some(_)) if matches!((lhs_ref_counter, rhs_ref_counter), (0, 0)) => return None,
                 _ => {},
             }
         }
-        self.issue_lint(cx, expr);
+        Some(expr)
     }
 
     /// There are some integer methods like `wrapping_div` that will panic depending on the
     /// provided input.
     fn manage_method_call<'tcx>(
-        &mut self,
-        args: &'tcx [hir::Expr<'_>],
+        &self,
+        args: &'tcx [hir::Expr<'tcx>],
         cx: &LateContext<'tcx>,
-        expr: &'tcx hir::Expr<'_>,
+        expr: &'tcx hir::Expr<'tcx>,
         ps: &'tcx hir::PathSegment<'_>,
-        receiver: &'tcx hir::Expr<'_>,
-    ) {
-        let Some(arg) = args.first() else {
-            return;
-        };
+        receiver: &'tcx hir::Expr<'tcx>,
+    ) -> Option<&'tcx hir::Expr<'tcx>> {
+        let arg = args.first()?;
         if ConstEvalCtxt::new(cx).eval_local(receiver, expr.span.ctxt()).is_some() {
-            return;
+            return None;
         }
         let instance_ty = cx.typeck_results().expr_ty_adjusted(receiver);
         if !is_integer(instance_ty) {
-            return;
+            return None;
+        }
+        if let Some(expr) = self.manage_sugar_methods(cx, expr, receiver, ps, arg) {
+            return Some(expr);
         }
-        self.manage_sugar_methods(cx, expr, receiver, ps, arg);
         if !self.disallowed_int_methods.contains(&ps.ident.name) {
-            return;
+            return None;
         }
         let (actual_arg, _) = peel_hir_expr_refs(arg);
         match literal_integer(cx, actual_arg) {
-            None | Some(0) => self.issue_lint(cx, arg),
-            Some(_) => {},
+            None | Some(0) => Some(arg),
+            Some(_) => None,
         }
     }
 
     fn manage_unary_ops<'tcx>(
-        &mut self,
+        &self,
         cx: &LateContext<'tcx>,
-        expr: &'tcx hir::Expr<'_>,
-        un_expr: &'tcx hir::Expr<'_>,
+        expr: &'tcx hir::Expr<'tcx>,
+        un_expr: &'tcx hir::Expr<'tcx>,
         un_op: hir::UnOp,
-    ) {
+    ) -> Option<&'tcx hir::Expr<'tcx>> {
         let hir::UnOp::Neg = un_op else {
-            return;
+            return None;
         };
         if ConstEvalCtxt::new(cx).eval(un_expr).is_some() {
-            return;
+            return None;
         }
         let ty = cx.typeck_results().expr_ty_adjusted(expr).peel_refs();
         if self.has_allowed_unary(ty) {
-            return;
+            return None;
         }
         let actual_un_expr = peel_hir_expr_refs(un_expr).0;
         if literal_integer(cx, actual_un_expr).is_some() {
-            return;
+            return None;
+        }
+        Some(expr)
+    }
+
+    /// Returns the expression of `expr` with arithmetic side effects, i.e. which may overflow or
+    /// panic, if any.
+    pub(crate) fn side_effect_expr<'tcx>(
+        &self,
+        cx: &LateContext<'tcx>,
+        expr: &'tcx hir::Expr<'tcx>,
+    ) -> Option<&'tcx hir::Expr<'tcx>> {
+        match &expr.kind {
+            hir::ExprKind::Binary(op, lhs, rhs) => self.manage_bin_ops(cx, expr, op.node, lhs, rhs),
+            hir::ExprKind::AssignOp(op, lhs, rhs) => self.manage_bin_ops(cx, expr, op.node.into(), lhs, rhs),
+            hir::ExprKind::MethodCall(ps, receiver, args, _) => self.manage_method_call(args, cx, expr, ps, receiver),
+            hir::ExprKind::Unary(un_op, un_expr) => self.manage_unary_ops(cx, expr, un_expr, *un_op),
+            _ => None,
         }
-        self.issue_lint(cx, expr);
     }
 
     fn should_skip_expr<'tcx>(&self, cx: &LateContext<'tcx>, expr: &hir::Expr<'tcx>) -> bool {
@@ -276,20 +295,8 @@ impl<'tcx> LateLintPass<'tcx> for ArithmeticSideEffects {
         if self.should_skip_expr(cx, expr) {
             return;
         }
-        match &expr.kind {
-            hir::ExprKind::Binary(op, lhs, rhs) => {
-                self.manage_bin_ops(cx, expr, op.node, lhs, rhs);
-            },
-            hir::ExprKind::AssignOp(op, lhs, rhs) => {
-                self.manage_bin_ops(cx, expr, op.node.into(), lhs, rhs);
-            },
-            hir::ExprKind::MethodCall(ps, receiver, args, _) => {
-                self.manage_method_call(args, cx, expr, ps, receiver);
-            },
-            hir::ExprKind::Unary(un_op, un_expr) => {
-                self.manage_unary_ops(cx, expr, un_expr, *un_op);
-            },
-            _ => {},
+        if let Some(expr) = self.side_effect_expr(cx, expr) {
+            self.issue_lint(cx, expr);
         }
     }
 
@@ -0,0 +1,377 @@
+use crate::indexing_slicing::{IndexBounds, index_bounds};
+use crate::operators::arithmetic_side_effects::ArithmeticSideEffects;
+use clippy_config::Conf;
+use clippy_utils::attrs::{get_builtin_attr, get_unique_builtin_attr};
+use clippy_utils::consts::{ConstEvalCtxt, Constant};
+use clippy_utils::diagnostics::span_lint_hir_and_then;
+use clippy_utils::macros::root_macro_call;
+use clippy_utils::mir::lint_root;
+use clippy_utils::res::MaybeDef as _;
+use clippy_utils::{fn_def_id, fn_has_unsatisfiable_preds, fulfill_or_allowed, higher, sym};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::intravisit::FnKind;
+use rustc_hir::{BinOpKind, Body, Expr, ExprKind, FnDecl, HirId, LangItem, UnOp};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::mir::TerminatorKind;
+use rustc_middle::ty::{self, Instance};
+use rustc_session::impl_lint_pass;
+use rustc_span::Span;
+use std::rc::Rc;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for the functions marked `#[clippy::no_panic]` which may panic: by indexing, with
+    /// arithmetic which may overflow or divide by zero, with `panic!` and assertions, by calling
+    /// `unwrap` or `expect`, or by calling a function of the crate which may panic and is not
+    /// marked `#[clippy::no_panic]`. The closures defined in the function are checked as part of
+    /// it.
+    ///
+    /// ### Why is this bad?
+    /// The function breaks its contract: callers rely on it not to panic, e.g. in firmware where
+    /// a panic resets the device.
+    ///
+    /// ### Known problems
+    /// The functions of other crates are assumed not to panic, except the panicking functions,
+    /// `unwrap`, `expect` and the implementations of `Index` and `IndexMut`: calls such as
+    /// `Vec::remove`, `slice::split_at` or `RefCell::borrow_mut` are not reported. The integer arithmetic
+    /// is checked as for [`arithmetic_side_effects`](#arithmetic_side_effects), whether the overflow
+    /// checks are enabled or not. Panics in `Drop` implementations are ignored.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// #[clippy::no_panic]
+    /// fn checksum(bytes: &[u8]) -> u8 {
+    ///     bytes[0] ^ bytes[1]
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// #[clippy::no_panic]
+    /// fn checksum(bytes: &[u8]) -> u8 {
+    ///     bytes.iter().fold(0, |acc, byte| acc ^ byte)
+    /// }
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub PANIC_IN_NO_PANIC_FN,
+    nursery,
+    "function marked `#[clippy::no_panic]` which may panic"
+}
+
+impl_lint_pass!(PanicInNoPanicFn => [PANIC_IN_NO_PANIC_FN]);
+
+pub struct PanicInNoPanicFn {
+    arithmetic: ArithmeticSideEffects,
+    /// Whether the crate has functions marked `#[clippy::no_panic]`.
+    enabled: bool,
+    /// The functions marked `#[clippy::no_panic]`, checked at the end of the crate.
+    no_panic_fns: Vec<LocalDefId>,
+    /// The possible panics of the indexing and arithmetic expressions of the local functions.
+    expr_panics: FxHashMap<LocalDefId, Vec<Panic>>,
+    /// The first possible panic of the local functions, see [`PanicInNoPanicFn::first_panic`].
+    first_panics: FxHashMap<DefId, Option<Rc<Panic>>>,
+}
+
+impl PanicInNoPanicFn {
+    pub fn new(conf: &'static Conf) -> Self {
+        Self {
+            arithmetic: ArithmeticSideEffects::new(conf),
+            enabled: false,
+            no_panic_fns: Vec::new(),
+            expr_panics: FxHashMap::default(),
+            first_panics: FxHashMap::default(),
+        }
+    }
+}
+
+/// A possible panic of a function.
+#[derive(Clone)]
+struct Panic {
+    span: Span,
+    /// The node the lint is emitted at.
+    node: HirId,
+    /// Why it may panic, e.g. "indexing may panic".
+    reason: String,
+    /// The possible panic of the function called, for calls to local functions.
+    callee: Option<Rc<Panic>>,
+}
+
+impl<'tcx> LateLintPass<'tcx> for PanicInNoPanicFn {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        self.enabled = cx.tcx.hir_body_owners().any(|def_id| has_no_panic_attr(cx, def_id));
+    }
+
+    fn check_fn(
+        &mut self,
+        cx: &LateContext<'tcx>,
+        _: FnKind<'tcx>,
+        _: &'tcx FnDecl<'_>,
+        _: &'tcx Body<'_>,
+        _: Span,
+        def_id: LocalDefId,
+    ) {
+        let attrs = cx.tcx.hir_attrs(cx.tcx.local_def_id_to_hir_id(def_id));
+        if get_unique_builtin_attr(cx.tcx.sess, attrs, sym::no_panic).is_some()
+            // Building MIR for `fn`s with unsatisfiable preds results in ICE.
+            && !fn_has_unsatisfiable_preds(cx, def_id.to_def_id())
+        {
+            self.no_panic_fns.push(def_id);
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if self.enabled
+            && let Some(body_id) = cx.enclosing_body
+            && let owner = cx.tcx.hir_body_owner_def_id(body_id)
+            && cx.tcx.hir_body_owner_kind(owner).is_fn_or_closure()
+            && let Some(reason) = self.expr_panic(cx, expr)
+        {
+            self.expr_panics.entry(owner).or_default().push(Panic {
+                span: expr.span.source_callsite(),
+                node: expr.hir_id,
+                reason: reason.to_string(),
+                callee: None,
+            });
+        }
+    }
+
+    fn check_crate_post(&mut self, cx: &LateContext<'tcx>) {
+        for def_id in std::mem::take(&mut self.no_panic_fns) {
+            for panic in self.panics(cx, def_id.to_def_id()) {
+                span_lint_hir_and_then(
+                    cx,
+                    PANIC_IN_NO_PANIC_FN,
+                    panic.node,
+                    panic.span,
+                    format!("{} in a function marked `#[clippy::no_panic]`", panic.reason),
+                    |diag| {
+                        let mut callee = panic.callee.as_deref();
+                        while let Some(panic) = callee {
+                            diag.span_note(panic.span, panic.reason.clone());
+                            callee = panic.callee.as_deref();
+                        }
+                    },
+                );
+            }
+        }
+    }
+}
+
+impl PanicInNoPanicFn {
+    /// Returns the possible panics of the function `def_id`, in source order. The closures defined
+    /// in the function are part of it, as they are usually called by it, e.g. with iterator
+    /// adapters.
+    fn panics(&mut self, cx: &LateContext<'_>, def_id: DefId) -> Vec<Panic> {
+        let mut panics = Vec::new();
+        let nested_bodies = def_id
+            .as_local()
+            .map_or(&[][..], |def_id| cx.tcx.nested_bodies_within(def_id).as_slice());
+        for &body_id in nested_bodies
+            .iter()
+            .filter(|&&body_id| cx.tcx.is_closure_like(body_id.to_def_id()))
+        {
+            if let Some(expr_panics) = self.expr_panics.get(&body_id) {
+                panics.extend_from_slice(expr_panics);
+            }
+            self.call_panics(cx, body_id.to_def_id(), &mut panics);
+        }
+        if let Some(expr_panics) = def_id.as_local().and_then(|def_id| self.expr_panics.get(&def_id)) {
+            panics.extend_from_slice(expr_panics);
+        }
+        self.call_panics(cx, def_id, &mut panics);
+        panics.sort_by_key(|panic| panic.span.lo());
+        panics
+    }
+
+    /// Adds the possible panics of the calls of the MIR of `def_id` to `panics`.
+    fn call_panics(&mut self, cx: &LateContext<'_>, def_id: DefId, panics: &mut Vec<Panic>) {
+        let tcx = cx.tcx;
+        let mir = tcx.optimized_mir(def_id);
+        let typing_env = ty::TypingEnv::post_analysis(tcx, def_id);
+        for (block, data) in mir.basic_blocks.iter_enumerated().filter(|(_, data)| !data.is_cleanup) {
+            let terminator = data.terminator();
+            let TerminatorKind::Call { func, .. } = &terminator.kind else {
+                continue;
+            };
+            let Some((callee, args)) = func.const_fn_def() else {
+                continue;
+            };
+            // The indexing is checked on the expressions, see `PanicInNoPanicFn::expr_panic`.
+            if is_index_fn(cx, callee) {
+                continue;
+            }
+            let span = terminator.source_info.span.source_callsite();
+            let callee = Instance::try_resolve(tcx, typing_env, callee, args)
+                .ok()
+                .flatten()
+                .map_or(callee, |instance| instance.def_id());
+            let (reason, callee) = if is_panicking_fn(cx, callee) {
+                let reason = match root_macro_call(terminator.source_info.span) {
+                    Some(macro_call) => format!("`{}!` may panic", tcx.item_name(macro_call.def_id)),
+                    None => format!("`{}` may panic", tcx.item_name(callee)),
+                };
+                (reason, None)
+            } else if let Some(callee_id) = callee.as_local()
+                && !has_no_panic_attr(cx, callee_id)
+                // The panics of the closures of the function are its own, see `PanicInNoPanicFn::panics`.
+                && !(tcx.is_closure_like(callee) && tcx.typeck_root_def_id(callee) == tcx.typeck_root_def_id(def_id))
+                && let Some(panic) = self.first_panic(cx, callee)
+            {
+                (format!("calling `{}` may panic", tcx.def_path_str(callee)), Some(panic))
+            } else {
+                continue;
+            };
+            if !panics.iter().any(|panic| panic.span == span) {
+                panics.push(Panic {
+                    span,
+                    node: lint_root(mir, mir.terminator_loc(block)),
+                    reason,
+                    callee,
+                });
+            }
+        }
+    }
+
+    /// Returns the first possible panic of the local function `def_id` not allowed by the lint.
+    fn first_panic(&mut self, cx: &LateContext<'_>, def_id: DefId) -> Option<Rc<Panic>> {
+        if let Some(panic) = self.first_panics.get(&def_id) {
+            return panic.clone();
+        }
+        if !cx.tcx.is_mir_available(def_id) || fn_has_unsatisfiable_preds(cx, def_id) {
+            return None;
+        }
+
+        // Recursive calls are found not to panic, as the function panics elsewhere if it does.
+        self.first_panics.insert(def_id, None);
+        let panic = self
+            .panics(cx, def_id)
+            .into_iter()
+            .find(|panic| !fulfill_or_allowed(cx, PANIC_IN_NO_PANIC_FN, [panic.node]))
+            .map(Rc::new);
+        self.first_panics.insert(def_id, panic.clone());
+        panic
+    }
+
+    /// Returns why the indexing or arithmetic expression `expr` may panic, if it may. The indexing
+    /// is checked as for `indexing_slicing`, and the integer arithmetic as for
+    /// `arithmetic_side_effects`. The calls to `Index::index` and `IndexMut::index_mut` may panic.
+    fn expr_panic<'tcx>(&self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) -> Option<&'static str> {
+        let typeck = cx.typeck_results();
+        if let ExprKind::Call(..) | ExprKind::MethodCall(..) = expr.kind
+            && fn_def_id(cx, expr).is_some_and(|def_id| is_index_fn(cx, def_id))
+        {
+            return Some("indexing may panic");
+        }
+        match expr.kind {
+            ExprKind::Index(base, index, _) => match index_bounds(cx, typeck.expr_ty(base).peel_refs(), expr, index) {
+                IndexBounds::InBounds => None,
+                IndexBounds::OutOfBounds(..) | IndexBounds::Unknown if higher::Range::hir(cx, index).is_some() => {
+                    Some("slicing may panic")
+                },
+                IndexBounds::OutOfBounds(..) | IndexBounds::Unknown => Some("indexing may panic"),
+            },
+            ExprKind::Binary(op, lhs, rhs) => self.binary_panic(cx, expr, op.node, lhs, rhs),
+            ExprKind::AssignOp(op, lhs, rhs) => self.binary_panic(cx, expr, op.node.into(), lhs, rhs),
+            ExprKind::Unary(UnOp::Neg, operand) if typeck.expr_ty(operand).peel_refs().is_integral() => self
+                .arithmetic
+                .side_effect_expr(cx, expr)
+                .map(|_| "this arithmetic operation may overflow"),
+            ExprKind::MethodCall(method, receiver, ..) if typeck.expr_ty(receiver).peel_refs().is_integral() => {
+                self.arithmetic.side_effect_expr(cx, expr).map(|_| {
+                    if matches!(
+                        method.ident.name,
+                        sym::add | sym::add_assign | sym::sub | sym::sub_assign | sym::mul | sym::mul_assign
+                    ) {
+                        "this arithmetic operation may overflow"
+                    } else {
+                        "this division may divide by zero"
+                    }
+                })
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns why the binary operation `expr` on integers may panic, if it may.
+    fn binary_panic<'tcx>(
+        &self,
+        cx: &LateContext<'tcx>,
+        expr: &'tcx Expr<'tcx>,
+        op: BinOpKind,
+        lhs: &'tcx Expr<'tcx>,
+        rhs: &Expr<'_>,
+    ) -> Option<&'static str> {
+        let ty = cx.typeck_results().expr_ty(lhs).peel_refs();
+        if !ty.is_integral() {
+            return None;
+        }
+        match op {
+            // `arithmetic_side_effects` leaves the integer shifts to rustc, which only checks
+            // constant operands.
+            BinOpKind::Shl | BinOpKind::Shr => {
+                if let Some(Constant::Int(shift)) = ConstEvalCtxt::new(cx).eval(rhs)
+                    && shift < u128::from(ty.primitive_size(cx.tcx).bits())
+                {
+                    None
+                } else {
+                    Some("this shift may overflow")
+                }
+            },
+            BinOpKind::Div | BinOpKind::Rem => self.arithmetic.side_effect_expr(cx, expr).map(|_| {
+                if ty.is_signed() {
+                    "this division may overflow or divide by zero"
+                } else {
+                    "this division may divide by zero"
+                }
+            }),
+            _ => self
+                .arithmetic
+                .side_effect_expr(cx, expr)
+                .map(|_| "this arithmetic operation may overflow"),
+        }
+    }
+}
+
+/// Checks if `def_id` is a function of the standard library which panics, or `unwrap` or `expect`
+/// on `Option` or `Result`.
+fn is_panicking_fn(cx: &LateContext<'_>, def_id: DefId) -> bool {
+    let tcx = cx.tcx;
+    if !matches!(tcx.crate_name(def_id.krate), sym::core | sym::std) {
+        return false;
+    }
+    if let Some(impl_id) = tcx.inherent_impl_of_assoc(def_id) {
+        return matches!(
+            tcx.item_name(def_id),
+            sym::unwrap | sym::expect | sym::unwrap_err | sym::expect_err
+        ) && matches!(
+            tcx.type_of(impl_id)
+                .instantiate_identity()
+                .skip_norm_wip()
+                .opt_diag_name(cx),
+            Some(sym::Option | sym::Result)
+        );
+    }
+    matches!(tcx.item_name(def_id), sym::panic_any | sym::begin_panic)
+        || tcx
+            .def_path(def_id)
+            .data
+            .iter()
+            .any(|data| data.data.get_opt_name() == Some(sym::panicking))
+}
+
+/// Checks if `def_id` is `Index::index` or `IndexMut::index_mut`, whose implementations panic
+/// when the index is out of bounds.
+fn is_index_fn(cx: &LateContext<'_>, def_id: DefId) -> bool {
+    cx.tcx.trait_of_assoc(def_id).is_some_and(|trait_id| {
+        matches!(
+            cx.tcx.lang_items().from_def_id(trait_id),
+            Some(LangItem::Index | LangItem::IndexMut)
+        )
+    })
+}
+
+fn has_no_panic_attr(cx: &LateContext<'_>, def_id: LocalDefId) -> bool {
+    get_builtin_attr(cx.tcx.hir_attrs(cx.tcx.local_def_id_to_hir_id(def_id)), sym::no_panic)
+        .next()
+        .is_some()
+}
@@ -0,0 +1,41 @@
+//! Self-time of each lint pass, recorded by the combined passes when the `CLIPPY_PASS_TIMES`
+//! environment variable names a directory.
+//!
+//! Only the time spent in the `check_*` methods of a pass is counted, not the walk of the tree
+//! between them. When a combined pass is dropped, its times are written to a new
+//! `<pid>-<early|late>-<n>.json` file of that directory, as an object mapping the name of each pass
+//! to a number of nanoseconds. `lintcheck --pass-times` aggregates them.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use std::{env, fs, process};
+
+const VAR: &str = "CLIPPY_PASS_TIMES";
+
+/// Checks if the times of the passes are recorded.
+pub fn enabled() -> bool {
+    env::var_os(VAR).is_some()
+}
+
+/// Writes the times of the passes of a combined pass of the given `kind`.
+pub fn write(kind: &str, times: &[(&str, Duration)]) {
+    static FILES: AtomicUsize = AtomicUsize::new(0);
+
+    let Some(dir) = env::var_os(VAR) else {
+        return;
+    };
+    let times: BTreeMap<&str, u128> = times
+        .iter()
+        .filter(|(_, time)| !time.is_zero())
+        .map(|&(name, time)| (name, time.as_nanos()))
+        .collect();
+    let json = serde_json::to_string(&times).unwrap();
+
+    let n = FILES.fetch_add(1, Ordering::Relaxed);
+    let path = Path::new(&dir).join(format!("{}-{kind}-{n}.json", process::id()));
+    if let Err(e) = fs::create_dir_all(&dir).and_then(|()| fs::write(&path, json)) {
+        eprintln!("failed to write `{}`: {e}", path.display());
+    }
+}
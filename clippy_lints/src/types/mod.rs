@@ -405,8 +405,8 @@ impl_lint_pass!(Types => [
 ]);
 
 pub struct Types {
+    conf: &'static Conf,
     vec_box_size_threshold: u64,
-    type_complexity_threshold: u64,
     avoid_breaking_exported_api: bool,
 }
 
@@ -545,8 +545,8 @@ impl<'tcx> LateLintPass<'tcx> for Types {
 impl Types {
     pub fn new(conf: &'static Conf) -> Self {
         Self {
+            conf,
             vec_box_size_threshold: conf.vec_box_size_threshold,
-            type_complexity_threshold: conf.type_complexity_threshold,
             avoid_breaking_exported_api: conf.avoid_breaking_exported_api,
         }
     }
@@ -583,7 +583,7 @@ impl Types {
             return;
         }
 
-        if !context.is_nested_call && type_complexity::check(cx, hir_ty, self.type_complexity_threshold) {
+        if !context.is_nested_call && type_complexity::check(cx, hir_ty, self.conf) {
             return;
         }
 
@@ -1,3 +1,4 @@
+use clippy_config::Conf;
 use clippy_utils::diagnostics::span_lint;
 use rustc_abi::ExternAbi;
 use rustc_hir::intravisit::{InferKind, Visitor, VisitorExt as _, walk_ty};
@@ -7,14 +8,18 @@ use rustc_span::Span;
 
 use super::TYPE_COMPLEXITY;
 
-pub(super) fn check(cx: &LateContext<'_>, ty: &hir::Ty<'_>, type_complexity_threshold: u64) -> bool {
+pub(super) fn check(cx: &LateContext<'_>, ty: &hir::Ty<'_>, conf: &'static Conf) -> bool {
     let score = {
         let mut visitor = TypeComplexityVisitor { score: 0, nest: 1 };
         visitor.visit_ty_unambig(ty);
         visitor.score
     };
 
-    if score > type_complexity_threshold {
+    if score
+        > *conf
+            .in_file(cx.tcx.sess.source_map(), ty.span)
+            .type_complexity_threshold()
+    {
         span_lint(
             cx,
             TYPE_COMPLEXITY,
@@ -4,17 +4,68 @@ use std::sync::Arc;
 use clippy_config::Conf;
 use clippy_utils::consts::const_item_rhs_to_expr;
 use clippy_utils::diagnostics::span_lint_and_then;
-use clippy_utils::is_lint_allowed;
 use clippy_utils::source::walk_span_to_context;
 use clippy_utils::visitors::{Descend, for_each_expr};
+use clippy_utils::{fn_def_id, is_lint_allowed};
 use hir::HirId;
 use rustc_errors::Applicability;
+use rustc_hir::def_id::DefId;
 use rustc_hir::{self as hir, Block, BlockCheckMode, FnSig, Impl, ItemKind, Node, UnsafeSource};
 use rustc_lexer::{FrontmatterAllowed, TokenKind, tokenize};
 use rustc_lint::{LateContext, LateLintPass, LintContext as _};
+use rustc_resolve::rustdoc::pulldown_cmark::{self, Event, Tag, TagEnd};
+use rustc_resolve::rustdoc::{add_doc_fragment, attrs_to_doc_fragments, main_body_opts};
 use rustc_session::impl_lint_pass;
 use rustc_span::{BytePos, Pos as _, RelativeBytePos, Span, SyntaxContext};
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `// SAFETY: ` comments on `unsafe` blocks which do not address each
+    /// precondition of the unsafe functions called in the block. The preconditions are the items
+    /// of the list of the `# Safety` section of the docs of the functions. A precondition is
+    /// addressed if the comment mentions one of its code spans, or one of its words if it has
+    /// none, or if the comment has a list of as many items as the preconditions.
+    ///
+    /// ### Why is this bad?
+    /// A safety comment which does not justify each precondition, e.g. `// SAFETY: fine`, does
+    /// not show that the call is sound.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// /// # Safety
+    /// ///
+    /// /// - `ptr` must be valid for reads.
+    /// /// - `ptr` must be aligned.
+    /// /// - `len` must not exceed the length of the buffer.
+    /// unsafe fn read_buffer(ptr: *const u8, len: usize) -> Vec<u8> {
+    ///     // ...
+    ///     # unimplemented!()
+    /// }
+    ///
+    /// let buffer = [0; 4];
+    /// // SAFETY: `buffer` is a valid buffer
+    /// let copy = unsafe { read_buffer(buffer.as_ptr(), 4) };
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # /// # Safety
+    /// # ///
+    /// # /// - `ptr` must be valid for reads.
+    /// # /// - `ptr` must be aligned.
+    /// # /// - `len` must not exceed the length of the buffer.
+    /// # unsafe fn read_buffer(ptr: *const u8, len: usize) -> Vec<u8> {
+    /// #     unimplemented!()
+    /// # }
+    /// let buffer = [0; 4];
+    /// // SAFETY: the `ptr` of `buffer` is valid for reads and aligned, and `len` is its length
+    /// let copy = unsafe { read_buffer(buffer.as_ptr(), 4) };
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub INCOMPLETE_SAFETY_COMMENT,
+    pedantic,
+    "safety comment not addressing each precondition of the unsafe functions called"
+}
+
 declare_clippy_lint! {
     /// ### What it does
     /// Checks for `unsafe` blocks and impls without a `// SAFETY: ` comment
@@ -94,6 +145,7 @@ declare_clippy_lint! {
 }
 
 impl_lint_pass!(UndocumentedUnsafeBlocks => [
+    INCOMPLETE_SAFETY_COMMENT,
     UNDOCUMENTED_UNSAFE_BLOCKS,
     UNNECESSARY_SAFETY_COMMENT,
 ]);
@@ -110,6 +162,31 @@ impl UndocumentedUnsafeBlocks {
             accept_comment_above_attributes: conf.accept_comment_above_attributes,
         }
     }
+
+    /// Returns the position of the `SAFETY:` of the safety comment of the unsafe block, if any.
+    fn block_safety_comment(&self, cx: &LateContext<'_>, block: &Block<'_>) -> Option<BytePos> {
+        let comment_before =
+            |span| match span_from_macro_expansion_has_safety_comment(cx, span, self.accept_comment_above_attributes) {
+                HasSafetyComment::Yes(pos, _) => Some(pos),
+                HasSafetyComment::No => None,
+                HasSafetyComment::Maybe => {
+                    if let HasSafetyComment::Yes(pos, _) =
+                        span_safety_comment(cx, span, self.accept_comment_above_attributes)
+                    {
+                        Some(pos)
+                    } else {
+                        None
+                    }
+                },
+            };
+        comment_before(block.span)
+            .or_else(|| block_inner_safety_comment(cx, block.span))
+            .or_else(|| {
+                block_parent_span(cx, block.hir_id)
+                    .filter(|_| self.accept_comment_above_statement)
+                    .and_then(comment_before)
+            })
+    }
 }
 
 impl<'tcx> LateLintPass<'tcx> for UndocumentedUnsafeBlocks {
@@ -146,6 +223,15 @@ impl<'tcx> LateLintPass<'tcx> for UndocumentedUnsafeBlocks {
             );
         }
 
+        if block.rules == BlockCheckMode::UnsafeBlock(UnsafeSource::UserProvided)
+            && !block.span.in_external_macro(cx.tcx.sess.source_map())
+            && !is_lint_allowed(cx, INCOMPLETE_SAFETY_COMMENT, block.hir_id)
+            && let Some(pos) = self.block_safety_comment(cx, block)
+            && let Some(comment) = comment_text(cx, pos)
+        {
+            check_preconditions_addressed(cx, block, &comment);
+        }
+
         if let Some(tail) = block.expr
             && !is_lint_allowed(cx, UNNECESSARY_SAFETY_COMMENT, tail.hir_id)
             && !tail.span.in_external_macro(cx.tcx.sess.source_map())
@@ -482,8 +568,18 @@ fn block_parents_have_safety_comment(
     cx: &LateContext<'_>,
     id: HirId,
 ) -> bool {
-    let span = match cx.tcx.parent_hir_node(id) {
-        Node::Expr(expr) if let Some((span, _)) = find_unsafe_block_parent_in_expr(cx, expr) => span,
+    // if unsafe block is part of a let/const/static statement,
+    // and accept_comment_above_statement is set to true
+    // we accept the safety comment in the line the precedes this statement.
+    accept_comment_above_statement
+        && block_parent_span(cx, id)
+            .is_some_and(|span| span_has_safety_comment(cx, span, accept_comment_above_attributes))
+}
+
+/// Returns the span of the statement, or const or static item, the unsafe block `id` is part of.
+fn block_parent_span(cx: &LateContext<'_>, id: HirId) -> Option<Span> {
+    match cx.tcx.parent_hir_node(id) {
+        Node::Expr(expr) if let Some((span, _)) = find_unsafe_block_parent_in_expr(cx, expr) => Some(span),
         Node::Stmt(hir::Stmt {
             kind:
                 hir::StmtKind::Let(hir::LetStmt { span, .. })
@@ -491,20 +587,16 @@ fn block_parents_have_safety_comment(
                 | hir::StmtKind::Semi(hir::Expr { span, .. }),
             ..
         })
-        | Node::LetStmt(hir::LetStmt { span, .. }) => *span,
+        | Node::LetStmt(hir::LetStmt { span, .. }) => Some(*span),
 
         node if let Some((span, _)) = span_and_hid_of_item_alike_node(&node)
             && is_const_or_static(&node) =>
         {
-            span
+            Some(span)
         },
 
-        _ => return false,
-    };
-    // if unsafe block is part of a let/const/static statement,
-    // and accept_comment_above_statement is set to true
-    // we accept the safety comment in the line the precedes this statement.
-    accept_comment_above_statement && span_has_safety_comment(cx, span, accept_comment_above_attributes)
+        _ => None,
+    }
 }
 
 /// Checks if an expression is "branchy", e.g. loop, match/if/etc.
@@ -759,6 +851,14 @@ fn get_body_search_span(cx: &LateContext<'_>) -> Option<Span> {
 }
 
 fn span_has_safety_comment(cx: &LateContext<'_>, span: Span, accept_comment_above_attributes: bool) -> bool {
+    matches!(
+        span_safety_comment(cx, span, accept_comment_above_attributes),
+        HasSafetyComment::Yes(..) | HasSafetyComment::Maybe
+    )
+}
+
+/// Checks if the lines immediately preceding the span in its body contain a safety comment.
+fn span_safety_comment(cx: &LateContext<'_>, span: Span, accept_comment_above_attributes: bool) -> HasSafetyComment {
     let source_map = cx.sess().source_map();
     let ctxt = span.ctxt();
     if ctxt.is_root()
@@ -773,22 +873,22 @@ fn span_has_safety_comment(cx: &LateContext<'_>, span: Span, accept_comment_abov
             // Get the text from the start of function body to the unsafe block.
             //     fn foo() { some_stuff; unsafe { stuff }; other_stuff; }
             //              ^-------------^
-            body_line.line < unsafe_line.line
-                && matches!(
-                    text_has_safety_comment(
-                        src,
-                        &unsafe_line.sf.lines()[body_line.line + 1..=unsafe_line.line],
-                        unsafe_line.sf.start_pos,
-                        accept_comment_above_attributes,
-                    ),
-                    HasSafetyComment::Yes(..)
+            if body_line.line < unsafe_line.line {
+                text_has_safety_comment(
+                    src,
+                    &unsafe_line.sf.lines()[body_line.line + 1..=unsafe_line.line],
+                    unsafe_line.sf.start_pos,
+                    accept_comment_above_attributes,
                 )
+            } else {
+                HasSafetyComment::No
+            }
         } else {
             // Problem getting source text. Pretend a comment was found.
-            true
+            HasSafetyComment::Maybe
         }
     } else {
-        false
+        HasSafetyComment::No
     }
 }
 
@@ -920,18 +1020,254 @@ fn is_const_or_static(node: &Node<'_>) -> bool {
 }
 
 fn block_has_inner_safety_comment(cx: &LateContext<'_>, span: Span) -> bool {
+    block_inner_safety_comment(cx, span).is_some()
+}
+
+/// Returns the position of the `SAFETY:` of the safety comment at the start of the block, if any.
+fn block_inner_safety_comment(cx: &LateContext<'_>, span: Span) -> Option<BytePos> {
     let source_map = cx.sess().source_map();
-    if let Ok(src) = source_map.span_to_snippet(span)
-        && let Some(after_brace) = src
-            .strip_prefix("unsafe")
-            .and_then(|s| s.trim_start().strip_prefix('{'))
-        && let Some(comment) = after_brace
-            .trim_start()
-            .strip_prefix("//")
-            .or_else(|| after_brace.trim_start().strip_prefix("/*"))
-    {
-        comment.trim_start().to_ascii_uppercase().starts_with("SAFETY:")
-    } else {
-        false
+    let src = source_map.span_to_snippet(span).ok()?;
+    let after_brace = src
+        .strip_prefix("unsafe")
+        .and_then(|s| s.trim_start().strip_prefix('{'))?
+        .trim_start();
+    let comment = after_brace
+        .strip_prefix("//")
+        .or_else(|| after_brace.strip_prefix("/*"))?
+        .trim_start();
+    comment
+        .to_ascii_uppercase()
+        .starts_with("SAFETY:")
+        .then(|| span.lo() + BytePos(u32::try_from(src.len() - comment.len()).unwrap()))
+}
+
+/// Returns the text of the comment starting at `pos`, without the comment markers.
+fn comment_text(cx: &LateContext<'_>, pos: BytePos) -> Option<String> {
+    let file_pos = cx.sess().source_map().lookup_byte_offset(pos);
+    let src = file_pos.sf.src.as_deref()?;
+    let start = file_pos.pos.to_usize();
+    let line_start = src[..start].rfind('\n').map_or(0, |i| i + 1);
+    let is_block_comment = src[line_start..start].contains("/*");
+
+    let mut text = String::new();
+    for (i, line) in src[start..].lines().enumerate() {
+        let line = line.trim();
+        if is_block_comment {
+            let (line, end) = line.split_once("*/").map_or((line, false), |(line, _)| (line, true));
+            text.push_str(line.trim_start_matches('*').trim());
+            text.push('\n');
+            if end {
+                break;
+            }
+        } else if i == 0 {
+            text.push_str(line);
+            text.push('\n');
+        } else if let Some(line) = line.strip_prefix("//") {
+            text.push_str(line.trim_start_matches(['/', '!']).trim());
+            text.push('\n');
+        } else {
+            break;
+        }
+    }
+    Some(text)
+}
+
+/// A precondition of an unsafe function, an item of the list of its `# Safety` section.
+struct Precondition {
+    text: String,
+    /// The code spans of the text, e.g. the names of the parameters.
+    code: Vec<String>,
+}
+
+impl Precondition {
+    /// Checks if the lowercase `comment` mentions one of the code spans of the precondition, or
+    /// one of its significant words if it has none.
+    fn is_addressed_by(&self, comment: &str) -> bool {
+        if self.code.is_empty() {
+            self.text
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|word| word.len() >= 4 && !PRECONDITION_STOP_WORDS.contains(&&*word.to_lowercase()))
+                .any(|word| {
+                    // Compare the stems, e.g. `aligned` and `alignment`
+                    let stem: String = word.to_lowercase().chars().take(6).collect();
+                    comment.contains(&stem)
+                })
+        } else {
+            self.code.iter().any(|code| {
+                code.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .filter(|ident| !ident.is_empty())
+                    .any(|ident| comment.contains(&ident.to_lowercase()))
+            })
+        }
+    }
+}
+
+/// The words of preconditions which do not tell them apart.
+const PRECONDITION_STOP_WORDS: &[&str] = &[
+    "about",
+    "after",
+    "also",
+    "before",
+    "behavior",
+    "behaviour",
+    "being",
+    "caller",
+    "could",
+    "does",
+    "each",
+    "every",
+    "from",
+    "guarantee",
+    "guaranteed",
+    "have",
+    "into",
+    "least",
+    "might",
+    "must",
+    "only",
+    "other",
+    "same",
+    "should",
+    "than",
+    "that",
+    "their",
+    "then",
+    "there",
+    "these",
+    "they",
+    "this",
+    "those",
+    "undefined",
+    "when",
+    "where",
+    "which",
+    "while",
+    "will",
+    "with",
+];
+
+/// Returns the items of the list of the `# Safety` section of the docs of `def_id`.
+fn safety_preconditions(cx: &LateContext<'_>, def_id: DefId) -> Vec<Precondition> {
+    #[allow(deprecated)]
+    let attrs = cx.tcx.get_all_attrs(def_id);
+    let (fragments, _) = attrs_to_doc_fragments(
+        attrs
+            .iter()
+            .filter(|attr| attr.doc_str_and_fragment_kind().is_some())
+            .map(|attr| (attr, None)),
+        true,
+    );
+    let mut doc = String::new();
+    for fragment in &fragments {
+        add_doc_fragment(&mut doc, fragment);
+    }
+
+    let mut preconditions = Vec::new();
+    let mut heading = None;
+    let mut in_safety = false;
+    let mut list_depth = 0;
+    let mut item: Option<Precondition> = None;
+    for event in pulldown_cmark::Parser::new_ext(&doc, main_body_opts()) {
+        match event {
+            Event::Start(Tag::Heading { .. }) => heading = Some(String::new()),
+            Event::End(TagEnd::Heading(_)) => {
+                in_safety = heading.take().is_some_and(|heading| heading.trim() == "Safety");
+                list_depth = 0;
+            },
+            Event::Start(Tag::List(_)) if in_safety => list_depth += 1,
+            Event::End(TagEnd::List(_)) if in_safety => list_depth -= 1,
+            Event::Start(Tag::Item) if in_safety && list_depth == 1 => {
+                item = Some(Precondition {
+                    text: String::new(),
+                    code: Vec::new(),
+                });
+            },
+            Event::End(TagEnd::Item) if in_safety && list_depth == 1 => preconditions.extend(item.take()),
+            Event::Text(text) | Event::Code(text) if let Some(heading) = &mut heading => heading.push_str(&text),
+            Event::Text(text) if let Some(item) = &mut item => item.text.push_str(&text),
+            Event::Code(code) if let Some(item) = &mut item => {
+                item.text.push('`');
+                item.text.push_str(&code);
+                item.text.push('`');
+                item.code.push(code.to_string());
+            },
+            Event::SoftBreak | Event::HardBreak if let Some(item) = &mut item => item.text.push(' '),
+            _ => {},
+        }
+    }
+    preconditions
+}
+
+/// Lints the safety comment of the unsafe block if it does not address the preconditions of
+/// the unsafe functions called in the block.
+fn check_preconditions_addressed<'tcx>(cx: &LateContext<'tcx>, block: &'tcx Block<'tcx>, comment: &str) {
+    let mut callees = Vec::new();
+    for_each_expr(cx.tcx, block, |expr| {
+        if let hir::ExprKind::Block(inner, _) = expr.kind
+            && inner.rules == BlockCheckMode::UnsafeBlock(UnsafeSource::UserProvided)
+        {
+            // Nested unsafe blocks have their own safety comment
+            return ControlFlow::<!, _>::Continue(Descend::No);
+        }
+        if let Some(def_id) = fn_def_id(cx, expr)
+            && cx.tcx.fn_sig(def_id).skip_binder().safety().is_unsafe()
+            && !callees.contains(&def_id)
+        {
+            callees.push(def_id);
+        }
+        ControlFlow::Continue(Descend::Yes)
+    });
+
+    let preconditions: Vec<_> = callees
+        .into_iter()
+        .flat_map(|def_id| {
+            safety_preconditions(cx, def_id)
+                .into_iter()
+                .map(move |precondition| (def_id, precondition))
+        })
+        .collect();
+    let comment = comment.to_lowercase();
+    let items = comment
+        .lines()
+        .filter(|line| {
+            line.starts_with(['-', '*', '+'])
+                || line
+                    .split_once(['.', ')'])
+                    .is_some_and(|(number, _)| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()))
+        })
+        .count();
+    if items >= preconditions.len() {
+        return;
+    }
+    let missing: Vec<_> = preconditions
+        .iter()
+        .filter(|(_, precondition)| !precondition.is_addressed_by(&comment))
+        .collect();
+    if let Some((def_id, _)) = missing.first() {
+        let source_map = cx.tcx.sess.source_map();
+        let span = if source_map.is_multiline(block.span) {
+            source_map.span_until_char(block.span, '\n')
+        } else {
+            block.span
+        };
+        span_lint_and_then(
+            cx,
+            INCOMPLETE_SAFETY_COMMENT,
+            span,
+            format!(
+                "safety comment does not address every precondition of `{}`",
+                cx.tcx.def_path_str(*def_id)
+            ),
+            |diag| {
+                for (def_id, precondition) in &missing {
+                    diag.note(format!(
+                        "`{}` requires: {}",
+                        cx.tcx.def_path_str(*def_id),
+                        precondition.text.trim().trim_end_matches('.')
+                    ));
+                }
+                diag.help("explain in the safety comment why each precondition holds");
+            },
+        );
     }
 }
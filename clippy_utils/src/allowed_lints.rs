@@ -0,0 +1,206 @@
+//! Lints allowed by the configuration file.
+//!
+//! The `[[overrides]]` entries of `clippy.toml` list with their `allow` key the lints allowed in
+//! the files matching their `paths`, or in the test code for the entries setting `cfg-test`. The
+//! configuration passes the allowed lints to [`track`], and the lint levels computed by the queries
+//! of [`provide`] allow them as if `#![allow]` was written at the top of each of these files, and
+//! `#[allow]` on each `#[test]` function and `#[cfg(test)]` item. The attributes of the code in
+//! these files and items take precedence, and the lints forbidden around them stay forbidden.
+
+use crate::str_utils::glob_matches;
+use crate::{baseline, is_cfg_test, is_in_test, is_in_test_function};
+use rustc_hir::{CRATE_HIR_ID, HirId, Item, ItemKind, OwnerId, OwnerNode};
+use rustc_lint::{Level, unerased_lint_store};
+use rustc_middle::lint::{LevelSpec, LintLevelSource, ShallowLintLevelMap};
+use rustc_middle::ty::TyCtxt;
+use rustc_middle::util::Providers;
+use rustc_span::{Span, Symbol};
+use std::ffi::OsStr;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// The lints allowed by the configuration file, as the lowercase name of each lint with its
+/// `clippy::` prefix, e.g. `clippy::unwrap_used`, along with the span of the name.
+#[derive(Default)]
+pub struct AllowedLints {
+    /// The lints allowed in the `#[test]` functions and the `#[cfg(test)]` items of the crate.
+    pub in_tests: Vec<(String, Span)>,
+    /// The lints allowed in the files matching some globs.
+    pub in_files: Vec<AllowedInFiles>,
+    /// The directory the globs of `in_files` are relative to, the package directory.
+    pub root: PathBuf,
+    /// The names given in all the `allow` keys, along with their span, including the ones of the
+    /// entries which don't apply to the crate.
+    pub names: Vec<(String, Span)>,
+}
+
+/// The lints allowed by an `[[overrides]]` entry setting `paths`.
+pub struct AllowedInFiles {
+    /// The globs matched against the path of the files, relative to the package directory.
+    pub paths: Vec<String>,
+    pub lints: Vec<(String, Span)>,
+    /// Whether the lints are only allowed in test code.
+    pub only_tests: bool,
+}
+
+/// The lints allowed by the configuration file, along with a description of them which changes
+/// along with the lint levels they set.
+static ALLOWED_LINTS: OnceLock<(AllowedLints, String)> = OnceLock::new();
+
+/// The name of the environment variable read by the lint levels of the crate, which the queries of
+/// [`provide`] set to the description of the allowed lints. The configuration file is not tracked
+/// by the incremental compilation, so this tells it to compute the lint levels again when it
+/// changes.
+const ALLOWED_LINTS_VAR: &str = "__CLIPPY_ALLOWED_LINTS";
+
+/// Allows the lints of `allowed`.
+pub fn track(allowed: AllowedLints) {
+    let mut description = String::new();
+    for lint in &allowed.in_tests {
+        let _ = write!(description, "{lint:?} in tests;");
+    }
+    for entry in &allowed.in_files {
+        let _ = write!(
+            description,
+            "{:?} in {:?}{};",
+            entry.lints,
+            entry.paths,
+            if entry.only_tests { " tests" } else { "" }
+        );
+    }
+    if !allowed.in_tests.is_empty() || !allowed.in_files.is_empty() || !allowed.names.is_empty() {
+        let _ = ALLOWED_LINTS.set((allowed, description));
+    }
+}
+
+/// Returns the names given in the `allow` keys of the configuration file, along with their span.
+pub fn names() -> &'static [(String, Span)] {
+    ALLOWED_LINTS.get().map_or(&[], |(allowed, _)| &allowed.names)
+}
+
+/// Overrides the queries computing the lint levels of the crate, to allow the lints allowed by the
+/// configuration file.
+pub fn provide(providers: &mut Providers) {
+    type ShallowLintLevelsOn = for<'tcx> fn(TyCtxt<'tcx>, OwnerId) -> ShallowLintLevelMap;
+    type EnvVarOs = for<'tcx> fn(TyCtxt<'tcx>, &'tcx OsStr) -> Option<&'tcx OsStr>;
+    static SHALLOW_LINT_LEVELS_ON: OnceLock<ShallowLintLevelsOn> = OnceLock::new();
+    static ENV_VAR_OS: OnceLock<EnvVarOs> = OnceLock::new();
+
+    let _ = SHALLOW_LINT_LEVELS_ON.set(providers.queries.shallow_lint_levels_on);
+    let _ = ENV_VAR_OS.set(providers.queries.env_var_os);
+    providers.queries.shallow_lint_levels_on = |tcx, owner| {
+        let mut levels = SHALLOW_LINT_LEVELS_ON.get().unwrap()(tcx, owner);
+        if let Some((allowed, _)) = ALLOWED_LINTS.get() {
+            let _ = tcx.env_var_os(OsStr::new(ALLOWED_LINTS_VAR));
+            add_levels(tcx, allowed, owner, &mut levels);
+        }
+        levels
+    };
+    providers.queries.env_var_os = |tcx, key| {
+        if key == ALLOWED_LINTS_VAR {
+            ALLOWED_LINTS
+                .get()
+                .map(|(_, description)| OsStr::new(tcx.arena.alloc_str(description)))
+        } else {
+            ENV_VAR_OS.get().unwrap()(tcx, key)
+        }
+    };
+}
+
+/// Adds to `levels`, the lint levels set by the attributes of `owner`, the levels of the lints of
+/// `allowed` in `owner`.
+fn add_levels(tcx: TyCtxt<'_>, allowed: &AllowedLints, owner: OwnerId, levels: &mut ShallowLintLevelMap) {
+    let sm = tcx.sess.source_map();
+    let owner_id = HirId::make_owner(owner.def_id);
+    let matching = |span: Span, only_tests: bool| {
+        let file = baseline::file(sm, &allowed.root, span);
+        allowed
+            .in_files
+            .iter()
+            .filter(move |entry| {
+                entry.only_tests == only_tests && entry.paths.iter().any(|glob| glob_matches(glob, &file))
+            })
+            .flat_map(|entry| &entry.lints)
+    };
+
+    if let Some(span) = file_span(tcx, owner)
+        && allowed.in_files.iter().any(|entry| !entry.only_tests)
+    {
+        allow(tcx, levels, owner_id, matching(span, false));
+    }
+
+    if !allowed.in_tests.is_empty() || allowed.in_files.iter().any(|entry| entry.only_tests) {
+        // The test code starts at the `#[test]` functions and the nodes with a `#[cfg(test)]`
+        // attribute which are not in test code already
+        let attrs = tcx.hir_attr_map(owner);
+        let nodes = [owner_id]
+            .into_iter()
+            .chain(attrs.map.keys().map(|&local_id| HirId { owner, local_id }));
+        for hir_id in nodes {
+            let starts_tests = if hir_id == owner_id {
+                is_cfg_test(tcx, hir_id) || is_in_test_function(tcx, hir_id)
+            } else {
+                is_cfg_test(tcx, hir_id)
+            };
+            if starts_tests && (hir_id == CRATE_HIR_ID || !is_in_test(tcx, tcx.parent_hir_id(hir_id))) {
+                let lints = allowed.in_tests.iter().chain(matching(tcx.hir_span(hir_id), true));
+                allow(tcx, levels, hir_id, lints);
+            }
+        }
+    }
+}
+
+/// Returns the span of the items of `owner` if it is the crate root or a module whose items are in
+/// their own file.
+fn file_span(tcx: TyCtxt<'_>, owner: OwnerId) -> Option<Span> {
+    match tcx.hir_owner_node(owner) {
+        OwnerNode::Crate(module) => Some(module.spans.inner_span),
+        OwnerNode::Item(&Item {
+            kind: ItemKind::Mod(_, module),
+            span,
+            ..
+        }) => {
+            let sm = tcx.sess.source_map();
+            (sm.span_to_filename(span) != sm.span_to_filename(module.spans.inner_span))
+                .then_some(module.spans.inner_span)
+        },
+        _ => None,
+    }
+}
+
+/// Allows `lints` at `hir_id`, except the ones whose level is set by the attributes of `hir_id` or
+/// which are forbidden there.
+fn allow<'a>(
+    tcx: TyCtxt<'_>,
+    levels: &mut ShallowLintLevelMap,
+    hir_id: HirId,
+    lints: impl IntoIterator<Item = &'a (String, Span)>,
+) {
+    let store = unerased_lint_store(tcx.sess);
+    for (name, span) in lints {
+        for &lint in store.find_lints(name).unwrap_or_default() {
+            if levels
+                .specs
+                .get(&hir_id.local_id)
+                .is_some_and(|specs| specs.contains_key(&lint))
+                || levels.lint_level_spec_at_node(tcx, lint, hir_id).level() == Level::Forbid
+            {
+                continue;
+            }
+            let spec = LevelSpec::new(
+                Level::Allow,
+                None,
+                LintLevelSource::Node {
+                    name: Symbol::intern(name),
+                    span: *span,
+                    reason: None,
+                },
+            );
+            levels
+                .specs
+                .get_mut_or_insert_default(hir_id.local_id)
+                .insert(lint, spec);
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//! Baseline files, recording the diagnostics of a crate so that only the new ones are reported.
+//!
+//! `cargo clippy --write-baseline <file>` runs `clippy-driver` with `--write-baseline=<dir>`, each
+//! crate records its diagnostics in a file of that directory instead of emitting them, and the
+//! files are then merged into the baseline by [`merge`]. `cargo clippy --baseline <file>` runs
+//! `clippy-driver` with `--baseline=<file>`, which emits only the diagnostics not in the baseline.
+//!
+//! A diagnostic is identified by its [`DiagnosticKey`], as in the diffs of lintcheck, so that it
+//! still matches the baseline when the code around it moves. The baseline records how many times
+//! each of them is emitted, any additional occurrence is reported.
+
+use crate::diagnostic_key::DiagnosticKey;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_errors::{DiagMessage, MultiSpan};
+use rustc_lint::{Level, Lint};
+use rustc_span::source_map::SourceMap;
+use rustc_span::{FileName, Span};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::{env, fs, process};
+
+#[derive(Deserialize, Serialize)]
+pub(crate) struct Entry {
+    #[serde(flatten)]
+    pub(crate) key: DiagnosticKey,
+    pub(crate) count: usize,
+}
+
+enum Mode {
+    /// Emit the diagnostics not in the baseline, each key is mapped to the number of occurrences
+    /// left in the baseline.
+    Read(FxHashMap<DiagnosticKey, usize>),
+    /// Record the diagnostics to write them in the given file.
+    Write(PathBuf, BTreeMap<DiagnosticKey, usize>),
+}
+
+struct Baseline {
+    /// The directory the files of the baseline are relative to.
+    root: PathBuf,
+    mode: Mode,
+}
+
+static BASELINE: OnceLock<Mutex<Baseline>> = OnceLock::new();
+
+/// Reads the baseline `path` whose diagnostics will not be emitted.
+pub fn read(path: &Path) -> Result<(), String> {
+    let contents = fs::read(path).map_err(|e| format!("failed to read baseline `{}`: {e}", path.display()))?;
+    let entries: Vec<Entry> = serde_json::from_slice(&contents)
+        .map_err(|e| format!("failed to deserialize baseline `{}`: {e}", path.display()))?;
+    init(
+        path,
+        Mode::Read(entries.into_iter().map(|entry| (entry.key, entry.count)).collect()),
+    );
+    Ok(())
+}
+
+/// Records the diagnostics instead of emitting them, to write them in the directory `dir` with
+/// [`write`].
+pub fn record(dir: &Path) {
+    let file = dir.join(format!("{}.json", process::id()));
+    init(dir, Mode::Write(file, BTreeMap::new()));
+}
+
+fn init(path: &Path, mode: Mode) {
+    let root = path
+        .parent()
+        .and_then(|root| root.canonicalize().ok())
+        .unwrap_or_default();
+    let _ = BASELINE.set(Mutex::new(Baseline { root, mode }));
+}
+
+/// Writes the diagnostics recorded since [`record`].
+pub fn write() -> Result<(), String> {
+    if let Some(baseline) = BASELINE.get()
+        && let Mode::Write(file, recorded) = &baseline.lock().unwrap().mode
+        && !recorded.is_empty()
+    {
+        let entries: Vec<Entry> = recorded
+            .iter()
+            .map(|(key, &count)| Entry {
+                key: key.clone(),
+                count,
+            })
+            .collect();
+        fs::write(file, serde_json::to_vec(&entries).unwrap())
+            .map_err(|e| format!("failed to write `{}`: {e}", file.display()))?;
+    }
+    Ok(())
+}
+
+/// Merges the files written by [`write`] to the directory `dir` into the baseline `path`, and
+/// removes the directory.
+///
+/// As a file may be linted as part of several crates, e.g. the library and its unit tests, the
+/// count of a diagnostic is the highest count of the crates.
+pub fn merge(dir: &Path, path: &Path) -> Result<(), String> {
+    let mut merged: BTreeMap<DiagnosticKey, usize> = BTreeMap::new();
+    let files = fs::read_dir(dir).map_err(|e| format!("failed to read `{}`: {e}", dir.display()))?;
+    for file in files {
+        let file = file.map_err(|e| e.to_string())?.path();
+        let contents = fs::read(&file).map_err(|e| format!("failed to read `{}`: {e}", file.display()))?;
+        let entries: Vec<Entry> = serde_json::from_slice(&contents).map_err(|e| e.to_string())?;
+        for entry in entries {
+            let count = merged.entry(entry.key).or_default();
+            *count = (*count).max(entry.count);
+        }
+    }
+    let entries: Vec<Entry> = merged.into_iter().map(|(key, count)| Entry { key, count }).collect();
+    fs::write(path, serde_json::to_string_pretty(&entries).unwrap() + "\n")
+        .map_err(|e| format!("failed to write baseline `{}`: {e}", path.display()))?;
+    fs::remove_dir_all(dir).map_err(|e| format!("failed to remove `{}`: {e}", dir.display()))
+}
+
+/// Checks if a lint emitted at the level returned by `level` is part of the baseline, or recorded
+/// to be written to it, in which case it must not be emitted.
+pub(crate) fn contains(
+    sm: &SourceMap,
+    lint: &'static Lint,
+    level: impl FnOnce() -> Level,
+    span: &MultiSpan,
+    msg: &DiagMessage,
+) -> bool {
+    let Some(baseline) = BASELINE.get() else {
+        return false;
+    };
+    // Allowed and expected lints are not emitted anyway
+    if !matches!(level(), Level::Warn | Level::ForceWarn | Level::Deny | Level::Forbid) {
+        return false;
+    }
+    let mut baseline = baseline.lock().unwrap();
+    let key = key(sm, &baseline.root, lint, span, msg);
+    match &mut baseline.mode {
+        Mode::Read(remaining) => match remaining.get_mut(&key) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            },
+            _ => false,
+        },
+        Mode::Write(_, recorded) => {
+            *recorded.entry(key).or_default() += 1;
+            true
+        },
+    }
+}
+
+/// Returns the key identifying a diagnostic of `lint`, whose file is relative to `root` when
+/// possible.
+pub(crate) fn key(
+    sm: &SourceMap,
+    root: &Path,
+    lint: &'static Lint,
+    span: &MultiSpan,
+    msg: &DiagMessage,
+) -> DiagnosticKey {
+    DiagnosticKey::new(
+        &lint.name_lower(),
+        &span
+            .primary_span()
+            .map_or_else(String::new, |span| file(sm, root, span)),
+        msg.as_str().unwrap_or_default(),
+    )
+}
+
+/// Returns the path of the file of `span`, relative to `root` when possible.
+pub fn file(sm: &SourceMap, root: &Path, span: Span) -> String {
+    let name = sm.span_to_filename(span);
+    let Some(path) = (match &name {
+        FileName::Real(real) => real.local_path(),
+        _ => None,
+    }) else {
+        return name.prefer_local_unconditionally().to_string();
+    };
+    let path = env::current_dir()
+        .and_then(|dir| dir.join(path).canonicalize())
+        .unwrap_or_else(|_| path.to_path_buf());
+    path.strip_prefix(root)
+        .unwrap_or(&path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
@@ -0,0 +1,288 @@
+//! Budgets of warnings, capping the number of diagnostics each lint may emit.
+//!
+//! The budgets are configured by the `budgets` table of `clippy.toml`, mapping the name of a lint
+//! to the number of diagnostics it is allowed to emit. The driver passes them to [`track`], every
+//! emitted diagnostic of these lints is counted, and the lints that emitted more diagnostics than
+//! their budget are reported as errors, so that their number can only go down. The lints that
+//! emitted fewer get a note to lower their budget.
+//!
+//! `cargo clippy` runs `clippy-driver` with `CLIPPY_BUDGETS_RECORD` set, each crate with budgets
+//! records its diagnostics with [`check`] in a file next to its metadata, and the files of the
+//! crates listed by cargo, including the ones which were not checked again as they did not change,
+//! are then merged by [`merge`], so that the budgets cover all the crates checked with a
+//! configuration file, e.g. the library, the binaries and the tests of a package. As with the
+//! baseline, a diagnostic emitted in several crates, e.g. the library and its unit tests, is
+//! counted once. When `clippy-driver` is run on its own, the diagnostics are counted in the crate
+//! and [`check`] reports them.
+//!
+//! When the configuration file may be shared by several packages, e.g. in a workspace, the note to
+//! lower the budgets is not emitted as the packages which were not checked may need a higher
+//! budget.
+
+use crate::baseline::{self, Entry};
+use crate::diagnostic_key::DiagnosticKey;
+use rustc_errors::{DiagMessage, MultiSpan, pluralize};
+use rustc_lint::{Level, Lint};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::config::OutputType;
+use rustc_span::Span;
+use rustc_span::source_map::SourceMap;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+/// The environment variable set by `cargo clippy` for the crates to record their diagnostics
+/// instead of reporting them.
+pub const RECORD_VAR: &str = "CLIPPY_BUDGETS_RECORD";
+
+/// The extension of the files the diagnostics of the crates are recorded to.
+const EXTENSION: &str = "clippy-budgets.json";
+
+struct Budget {
+    max: u64,
+    emitted: u64,
+    /// The span of the entry in the configuration file.
+    span: Span,
+    /// The diagnostics recorded for `cargo clippy`.
+    recorded: BTreeMap<DiagnosticKey, usize>,
+}
+
+struct Budgets {
+    lints: BTreeMap<String, Budget>,
+    /// Whether the configuration file may be shared by several packages.
+    shared: bool,
+    /// Whether the diagnostics are recorded instead of being reported.
+    record: bool,
+}
+
+static BUDGETS: OnceLock<Mutex<Budgets>> = OnceLock::new();
+
+/// The budget of a lint with the diagnostics of a crate, as recorded by [`check`].
+#[derive(Deserialize, Serialize)]
+struct Recorded {
+    lint: String,
+    budget: u64,
+    location: String,
+    shared: bool,
+    diagnostics: Vec<Entry>,
+}
+
+/// The number of diagnostics a lint emitted in all the crates checked with a configuration file,
+/// as merged by [`merge`].
+pub struct Count {
+    /// The lowercase name of the lint, with its `clippy::` prefix.
+    pub lint: String,
+    pub budget: u64,
+    pub emitted: u64,
+    /// The location of the entry in the configuration file, as `path:line:column`.
+    pub location: String,
+    /// Whether the configuration file may be shared by several packages.
+    pub shared: bool,
+}
+
+/// Counts the diagnostics of the lints of `budgets`, given as the lowercase name of each lint with
+/// its `clippy::` prefix, e.g. `clippy::unwrap_used`, with its budget and the span of its entry.
+/// `shared` tells whether the configuration file may be shared by several packages. When `record`
+/// is set, the diagnostics are recorded by [`check`] instead of being reported.
+pub fn track(budgets: impl IntoIterator<Item = (String, u64, Span)>, shared: bool, record: bool) {
+    let lints: BTreeMap<String, Budget> = budgets
+        .into_iter()
+        .map(|(lint, budget, span)| {
+            (
+                lint,
+                Budget {
+                    max: budget,
+                    emitted: 0,
+                    span,
+                    recorded: BTreeMap::new(),
+                },
+            )
+        })
+        .collect();
+    // The crates without budgets are tracked as well when recording, to remove their stale records
+    if !lints.is_empty() || record {
+        let _ = BUDGETS.set(Mutex::new(Budgets { lints, shared, record }));
+    }
+}
+
+/// Counts a diagnostic of `lint` emitted at the level returned by `level`.
+pub(crate) fn count(
+    sm: &SourceMap,
+    lint: &'static Lint,
+    level: impl FnOnce() -> Level,
+    span: &MultiSpan,
+    msg: &DiagMessage,
+) {
+    let Some(budgets) = BUDGETS.get() else {
+        return;
+    };
+    let mut budgets = budgets.lock().unwrap();
+    let record = budgets.record;
+    if let Some(budget) = budgets.lints.get_mut(&lint.name_lower())
+        // Allowed and expected lints are not emitted
+        && matches!(level(), Level::Warn | Level::ForceWarn | Level::Deny | Level::Forbid)
+    {
+        budget.emitted += 1;
+        if record {
+            *budget
+                .recorded
+                .entry(baseline::key(sm, Path::new(""), lint, span, msg))
+                .or_default() += 1;
+        }
+    }
+}
+
+/// Records the diagnostics of the crate when run by `cargo clippy`, otherwise reports the lints
+/// whose diagnostics exceeded their budget as errors, and the lints that could lower their budget
+/// unless the configuration file may be shared.
+pub fn check(tcx: TyCtxt<'_>) {
+    let Some(budgets) = BUDGETS.get() else {
+        return;
+    };
+    let budgets = budgets.lock().unwrap();
+    let sess = tcx.sess;
+    if budgets.record {
+        let file = tcx
+            .output_filenames(())
+            .path(OutputType::Metadata)
+            .as_path()
+            .with_extension(EXTENSION);
+        if budgets.lints.is_empty() {
+            if let Err(e) = fs::remove_file(&file)
+                && e.kind() != ErrorKind::NotFound
+            {
+                sess.dcx().err(format!("failed to remove `{}`: {e}", file.display()));
+            }
+            return;
+        }
+        let recorded: Vec<Recorded> = budgets
+            .lints
+            .iter()
+            .map(|(lint, budget)| Recorded {
+                lint: lint.clone(),
+                budget: budget.max,
+                location: location(sess.source_map(), budget.span),
+                shared: budgets.shared,
+                diagnostics: budget
+                    .recorded
+                    .iter()
+                    .map(|(key, &count)| Entry {
+                        key: key.clone(),
+                        count,
+                    })
+                    .collect(),
+            })
+            .collect();
+        if let Err(e) = fs::write(&file, serde_json::to_vec(&recorded).unwrap()) {
+            sess.dcx().err(format!("failed to write `{}`: {e}", file.display()));
+        }
+        return;
+    }
+    for (
+        lint,
+        &Budget {
+            max: budget,
+            emitted,
+            span,
+            ..
+        },
+    ) in &budgets.lints
+    {
+        if emitted > budget {
+            sess.dcx()
+                .struct_span_err(
+                    span,
+                    format!(
+                        "`{lint}` emitted {emitted} warning{}, exceeding its budget of {budget}",
+                        pluralize!(emitted)
+                    ),
+                )
+                .with_help(format!(
+                    "fix {} of them, or raise the budget of `{lint}` in `clippy.toml`",
+                    emitted - budget
+                ))
+                .emit();
+        } else if emitted < budget && !budgets.shared {
+            sess.dcx().span_note(
+                span,
+                format!(
+                    "`{lint}` emitted {emitted} warning{}, lower its budget in `clippy.toml` from {budget} to {emitted}",
+                    pluralize!(emitted)
+                ),
+            );
+        }
+    }
+}
+
+/// Returns the location of the start of `span`, as `path:line:column`.
+fn location(sm: &SourceMap, span: Span) -> String {
+    let loc = sm.lookup_char_pos(span.lo());
+    format!(
+        "{}:{}:{}",
+        sm.filename_for_diagnostics(&loc.file.name),
+        loc.line,
+        loc.col_display + 1
+    )
+}
+
+/// Returns the files [`check`] may have recorded the diagnostics of a crate to, given a file of the
+/// crate listed by cargo: next to its metadata, or in its own directory for build scripts.
+pub fn record_files(artifact: &Path) -> Vec<PathBuf> {
+    let Some(dir) = artifact.parent() else {
+        return Vec::new();
+    };
+    if artifact.extension().is_some_and(|ext| ext == "rmeta") {
+        vec![artifact.with_extension(EXTENSION)]
+    } else {
+        fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|path| path.to_string_lossy().ends_with(EXTENSION))
+            .collect()
+    }
+}
+
+/// Merges the existing `files` written by [`check`] into the number of diagnostics of each lint in
+/// all the crates checked with its configuration file.
+///
+/// As a file may be linted as part of several crates, e.g. the library and its unit tests, the
+/// count of a diagnostic is the highest count of the crates.
+pub fn merge(files: impl IntoIterator<Item = impl AsRef<Path>>) -> Result<Vec<Count>, String> {
+    // The budgets are identified by the location of their entry, which includes the path of the
+    // configuration file
+    let mut merged: BTreeMap<(String, String), (Recorded, BTreeMap<DiagnosticKey, usize>)> = BTreeMap::new();
+    for file in files {
+        let file = file.as_ref();
+        let contents = match fs::read(file) {
+            Ok(contents) => contents,
+            // The crates without budgets or not checked by Clippy, e.g. the dependencies
+            Err(e) if e.kind() == ErrorKind::NotFound => continue,
+            Err(e) => return Err(format!("failed to read `{}`: {e}", file.display())),
+        };
+        let recorded: Vec<Recorded> = serde_json::from_slice(&contents).map_err(|e| e.to_string())?;
+        for mut recorded in recorded {
+            let diagnostics = std::mem::take(&mut recorded.diagnostics);
+            let (_, counts) = merged
+                .entry((recorded.location.clone(), recorded.lint.clone()))
+                .or_insert_with(|| (recorded, BTreeMap::new()));
+            for entry in diagnostics {
+                let count = counts.entry(entry.key).or_default();
+                *count = (*count).max(entry.count);
+            }
+        }
+    }
+    Ok(merged
+        .into_values()
+        .map(|(recorded, counts)| Count {
+            lint: recorded.lint,
+            budget: recorded.budget,
+            emitted: counts.values().map(|&count| count as u64).sum(),
+            location: recorded.location,
+            shared: recorded.shared,
+        })
+        .collect())
+}
@@ -0,0 +1,27 @@
+//! The key identifying a diagnostic across runs, shared by the baselines of `cargo clippy` and the
+//! diffs of lintcheck, which includes this file, so that both match the same diagnostics.
+//!
+//! A diagnostic is identified by its lint, its file and its message, without its line and column so
+//! that it still matches when the code around it moves. The diagnostics with the same key are only
+//! told apart by their number.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub struct DiagnosticKey {
+    /// The lowercase name of the lint with its `clippy::` prefix, e.g. `clippy::unwrap_used`.
+    pub lint: String,
+    /// The path of the file of the primary span, with `/` separators.
+    pub file: String,
+    pub message: String,
+}
+
+impl DiagnosticKey {
+    pub fn new(lint: &str, file: &str, message: &str) -> Self {
+        Self {
+            lint: lint.to_owned(),
+            file: file.replace('\\', "/"),
+            message: message.trim().to_owned(),
+        }
+    }
+}
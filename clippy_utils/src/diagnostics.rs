@@ -8,6 +8,7 @@
 //! Thank you!
 //! ~The `INTERNAL_METADATA_COLLECTOR` lint
 
+use crate::{baseline, budgets};
 use rustc_errors::{Applicability, Diag, DiagCtxtHandle, DiagMessage, Diagnostic, Level, MultiSpan};
 #[cfg(debug_assertions)]
 use rustc_errors::{EmissionGuarantee, SubstitutionPart, Suggestions};
@@ -251,6 +252,24 @@ where
     }
 
     let sp = sp.into();
+    let msg = msg.into();
+    if baseline::contains(
+        cx.sess().source_map(),
+        lint,
+        || cx.get_lint_level_spec(lint).level(),
+        &sp,
+        &msg,
+    ) {
+        return;
+    }
+    budgets::count(
+        cx.sess().source_map(),
+        lint,
+        || cx.get_lint_level_spec(lint).level(),
+        &sp,
+        &msg,
+    );
+
     #[expect(clippy::disallowed_methods)]
     cx.emit_span_lint(
         lint,
@@ -329,6 +348,25 @@ pub fn span_lint_hir_and_then(
     msg: impl Into<DiagMessage>,
     f: impl FnOnce(&mut Diag<'_, ()>),
 ) {
+    let sp = sp.into();
+    let msg = msg.into();
+    if baseline::contains(
+        cx.tcx.sess.source_map(),
+        lint,
+        || cx.tcx.lint_level_spec_at_node(lint, hir_id).level(),
+        &sp,
+        &msg,
+    ) {
+        return;
+    }
+    budgets::count(
+        cx.tcx.sess.source_map(),
+        lint,
+        || cx.tcx.lint_level_spec_at_node(lint, hir_id).level(),
+        &sp,
+        &msg,
+    );
+
     #[expect(clippy::disallowed_methods)]
     cx.tcx.emit_node_span_lint(
         lint,
@@ -39,12 +39,16 @@ extern crate rustc_session;
 extern crate rustc_span;
 extern crate rustc_trait_selection;
 
+pub mod allowed_lints;
 pub mod ast_utils;
 #[deny(missing_docs)]
 pub mod attrs;
+pub mod baseline;
+pub mod budgets;
 mod check_proc_macro;
 pub mod comparisons;
 pub mod consts;
+pub mod diagnostic_key;
 pub mod diagnostics;
 pub mod eager_or_lazy;
 pub mod higher;
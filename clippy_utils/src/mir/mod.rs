@@ -8,6 +8,8 @@ use rustc_middle::mir::{
     BasicBlock, Body, InlineAsmOperand, Local, Location, Place, START_BLOCK, StatementKind, TerminatorKind, traversal,
 };
 use rustc_middle::ty::TyCtxt;
+use rustc_mir_dataflow::impls::MaybeLiveLocals;
+use rustc_mir_dataflow::{Analysis as _, ResultsCursor};
 
 mod possible_borrower;
 pub use possible_borrower::PossibleBorrowerMap;
@@ -139,6 +141,24 @@ pub fn used_exactly_once(mir: &Body<'_>, local: Local) -> Option<bool> {
     })
 }
 
+/// Computes the locals which are live, i.e. which may be used later, at each location of `mir`. A
+/// local holding a value with drop glue stays live until it is moved out or dropped.
+pub fn live_locals<'mir, 'tcx>(tcx: TyCtxt<'tcx>, mir: &'mir Body<'tcx>) -> ResultsCursor<'mir, 'tcx, MaybeLiveLocals> {
+    MaybeLiveLocals
+        .iterate_to_fixpoint(tcx, mir, None)
+        .into_results_cursor(mir)
+}
+
+/// Returns the node the lints of the statement or terminator at `location` are emitted at, i.e.
+/// the node whose lint levels apply to it.
+pub fn lint_root(mir: &Body<'_>, location: Location) -> HirId {
+    mir.source_scopes[mir.source_info(location).scope]
+        .local_data
+        .as_ref()
+        .unwrap_crate_local()
+        .lint_root
+}
+
 /// Returns the `mir::Body` containing the node associated with `hir_id`.
 #[expect(clippy::module_name_repetitions)]
 pub fn enclosing_mir(tcx: TyCtxt<'_>, hir_id: HirId) -> Option<&Body<'_>> {
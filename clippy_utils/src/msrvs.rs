@@ -8,10 +8,14 @@ use rustc_hir::{HirId, RustcVersion, StabilityLevel, StableSince};
 use rustc_lint::LateContext;
 use rustc_middle::ty::TyCtxt;
 use rustc_session::Session;
-use rustc_span::Symbol;
+use rustc_span::{BytePos, Pos as _, SourceFile, Span, Symbol, SyntaxContext};
 use serde::Deserialize;
 use std::iter::once;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::{env, fs};
+use toml::Spanned;
+use toml::de::{DeTable, DeValue};
 
 macro_rules! msrv_aliases {
     ($($major:literal,$minor:literal,$patch:literal {
@@ -32,12 +36,12 @@ msrv_aliases! {
     1,88,0 { LET_CHAINS, AS_CHUNKS }
     1,87,0 { OS_STR_DISPLAY, INT_MIDPOINT, CONST_CHAR_IS_DIGIT, UNSIGNED_IS_MULTIPLE_OF, INTEGER_SIGN_CAST }
     1,86,0 { VEC_POP_IF }
-    1,85,0 { UINT_FLOAT_MIDPOINT, CONST_SIZE_OF_VAL, WAKER_NOOP }
+    1,85,0 { UINT_FLOAT_MIDPOINT, CONST_SIZE_OF_VAL, WAKER_NOOP, ASYNC_CLOSURE }
     1,84,0 { CONST_OPTION_AS_SLICE, MANUAL_DANGLING_PTR }
     1,83,0 { CONST_EXTERN_FN, CONST_FLOAT_BITS_CONV, CONST_FLOAT_CLASSIFY, CONST_MUT_REFS, CONST_UNWRAP }
     1,82,0 { IS_NONE_OR, REPEAT_N, RAW_REF_OP, SPECIALIZED_TO_STRING_FOR_REFS }
     1,81,0 { LINT_REASONS_STABILIZATION, ERROR_IN_CORE, EXPLICIT_SELF_TYPE_ELISION, DURATION_ABS_DIFF }
-    1,80,0 { BOX_INTO_ITER, LAZY_CELL }
+    1,80,0 { BOX_INTO_ITER, LAZY_CELL, EXCLUSIVE_RANGE_PATTERN }
     1,79,0 { CONST_BLOCKS, CSTR_COUNT_BYTES }
     1,77,0 { C_STR_LITERALS }
     1,76,0 { PTR_FROM_REF, OPTION_RESULT_INSPECT }
@@ -162,18 +166,25 @@ impl Msrv {
         self.at(tcx, node).is_none_or(|msrv| msrv >= required)
     }
 
+    /// Reads the MSRV from the `rust-version` of `Cargo.toml` if it isn't set in `clippy.toml`,
+    /// falling back to the toolchain pinned by a `rust-toolchain.toml` file.
     pub fn read_cargo(&mut self, sess: &Session) {
-        let cargo_msrv = std::env::var("CARGO_PKG_RUST_VERSION")
+        let cargo_msrv = env::var("CARGO_PKG_RUST_VERSION")
             .ok()
             .and_then(|v| parse_version(Symbol::intern(&v)));
 
         match (self.0, cargo_msrv) {
             (None, Some(cargo_msrv)) => self.0 = Some(cargo_msrv),
             (Some(clippy_msrv), Some(cargo_msrv)) if clippy_msrv != cargo_msrv => {
-                sess.dcx().warn(format!(
+                let message = format!(
                     "the MSRV in `clippy.toml` and `Cargo.toml` differ; using `{clippy_msrv}` from `clippy.toml`"
-                ));
+                );
+                match env::var_os("CARGO_MANIFEST_DIR").and_then(|dir| manifest_rust_version(sess, Path::new(&dir))) {
+                    Some((_, span)) => sess.dcx().span_warn(span, message),
+                    None => sess.dcx().warn(message),
+                }
             },
+            (None, None) => self.0 = read_toolchain_file(),
             _ => {},
         }
     }
@@ -234,6 +245,97 @@ impl MsrvStack {
     }
 }
 
+/// Reads the version of the toolchain pinned by the `rust-toolchain.toml` or `rust-toolchain` file
+/// of the package, if it is pinned to a version rather than a channel like `stable`.
+fn read_toolchain_file() -> Option<RustcVersion> {
+    let dir = match env::var_os("CARGO_MANIFEST_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => env::current_dir().ok()?,
+    };
+    let contents = dir.ancestors().find_map(|dir| {
+        ["rust-toolchain", "rust-toolchain.toml"]
+            .iter()
+            .find_map(|name| fs::read_to_string(dir.join(name)).ok())
+    })?;
+    let channel = if let Ok(toolchain_file) = DeTable::parse(&contents) {
+        // `channel = "1.70.0"` in the `[toolchain]` table
+        let channel = toolchain_file.get_ref().get("toolchain")?.get_ref().get("channel")?;
+        Symbol::intern(channel.get_ref().as_str()?)
+    } else {
+        // The legacy format only contains the channel
+        Symbol::intern(contents.trim())
+    };
+    parse_version(channel)
+}
+
+/// Reads the `rust-version` of the manifest in `dir`, along with the span of its value. An
+/// inherited `rust-version.workspace = true` is read from the manifest of the workspace.
+pub fn manifest_rust_version(sess: &Session, dir: &Path) -> Option<(RustcVersion, Span)> {
+    with_manifest(sess, dir, |file, manifest| {
+        let rust_version = manifest.get("package")?.get_ref().get("rust-version")?;
+        match rust_version.get_ref() {
+            DeValue::String(version) => Some((parse_version(Symbol::intern(version))?, toml_span(file, rust_version))),
+            DeValue::Table(table)
+                if table.get("workspace").and_then(|value| value.get_ref().as_bool()) == Some(true) =>
+            {
+                dir.ancestors().skip(1).find_map(|dir| {
+                    with_manifest(sess, dir, |file, manifest| {
+                        let workspace = manifest.get("workspace")?;
+                        let rust_version = workspace.get_ref().get("package")?.get_ref().get("rust-version")?;
+                        Some((
+                            parse_version(Symbol::intern(rust_version.get_ref().as_str()?))?,
+                            toml_span(file, rust_version),
+                        ))
+                    })
+                })
+            },
+            _ => None,
+        }
+    })
+}
+
+/// Returns the span of the key of the dependency on the package `name` in the manifest in `dir`,
+/// including the ones renamed with `package = "name"` and the platform specific ones.
+pub fn manifest_dependency_span(sess: &Session, dir: &Path, name: &str) -> Option<Span> {
+    with_manifest(sess, dir, |file, manifest| {
+        let targets = manifest
+            .get("target")
+            .and_then(|targets| targets.get_ref().as_table())
+            .into_iter()
+            .flat_map(|targets| targets.values())
+            .filter_map(|target| target.get_ref().as_table());
+        once(manifest)
+            .chain(targets)
+            .filter_map(|table| table.get("dependencies")?.get_ref().as_table())
+            .flat_map(|dependencies| dependencies.iter())
+            .find(|(key, value)| {
+                key.get_ref() == name
+                    || value
+                        .get_ref()
+                        .as_table()
+                        .and_then(|dependency| dependency.get("package")?.get_ref().as_str())
+                        == Some(name)
+            })
+            .map(|(key, _)| toml_span(file, key))
+    })
+}
+
+fn with_manifest<R>(sess: &Session, dir: &Path, f: impl FnOnce(&SourceFile, &DeTable<'_>) -> Option<R>) -> Option<R> {
+    let file = sess.source_map().load_file(&dir.join("Cargo.toml")).ok()?;
+    let manifest = DeTable::parse(file.src.as_deref()?).ok()?;
+    f(&file, manifest.get_ref())
+}
+
+fn toml_span<T>(file: &SourceFile, value: &Spanned<T>) -> Span {
+    let range = value.span();
+    Span::new(
+        file.start_pos + BytePos::from_usize(range.start),
+        file.start_pos + BytePos::from_usize(range.end),
+        SyntaxContext::root(),
+        None,
+    )
+}
+
 fn parse_attrs(sess: &Session, attrs: &[impl AttributeExt]) -> Option<RustcVersion> {
     let mut msrv_attrs = attrs.iter().filter(|attr| attr.path_matches(&[sym::clippy, sym::msrv]));
 
@@ -5,6 +5,7 @@
 //! See <https://github.com/rust-lang/rust-clippy/issues/5393> for more information.
 
 use crate::res::MaybeQPath;
+use crate::str_utils::glob_matches;
 use crate::sym;
 use rustc_ast::Mutability;
 use rustc_data_structures::fx::FxHashMap;
@@ -15,7 +16,7 @@ use rustc_hir::{ItemKind, Node, UseKind};
 use rustc_lint::LateContext;
 use rustc_middle::ty::fast_reject::SimplifiedType;
 use rustc_middle::ty::layout::HasTyCtxt;
-use rustc_middle::ty::{FloatTy, IntTy, Ty, TyCtxt, UintTy};
+use rustc_middle::ty::{AssocItem, FloatTy, IntTy, Ty, TyCtxt, UintTy};
 use rustc_span::{Ident, STDLIB_STABLE_CRATES, Symbol};
 use std::sync::OnceLock;
 
@@ -196,6 +197,109 @@ pub fn lookup_path(tcx: TyCtxt<'_>, ns: PathNS, path: &[Symbol]) -> Vec<DefId> {
     out
 }
 
+/// Resolves a def path like [`lookup_path`], with the addition that the last segment may contain
+/// `*` wildcards, e.g. `std::process::*` or `slice::*_unchecked`, and that a segment can name the
+/// inherent impls of a primitive type the way rustdoc does, e.g. `core::slice::<impl [T]>::len`.
+///
+/// A wildcard segment matches the items directly inside of a module, type, trait or impl, so
+/// `std::*` does not match `std::process::exit`.
+///
+/// This function is expensive and should be used sparingly.
+pub fn lookup_path_glob(tcx: TyCtxt<'_>, ns: PathNS, path: &[Symbol]) -> Vec<DefId> {
+    let [ref base_path @ .., last] = *path else {
+        return Vec::new();
+    };
+    let last = last.as_str();
+    if !last.contains('*') && !base_path.iter().any(|&segment| impl_self_ty(segment).is_some()) {
+        return lookup_path(tcx, ns, path);
+    }
+
+    let mut out = Vec::new();
+    for base in lookup_glob_bases(tcx, base_path) {
+        if last.contains('*') {
+            out.extend(
+                item_children(tcx, base)
+                    .into_iter()
+                    .filter(|&(name, def_id)| {
+                        ns.matches(tcx.def_kind(def_id).ns()) && glob_matches(last, name.as_str())
+                    })
+                    .map(|(_, def_id)| def_id),
+            );
+        } else {
+            lookup_with_base(tcx, base, ns, &path[path.len() - 1..], &mut out);
+        }
+    }
+    out
+}
+
+/// Resolves the segments before the last one of a path given to [`lookup_path_glob`].
+fn lookup_glob_bases(tcx: TyCtxt<'_>, path: &[Symbol]) -> Vec<DefId> {
+    if let Some(pos) = path.iter().rposition(|&segment| impl_self_ty(segment).is_some()) {
+        let impls = impl_self_ty(path[pos]).map_or(&[][..], |ty| find_primitive_impls(tcx, ty));
+        let mut out = Vec::new();
+        for &base in impls {
+            if pos + 1 == path.len() {
+                out.push(base);
+            } else {
+                lookup_with_base(tcx, base, PathNS::Type, &path[pos + 1..], &mut out);
+            }
+        }
+        out
+    } else if let [root] = *path {
+        find_crates(tcx, root)
+            .iter()
+            .chain(find_primitive_impls(tcx, root))
+            .copied()
+            .collect()
+    } else {
+        lookup_path(tcx, PathNS::Type, path)
+    }
+}
+
+/// Returns the name of the primitive type of a rustdoc-style impl segment like `<impl [T]>`.
+fn impl_self_ty(segment: Symbol) -> Option<Symbol> {
+    let ty = segment.as_str().strip_prefix("<impl ")?.strip_suffix('>')?.trim();
+    Some(if ty.starts_with("[T;") {
+        sym::array
+    } else {
+        match ty {
+            "[T]" => sym::slice,
+            "*const T" => sym::const_ptr,
+            "*mut T" => sym::mut_ptr,
+            _ => Symbol::intern(ty),
+        }
+    })
+}
+
+/// The named items directly inside of a module, type, trait or impl.
+fn item_children(tcx: TyCtxt<'_>, def_id: DefId) -> Vec<(Symbol, DefId)> {
+    let assoc_items = |impl_def_id: DefId| {
+        tcx.associated_item_def_ids(impl_def_id)
+            .iter()
+            .map(move |&assoc_def_id| (tcx.item_name(assoc_def_id), assoc_def_id))
+    };
+    match tcx.def_kind(def_id) {
+        DefKind::Mod => {
+            let children = match def_id.as_local() {
+                Some(local_id) => tcx.module_children_local(local_id),
+                None => tcx.module_children(def_id),
+            };
+            children
+                .iter()
+                .filter(|child| def_id.is_local() || child.vis.is_public())
+                .filter_map(|child| Some((child.ident.name, child.res.opt_def_id()?)))
+                .collect()
+        },
+        DefKind::Struct | DefKind::Enum | DefKind::Union => tcx
+            .inherent_impls(def_id)
+            .iter()
+            .flat_map(|&impl_def_id| assoc_items(impl_def_id))
+            .collect(),
+        DefKind::Trait | DefKind::Impl { .. } => assoc_items(def_id).collect(),
+        _ => Vec::new(),
+    }
+}
+
 /// Finds the crates called `name`, may be multiple due to multiple major versions.
 pub fn find_crates(tcx: TyCtxt<'_>, name: Symbol) -> &'static [DefId] {
     static BY_NAME: OnceLock<FxHashMap<Symbol, Vec<DefId>>> = OnceLock::new();
@@ -274,6 +378,42 @@ fn lookup_with_base(tcx: TyCtxt<'_>, mut base: DefId, ns: PathNS, mut path: &[Sy
     }
 }
 
+/// Resolves a path to a method of a trait impl through the `Self` type of the impl, e.g.
+/// `std::fs::File::flush` for `flush` in `impl Write for File`, which [`lookup_path`] does not
+/// find as it only looks into the inherent impls of a type.
+///
+/// This function is expensive and should be used sparingly.
+pub fn lookup_trait_impl_fn(tcx: TyCtxt<'_>, path: &[Symbol]) -> Vec<DefId> {
+    let [ref ty_path @ .., name] = *path else {
+        return Vec::new();
+    };
+    lookup_path(tcx, PathNS::Type, ty_path)
+        .into_iter()
+        .filter(|&def_id| matches!(tcx.def_kind(def_id), DefKind::Struct | DefKind::Enum | DefKind::Union))
+        .flat_map(|adt| trait_impl_children(tcx, adt, name))
+        .collect()
+}
+
+/// Finds the functions called `name` in the trait impls for the ADT `adt`, e.g. `read` in
+/// `impl Read for File`. Methods which are not overridden by the impl are not found.
+fn trait_impl_children(tcx: TyCtxt<'_>, adt: DefId, name: Symbol) -> impl Iterator<Item = DefId> {
+    let self_ty = SimplifiedType::Adt(adt);
+    tcx.all_traits_including_private()
+        .filter(move |&trait_def_id| {
+            tcx.associated_items(trait_def_id)
+                .filter_by_name_unhygienic(name)
+                .any(AssocItem::is_fn)
+        })
+        .filter_map(move |trait_def_id| tcx.trait_impls_of(trait_def_id).non_blanket_impls().get(&self_ty))
+        .flatten()
+        .filter_map(move |&impl_def_id| {
+            tcx.associated_items(impl_def_id)
+                .filter_by_name_unhygienic(name)
+                .find(|item| item.is_fn())
+                .map(|item| item.def_id)
+        })
+}
+
 fn item_child_by_name(tcx: TyCtxt<'_>, def_id: DefId, ns: PathNS, name: Symbol) -> Option<DefId> {
     if let Some(local_id) = def_id.as_local() {
         local_item_child_by_name(tcx, local_id, ns, name)
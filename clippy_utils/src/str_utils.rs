@@ -289,6 +289,65 @@ pub fn to_camel_case(item_name: &str) -> String {
     s
 }
 
+/// Matches `path`, whose components are separated by `/`, against `glob`. In `glob`, `*` matches
+/// any sequence of characters within a component, `?` matches a single character and a `**`
+/// component matches any number of components.
+pub fn glob_matches(glob: &str, path: &str) -> bool {
+    wildcard_matches(
+        glob.split('/'),
+        path.split('/'),
+        |component| *component == "**",
+        |glob, component| {
+            wildcard_matches(
+                glob.chars(),
+                component.chars(),
+                |c| *c == '*',
+                |g, c| *g == '?' || g == c,
+            )
+        },
+    )
+}
+
+/// Matches `text` against `pattern`, in which the items for which `is_star` holds match any
+/// sequence of items, and the others match an item for which `matches` holds. The last star is
+/// backtracked to when the rest of the pattern does not match, which never needs to go back further
+/// as any sequence matched by an earlier star can also be matched by the last one.
+fn wildcard_matches<P: Iterator + Clone, T: Iterator + Clone>(
+    mut pattern: P,
+    mut text: T,
+    is_star: impl Fn(&P::Item) -> bool,
+    matches: impl Fn(&P::Item, &T::Item) -> bool,
+) -> bool {
+    // The pattern after the last star, and the text from which it is matched
+    let mut star: Option<(P, T)> = None;
+    loop {
+        let mut next_pattern = pattern.clone();
+        let mut next_text = text.clone();
+        match (next_pattern.next(), next_text.next()) {
+            (Some(p), _) if is_star(&p) => {
+                star = Some((next_pattern.clone(), text.clone()));
+                pattern = next_pattern;
+            },
+            (Some(p), Some(t)) if matches(&p, &t) => {
+                pattern = next_pattern;
+                text = next_text;
+            },
+            (None, None) => return true,
+            _ => {
+                // The star matches one more item
+                let Some((star_pattern, star_text)) = &mut star else {
+                    return false;
+                };
+                if star_text.next().is_none() {
+                    return false;
+                }
+                pattern = star_pattern.clone();
+                text = star_text.clone();
+            },
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -375,4 +434,33 @@ mod test {
             vec!["\u{f6}\u{f6}", "Aab", "A", "Bcd"]
         );
     }
+
+    #[test]
+    fn glob_matches_full() {
+        assert!(glob_matches("tests/**", "tests/it.rs"));
+        assert!(glob_matches("tests/**", "tests/it/main.rs"));
+        assert!(glob_matches("**/main.rs", "src/main.rs"));
+        assert!(glob_matches("**/main.rs", "main.rs"));
+        assert!(glob_matches("src/bin/*.rs", "src/bin/tool.rs"));
+        assert!(glob_matches("benches/ben?h.rs", "benches/bench.rs"));
+
+        assert!(!glob_matches("tests/**", "src/lib.rs"));
+        assert!(!glob_matches("src/bin/*.rs", "src/bin/tool/main.rs"));
+        assert!(!glob_matches("src/*.rs", "src/lib.txt"));
+        assert!(!glob_matches("benches/ben?h.rs", "benches/benh.rs"));
+    }
+
+    #[test]
+    fn glob_matches_stars() {
+        assert!(glob_matches("**/tests/**/*_it.rs", "a/tests/b/tests/c/d_it.rs"));
+        assert!(glob_matches("*a*b*", "xaybz"));
+        assert!(!glob_matches("**/tests/**/*_it.rs", "a/tests/b/c_it.txt"));
+        assert!(!glob_matches("*a*b", "xaybz"));
+
+        // Matched without backtracking to the earlier stars
+        let path = "a".repeat(64);
+        assert!(!glob_matches(&format!("{}b", "*a".repeat(32)), &path));
+        let path = vec!["a"; 64].join("/");
+        assert!(!glob_matches(&format!("{}/b", vec!["**/a"; 32].join("/")), &path));
+    }
 }
@@ -46,7 +46,9 @@ generate! {
     Binary,
     BinaryHeap,
     CLIPPY_ARGS,
+    CLIPPY_BUDGETS_RECORD,
     CLIPPY_CONF_DIR,
+    CLIPPY_PASS_TIMES,
     CRLF: "\r\n",
     Cargo_toml: "Cargo.toml",
     Child,
@@ -444,6 +446,7 @@ generate! {
     next_if_eq,
     next_multiple_of,
     next_tuple,
+    no_panic,
     nth,
     ok,
     ok_or,
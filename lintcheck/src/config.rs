@@ -28,6 +28,10 @@ pub(crate) struct LintcheckConfig {
         hide_env = true
     )]
     pub sources_toml_path: PathBuf,
+    /// Read the crates from a directory created by `lintcheck pack` or `cargo vendor` instead of
+    /// downloading them, their dependencies are read from it too
+    #[clap(long, value_name = "DIR", env = "LINTCHECK_VENDOR_DIR", hide_env = true)]
+    pub vendor_dir: Option<PathBuf>,
     /// File to save the clippy lint results here
     #[clap(skip = "")]
     pub lintcheck_results_path: PathBuf, // Overridden in new()
@@ -55,6 +59,22 @@ pub(crate) struct LintcheckConfig {
     /// `target/lintcheck/sources/<package>-<version>/perf.data`
     #[clap(long)]
     pub perf: bool,
+    /// Apply the machine-applicable suggestions of each lint separately and report those which
+    /// break `cargo check --tests` or change the public items of the crate
+    #[clap(long, conflicts_with_all(["fix", "recursive", "baseline_driver"]))]
+    pub fix_triage: bool,
+    /// Record the time Clippy spends in each lint pass, and print the slowest ones compared with
+    /// the previous run, or with the baseline driver
+    #[clap(long, conflicts_with("fix"))]
+    pub pass_times: bool,
+    /// Reduce each crate that made Clippy ICE to a reproducer, saved next to the log file
+    #[clap(long, conflicts_with_all(["fix", "recursive"]))]
+    pub reduce_ices: bool,
+    /// Also run the `clippy-driver` at this path on each crate and print a markdown diff of the
+    /// warnings of both drivers, with `--perf` the instructions spent in each lint pass are
+    /// compared too
+    #[clap(long, value_name = "PATH", conflicts_with_all(["fix", "recursive"]))]
+    pub baseline_driver: Option<PathBuf>,
     #[command(subcommand)]
     pub subcommand: Option<Commands>,
 }
@@ -72,6 +92,19 @@ pub(crate) enum Commands {
         #[clap(long, value_name = "PATH")]
         write_summary: Option<PathBuf>,
     },
+    /// Create a directory containing the crates of a crates TOML file and their dependencies, to be
+    /// used with `--vendor-dir`
+    Pack {
+        /// Output directory
+        output: PathBuf,
+        /// The crates TOML file to read the crates from
+        #[clap(
+            long = "crates-toml",
+            value_name = "CRATES-SOURCES-TOML-PATH",
+            default_value = "lintcheck/lintcheck_crates.toml"
+        )]
+        sources_toml_path: PathBuf,
+    },
     /// Create a lintcheck crates TOML file containing the top N popular crates
     Popular {
         /// Output TOML file name
@@ -87,6 +120,7 @@ pub(crate) enum OutputFormat {
     Text,
     Markdown,
     Json,
+    Html,
 }
 
 impl OutputFormat {
@@ -95,6 +129,7 @@ impl OutputFormat {
             OutputFormat::Text => "txt",
             OutputFormat::Markdown => "md",
             OutputFormat::Json => "json",
+            OutputFormat::Html => "html",
         }
     }
 }
@@ -112,6 +147,13 @@ impl LintcheckConfig {
             config.format.file_extension(),
         ));
 
+        // cargo reads the dependencies from the vendor directory in the directory of each crate
+        if let Some(vendor_dir) = &mut config.vendor_dir {
+            *vendor_dir = vendor_dir
+                .canonicalize()
+                .unwrap_or_else(|e| panic!("failed to read {}: {e}", vendor_dir.display()));
+        }
+
         // look at the --threads arg, if 0 is passed, use the threads count
         if config.max_jobs == 0 {
             config.max_jobs = if config.fix || config.recursive {
@@ -0,0 +1,366 @@
+//! HTML output for [`crate::config::OutputFormat::Html`].
+//!
+//! The report is a single self-contained page listing the warnings by lint, with a collapsible
+//! section for each crate. Each warning shows an excerpt of the extracted sources with its span
+//! highlighted, and the diff of each of its suggestions.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use cargo_metadata::diagnostic::{Diagnostic, DiagnosticSpan};
+
+use crate::lintcheck_sources;
+use crate::output::{ClippyWarning, RustcIce};
+
+/// Number of lines shown before and after the span of a warning.
+const CONTEXT_LINES: usize = 3;
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccc; padding: 0.2em 0.6em; }
+td.count { text-align: right; }
+details { margin: 0.5em 0; }
+summary { cursor: pointer; font-weight: bold; }
+.warning { border-left: 3px solid #e0a000; margin: 1em 0; padding-left: 1em; }
+pre { background: #f6f8fa; padding: 0.5em; overflow-x: auto; }
+.line-number { color: #999; user-select: none; }
+mark { background: #ffe08a; }
+.del { background: #ffebe9; }
+.ins { background: #e6ffec; }
+.hunk { color: #888; }
+";
+
+/// Creates the log file output for [`crate::config::OutputFormat::Html`]
+pub(crate) fn output(warnings: &[ClippyWarning], ices: &[RustcIce], clippy_ver: &str) -> String {
+    let mut lints: BTreeMap<&str, BTreeMap<&str, Vec<&ClippyWarning>>> = BTreeMap::new();
+    for warning in warnings {
+        lints
+            .entry(&warning.name)
+            .or_default()
+            .entry(&warning.krate)
+            .or_default()
+            .push(warning);
+    }
+
+    let mut html = format!(
+        "\
+<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<title>Lintcheck report</title>
+<style>{STYLE}</style>
+</head>
+<body>
+<h1>Lintcheck report</h1>
+<p>{}</p>
+<table>
+<tr><th>Lint</th><th>Crates</th><th>Warnings</th></tr>
+",
+        escape(clippy_ver.trim())
+    );
+    for (lint, crates) in &lints {
+        writeln!(
+            html,
+            r##"<tr><td><a href="#{}"><code>{lint}</code></a></td><td class="count">{}</td><td class="count">{}</td></tr>"##,
+            html_id(lint),
+            crates.len(),
+            crates.values().map(Vec::len).sum::<usize>()
+        )
+        .unwrap();
+    }
+    html.push_str("</table>\n");
+
+    let mut sources = HashMap::new();
+    for (lint, crates) in lints {
+        writeln!(html, r#"<h2 id="{}"><code>{lint}</code></h2>"#, html_id(lint)).unwrap();
+        for (krate, mut warnings) in crates {
+            warnings.sort_by_key(|warning| {
+                let span = warning.span();
+                (&span.file_name, span.line_start, span.column_start)
+            });
+            writeln!(
+                html,
+                "<details>\n<summary>{} ({})</summary>",
+                escape(krate),
+                warnings.len()
+            )
+            .unwrap();
+            for warning in warnings {
+                write_warning(&mut html, warning, &mut sources);
+            }
+            html.push_str("</details>\n");
+        }
+    }
+
+    if !ices.is_empty() {
+        html.push_str("<h2 id=\"ices\">ICEs</h2>\n");
+        for ice in ices {
+            writeln!(
+                html,
+                "<details>\n<summary>{}</summary>\n<pre>{}</pre>\n</details>",
+                escape(&ice.crate_name),
+                escape(&ice.ice_content)
+            )
+            .unwrap();
+        }
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn write_warning(html: &mut String, warning: &ClippyWarning, sources: &mut HashMap<String, Option<String>>) {
+    let span = warning.span();
+    writeln!(
+        html,
+        r#"<div class="warning">
+<p><a href="{}"><code>{}:{}</code></a>: {}</p>"#,
+        escape(&warning.url),
+        escape(&span.file_name),
+        span.line_start,
+        escape(&warning.diag.message)
+    )
+    .unwrap();
+
+    html.push_str("<pre>");
+    for ExcerptLine {
+        number,
+        line,
+        highlight,
+    } in excerpt(span, sources)
+    {
+        write!(html, r#"<span class="line-number">{number:>5} </span>"#).unwrap();
+        match highlight {
+            Some((start, end)) => {
+                let start = char_index(&line, start);
+                let end = char_index(&line, end).max(start);
+                write!(
+                    html,
+                    "{}<mark>{}</mark>{}",
+                    escape(&line[..start]),
+                    escape(&line[start..end]),
+                    escape(&line[end..])
+                )
+                .unwrap();
+            },
+            None => html.push_str(&escape(&line)),
+        }
+        html.push('\n');
+    }
+    html.push_str("</pre>\n");
+
+    for child in &warning.diag.children {
+        let hunks = suggestion_hunks(child);
+        if hunks.is_empty() {
+            continue;
+        }
+        writeln!(html, "<p>{}</p>\n<pre>", escape(&child.message)).unwrap();
+        for (file, line_start, old, new) in hunks {
+            writeln!(html, r#"<span class="hunk">@@ {}:{line_start} @@</span>"#, escape(file)).unwrap();
+            for change in diff::slice(&old, &new) {
+                let (class, prefix, line) = match change {
+                    diff::Result::Both(line, _) => ("", ' ', line),
+                    diff::Result::Left(line) => ("del", '-', line),
+                    diff::Result::Right(line) => ("ins", '+', line),
+                };
+                writeln!(html, r#"<span class="{class}">{prefix}{}</span>"#, escape(line)).unwrap();
+            }
+        }
+        html.push_str("</pre>\n");
+    }
+
+    if let Some(rendered) = &warning.diag.rendered {
+        writeln!(
+            html,
+            "<details>\n<summary>Diagnostic</summary>\n<pre>{}</pre>\n</details>",
+            escape(rendered.trim_end())
+        )
+        .unwrap();
+    }
+    html.push_str("</div>\n");
+}
+
+/// A line of the source excerpt of a warning.
+struct ExcerptLine {
+    number: usize,
+    line: String,
+    /// The 1-based columns of the span of the warning in the line, if any.
+    highlight: Option<(usize, usize)>,
+}
+
+/// Returns the lines of the source around `span`. The lines of the span in the diagnostic are
+/// used when the file cannot be read.
+fn excerpt(span: &DiagnosticSpan, sources: &mut HashMap<String, Option<String>>) -> Vec<ExcerptLine> {
+    let source = sources.entry(span.file_name.clone()).or_insert_with(|| {
+        let file = Path::new(&span.file_name);
+        fs::read_to_string(file)
+            .or_else(|_| fs::read_to_string(Path::new(&lintcheck_sources()).join(file)))
+            .ok()
+    });
+
+    match source {
+        Some(source) if source.lines().count() >= span.line_end => {
+            let first = span.line_start.saturating_sub(CONTEXT_LINES).max(1);
+            source
+                .lines()
+                .enumerate()
+                .map(|(i, line)| (i + 1, line))
+                .skip(first - 1)
+                .take_while(|&(number, _)| number <= span.line_end + CONTEXT_LINES)
+                .map(|(number, line)| {
+                    let start = if number == span.line_start {
+                        span.column_start
+                    } else {
+                        1
+                    };
+                    let end = if number == span.line_end {
+                        span.column_end
+                    } else {
+                        usize::MAX
+                    };
+                    ExcerptLine {
+                        number,
+                        line: line.to_string(),
+                        highlight: (span.line_start..=span.line_end)
+                            .contains(&number)
+                            .then_some((start, end)),
+                    }
+                })
+                .collect()
+        },
+        _ => span
+            .text
+            .iter()
+            .enumerate()
+            .map(|(i, line)| ExcerptLine {
+                number: span.line_start + i,
+                line: line.text.clone(),
+                highlight: Some((line.highlight_start, line.highlight_end)),
+            })
+            .collect(),
+    }
+}
+
+/// Returns the suggested replacements of `child` grouped by the lines they change, as the file and
+/// first line of each group, with its lines before and after the replacements.
+fn suggestion_hunks(child: &Diagnostic) -> Vec<(&str, usize, Vec<String>, Vec<String>)> {
+    let mut spans: Vec<&DiagnosticSpan> = child
+        .spans
+        .iter()
+        .filter(|span| span.suggested_replacement.is_some() && !span.text.is_empty())
+        .collect();
+    spans.sort_by_key(|span| (&span.file_name, span.line_start, span.column_start));
+
+    let mut hunks = Vec::new();
+    for group in spans.chunk_by(|a, b| a.file_name == b.file_name && b.line_start <= a.line_end + 1) {
+        let line_start = group[0].line_start;
+        let mut lines: BTreeMap<usize, &str> = BTreeMap::new();
+        for span in group {
+            for (i, line) in span.text.iter().enumerate() {
+                lines.insert(span.line_start + i, &line.text);
+            }
+        }
+        let old: Vec<String> = lines.values().map(|&line| line.to_string()).collect();
+
+        // The byte offset of a 1-based line and column in the text of the group
+        let offset = |line: usize, column: usize| {
+            lines.range(..line).map(|(_, text)| text.len() + 1).sum::<usize>()
+                + lines.get(&line).map_or(0, |text| char_index(text, column))
+        };
+        let mut text = old.join("\n");
+        let mut end = usize::MAX;
+        // Applied back to front so the offsets of the remaining ones stay valid
+        for span in group.iter().rev() {
+            let start = offset(span.line_start, span.column_start);
+            let span_end = offset(span.line_end, span.column_end);
+            if start <= span_end && span_end <= end && span_end <= text.len() {
+                text.replace_range(start..span_end, span.suggested_replacement.as_deref().unwrap());
+                end = start;
+            }
+        }
+        let new = text.split('\n').map(ToString::to_string).collect();
+
+        hunks.push((group[0].file_name.as_str(), line_start, old, new));
+    }
+    hunks
+}
+
+/// Returns the byte index of the 1-based char `column` of `line`, or its length if it is shorter.
+fn char_index(line: &str, column: usize) -> usize {
+    line.char_indices()
+        .nth(column.saturating_sub(1))
+        .map_or(line.len(), |(i, _)| i)
+}
+
+fn html_id(lint: &str) -> String {
+    lint.replace("::", "-")
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[test]
+fn suggestion_diff() {
+    let span = |line_start: usize, line_end: usize, columns: (usize, usize), text: &[&str], replacement: &str| {
+        serde_json::json!({
+            "file_name": "src/lib.rs",
+            "byte_start": 0,
+            "byte_end": 0,
+            "line_start": line_start,
+            "line_end": line_end,
+            "column_start": columns.0,
+            "column_end": columns.1,
+            "is_primary": true,
+            "text": text.iter().map(|text| serde_json::json!({
+                "text": text,
+                "highlight_start": 1,
+                "highlight_end": 1,
+            })).collect::<Vec<_>>(),
+            "suggested_replacement": replacement,
+        })
+    };
+    let child: Diagnostic = serde_json::from_value(serde_json::json!({
+        "message": "try",
+        "level": "help",
+        "spans": [
+            span(2, 2, (13, 20), &["    let x = vec![1];"], "[1]"),
+            span(1, 1, (8, 15), &["    if x.len() == 0 {"], "x.is_empty()"),
+            span(20, 21, (5, 2), &["    foo(", ")"], "bar()"),
+        ],
+        "children": [],
+    }))
+    .unwrap();
+
+    assert_eq!(
+        suggestion_hunks(&child),
+        vec![
+            (
+                "src/lib.rs",
+                1,
+                vec!["    if x.len() == 0 {".to_string(), "    let x = vec![1];".to_string()],
+                vec!["    if x.is_empty() == 0 {".to_string(), "    let x = [1];".to_string()],
+            ),
+            (
+                "src/lib.rs",
+                20,
+                vec!["    foo(".to_string(), ")".to_string()],
+                vec!["    bar()".to_string()]
+            ),
+        ]
+    );
+}
@@ -1,6 +1,7 @@
 use std::collections::{HashMap, HashSet};
 use std::fs::{self};
-use std::io::{self, ErrorKind};
+use std::io::{self, ErrorKind, Write as _};
+use std::mem;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::Duration;
@@ -37,6 +38,11 @@ struct TomlCrate {
     git_hash: Option<String>,
     path: Option<String>,
     options: Option<Vec<String>>,
+    /// The sets of features to check the crate with, each one in a separate run with
+    /// `--no-default-features`, e.g. `[["default"], [], ["serde", "std"]]`
+    features: Option<Vec<Vec<String>>>,
+    /// Globs of the workspace members to check instead of the root package, e.g. `["crates/*"]`
+    members: Option<Vec<String>>,
     /// Magic values:
     /// * `{krate}` will be replaced by `self.name`
     /// * `{krate_}` will be replaced by `self.name` with all `-` replaced by `_`
@@ -82,17 +88,95 @@ pub struct CrateWithSource {
     pub source: CrateSource,
     pub file_link: String,
     pub options: Option<Vec<String>>,
+    pub features: Option<Vec<Vec<String>>>,
+    pub members: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize, Eq, Hash, PartialEq, Ord, PartialOrd)]
 pub enum CrateSource {
-    CratesIo { version: String },
-    Git { url: String, commit: String },
-    Path { path: PathBuf },
+    CratesIo {
+        version: String,
+    },
+    Git {
+        url: String,
+        commit: String,
+    },
+    Path {
+        path: PathBuf,
+    },
+    /// A `.crate` file or a directory of a vendor directory, replacing a crates.io or git source
+    Vendor {
+        path: PathBuf,
+        version: String,
+    },
 }
 
-/// Read a `lintcheck_crates.toml` file
-pub fn read_crates(toml_path: &Path) -> (Vec<CrateWithSource>, RecursiveOptions) {
+impl CrateSource {
+    /// Replaces a crates.io or git source of the crate `name` by its copy in `vendor_dir`, which
+    /// was created by `lintcheck pack` or `cargo vendor`.
+    ///
+    /// A crates.io source is read from `<name>-<version>.crate`, `<name>-<version>/` or
+    /// `<name>/`, and a git source from `git/<name>-<commit>/`.
+    fn vendored(self, name: &str, vendor_dir: &Path) -> Self {
+        let (version, path) = match self {
+            CrateSource::CratesIo { version } => {
+                let path = [
+                    vendor_dir.join(format!("{name}-{version}.crate")),
+                    vendor_dir.join(format!("{name}-{version}")),
+                ]
+                .into_iter()
+                .find(|path| path.exists())
+                .or_else(|| {
+                    let path = vendor_dir.join(name);
+                    (package_version(&path).as_ref() == Some(&version)).then_some(path)
+                });
+                (version, path)
+            },
+            CrateSource::Git { commit, .. } => {
+                let path = vendor_dir.join("git").join(format!("{name}-{commit}"));
+                (commit, path.is_dir().then_some(path))
+            },
+            CrateSource::Path { .. } | CrateSource::Vendor { .. } => return self,
+        };
+        let path = path.unwrap_or_else(|| panic!("{name} {version} not found in {}", vendor_dir.display()));
+        CrateSource::Vendor { path, version }
+    }
+}
+
+/// Returns the version of the package whose manifest is in `dir`.
+fn package_version(dir: &Path) -> Option<String> {
+    let manifest: toml::Table = toml::from_str(&fs::read_to_string(dir.join("Cargo.toml")).ok()?).ok()?;
+    Some(manifest.get("package")?.get("version")?.as_str()?.to_string())
+}
+
+/// Returns the features of the package whose manifest is in `dir`, including its optional
+/// dependencies.
+pub(crate) fn package_features(dir: &Path) -> HashSet<String> {
+    let Some(manifest) = fs::read_to_string(dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|manifest| toml::from_str::<toml::Table>(&manifest).ok())
+    else {
+        return HashSet::new();
+    };
+    let features = manifest
+        .get("features")
+        .and_then(toml::Value::as_table)
+        .into_iter()
+        .flatten();
+    let optional_dependencies = ["dependencies", "build-dependencies"]
+        .iter()
+        .filter_map(|table| manifest.get(*table)?.as_table())
+        .flatten()
+        .filter(|(_, dependency)| dependency.get("optional").and_then(toml::Value::as_bool) == Some(true));
+    features
+        .chain(optional_dependencies)
+        .map(|(name, _)| name.clone())
+        .collect()
+}
+
+/// Read a `lintcheck_crates.toml` file, the crates.io and git sources are replaced by their copy in
+/// `vendor_dir` if any
+pub fn read_crates(toml_path: &Path, vendor_dir: Option<&Path>) -> (Vec<CrateWithSource>, RecursiveOptions) {
     let toml_content: String =
         fs::read_to_string(toml_path).unwrap_or_else(|_| panic!("Failed to read {}", toml_path.display()));
     let crate_list: SourceList =
@@ -112,6 +196,8 @@ pub fn read_crates(toml_path: &Path) -> (Vec<CrateWithSource>, RecursiveOptions)
                 },
                 file_link: tk.file_link(DEFAULT_PATH_LINK),
                 options: tk.options.clone(),
+                features: tk.features.clone(),
+                members: tk.members.clone(),
             });
         } else if let Some(ref version) = tk.version {
             crate_sources.push(CrateWithSource {
@@ -121,6 +207,8 @@ pub fn read_crates(toml_path: &Path) -> (Vec<CrateWithSource>, RecursiveOptions)
                 },
                 file_link: tk.file_link(DEFAULT_DOCS_LINK),
                 options: tk.options.clone(),
+                features: tk.features.clone(),
+                members: tk.members.clone(),
             });
         } else if tk.git_url.is_some() && tk.git_hash.is_some() {
             // otherwise, we should have a git source
@@ -132,6 +220,8 @@ pub fn read_crates(toml_path: &Path) -> (Vec<CrateWithSource>, RecursiveOptions)
                 },
                 file_link: tk.file_link(DEFAULT_GITHUB_LINK),
                 options: tk.options.clone(),
+                features: tk.features.clone(),
+                members: tk.members.clone(),
             });
         } else {
             panic!("Invalid crate source: {tk:?}");
@@ -154,6 +244,12 @@ pub fn read_crates(toml_path: &Path) -> (Vec<CrateWithSource>, RecursiveOptions)
             unreachable!("Failed to translate TomlCrate into CrateSource!");
         }
     }
+    if let Some(vendor_dir) = vendor_dir {
+        for krate in &mut crate_sources {
+            let source = mem::replace(&mut krate.source, CrateSource::Path { path: PathBuf::new() });
+            krate.source = source.vendored(&krate.name, vendor_dir);
+        }
+    }
     // sort the crates
     crate_sources.sort();
 
@@ -198,20 +294,19 @@ impl CrateWithSource {
         }
         let name = &self.name;
         let options = &self.options;
+        let features = &self.features;
+        let members = &self.members;
         let file_link = &self.file_link;
         match &self.source {
             CrateSource::CratesIo { version } => {
                 let extract_dir = PathBuf::from(lintcheck_sources());
-                // Keep constant downloads path to avoid repeating work and
-                // filling up disk space unnecessarily.
-                let krate_download_dir = PathBuf::from("target/lintcheck/downloads/");
+                let krate_file_path = crate_download_path(name, version);
 
                 // url to download the crate from crates.io
                 let url = format!("https://crates.io/api/v1/crates/{name}/{version}/download");
                 println!("Downloading and extracting {name} {version} from {url}");
-                create_dirs(&krate_download_dir, &extract_dir);
+                create_dirs(krate_file_path.parent().unwrap(), &extract_dir);
 
-                let krate_file_path = krate_download_dir.join(format!("{name}-{version}.crate.tar.gz"));
                 // don't download/extract if we already have done so
                 if !krate_file_path.is_file() || !extract_dir.join(format!("{name}-{version}")).exists() {
                     // create a file path to download and write the crate data into
@@ -233,6 +328,8 @@ impl CrateWithSource {
                     name: name.clone(),
                     path: extract_dir.join(format!("{name}-{version}/")),
                     options: options.clone(),
+                    features: features.clone(),
+                    members: members.clone(),
                     base_url: file_link.clone(),
                 }
             },
@@ -275,45 +372,53 @@ impl CrateWithSource {
                     name: name.clone(),
                     path: repo_path,
                     options: options.clone(),
+                    features: features.clone(),
+                    members: members.clone(),
                     base_url: file_link.clone(),
                 }
             },
             CrateSource::Path { path } => {
-                fn is_cache_dir(entry: &DirEntry) -> bool {
-                    fs::read(entry.path().join("CACHEDIR.TAG"))
-                        .is_ok_and(|x| x.starts_with(b"Signature: 8a477f597d28d172789f06886806bc55"))
-                }
-
-                // copy path into the dest_crate_root but skip directories that contain a CACHEDIR.TAG file.
-                // The target/ directory contains a CACHEDIR.TAG file so it is the most commonly skipped directory
-                // as a result of this filter.
                 let dest_crate_root = PathBuf::from(lintcheck_sources()).join(name);
                 if dest_crate_root.exists() {
                     println!("Deleting existing directory at `{}`", dest_crate_root.display());
-                    fs::remove_dir_all(&dest_crate_root).unwrap();
                 }
 
                 println!("Copying `{}` to `{}`", path.display(), dest_crate_root.display());
+                copy_dir(path, &dest_crate_root);
 
-                for entry in WalkDir::new(path).into_iter().filter_entry(|e| !is_cache_dir(e)) {
-                    let entry = entry.unwrap();
-                    let entry_path = entry.path();
-                    let relative_entry_path = entry_path.strip_prefix(path).unwrap();
-                    let dest_path = dest_crate_root.join(relative_entry_path);
-                    let metadata = entry_path.symlink_metadata().unwrap();
-
-                    if metadata.is_dir() {
-                        fs::create_dir(dest_path).unwrap();
-                    } else if metadata.is_file() {
-                        fs::copy(entry_path, dest_path).unwrap();
+                Crate {
+                    version: String::from("local"),
+                    name: name.clone(),
+                    path: dest_crate_root,
+                    options: options.clone(),
+                    features: features.clone(),
+                    members: members.clone(),
+                    base_url: file_link.clone(),
+                }
+            },
+            CrateSource::Vendor { path, version } => {
+                let extract_dir = PathBuf::from(lintcheck_sources());
+                let dest_crate_root = extract_dir.join(format!("{name}-{version}"));
+                println!("Extracting {name} {version} from `{}`", path.display());
+                if path.is_file() {
+                    if dest_crate_root.exists() {
+                        fs::remove_dir_all(&dest_crate_root).unwrap();
                     }
+                    fs::create_dir_all(&extract_dir).unwrap();
+                    let ungz_tar = flate2::read::GzDecoder::new(fs::File::open(path).unwrap());
+                    let mut archive = tar::Archive::new(ungz_tar);
+                    archive.unpack(&extract_dir).expect("Failed to extract!");
+                } else {
+                    copy_dir(path, &dest_crate_root);
                 }
 
                 Crate {
-                    version: String::from("local"),
+                    version: version.clone(),
                     name: name.clone(),
                     path: dest_crate_root,
                     options: options.clone(),
+                    features: features.clone(),
+                    members: members.clone(),
                     base_url: file_link.clone(),
                 }
             },
@@ -321,6 +426,119 @@ impl CrateWithSource {
     }
 }
 
+/// Returns the path the `.crate` file of a crates.io source is downloaded to.
+pub(crate) fn crate_download_path(name: &str, version: &str) -> PathBuf {
+    // Keep constant downloads path to avoid repeating work and
+    // filling up disk space unnecessarily.
+    PathBuf::from(format!("target/lintcheck/downloads/{name}-{version}.crate.tar.gz"))
+}
+
+/// Makes cargo read the dependencies of the crate at `path` from the absolute path `vendor_dir`,
+/// which was created by `lintcheck pack` or `cargo vendor`.
+pub(crate) fn use_vendored_dependencies(path: &Path, vendor_dir: &Path) {
+    let config_path = path.join(".cargo/config.toml");
+    let config = fs::read_to_string(&config_path).unwrap_or_default();
+    if config.contains("[source.lintcheck-vendor]") {
+        return;
+    }
+    fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&config_path)
+        .unwrap();
+    write!(
+        file,
+        "\n[source.crates-io]\nreplace-with = \"lintcheck-vendor\"\n\n[source.lintcheck-vendor]\ndirectory = {:?}\n",
+        vendor_dir.display().to_string()
+    )
+    .unwrap();
+}
+
+/// Returns the directories of the packages in `root` matching the `globs`, in which `*` matches
+/// any part of a file name. A glob matching `root` itself is `.`.
+pub(crate) fn workspace_members(root: &Path, globs: &[String]) -> Vec<PathBuf> {
+    let mut members = Vec::new();
+    for glob in globs {
+        let mut dirs = vec![root.to_path_buf()];
+        for component in glob
+            .split('/')
+            .filter(|component| !component.is_empty() && *component != ".")
+        {
+            dirs = dirs
+                .iter()
+                .flat_map(|dir| {
+                    if component.contains('*') {
+                        fs::read_dir(dir)
+                            .into_iter()
+                            .flatten()
+                            .filter_map(Result::ok)
+                            .filter(|entry| wildcard_match(component, &entry.file_name().to_string_lossy()))
+                            .map(|entry| entry.path())
+                            .collect()
+                    } else {
+                        vec![dir.join(component)]
+                    }
+                })
+                .collect();
+        }
+        let mut matched: Vec<PathBuf> = dirs
+            .into_iter()
+            .filter(|dir| dir.join("Cargo.toml").is_file())
+            .collect();
+        if matched.is_empty() {
+            eprintln!("WARNING: no package in {} matches `{glob}`", root.display());
+        }
+        members.append(&mut matched);
+    }
+    members.sort();
+    members.dedup();
+    members
+}
+
+/// Checks if `name` matches `pattern`, in which `*` matches any sequence of characters.
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == name,
+        Some((prefix, rest)) => {
+            let Some(name) = name.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=name.len())
+                .filter(|&i| name.is_char_boundary(i))
+                .any(|i| wildcard_match(rest, &name[i..]))
+        },
+    }
+}
+
+/// Replaces the directory `dest` with a copy of `src`, but skips directories that contain a
+/// CACHEDIR.TAG file. The target/ directory contains a CACHEDIR.TAG file so it is the most commonly
+/// skipped directory as a result of this filter.
+pub(crate) fn copy_dir(src: &Path, dest: &Path) {
+    fn is_cache_dir(entry: &DirEntry) -> bool {
+        fs::read(entry.path().join("CACHEDIR.TAG"))
+            .is_ok_and(|x| x.starts_with(b"Signature: 8a477f597d28d172789f06886806bc55"))
+    }
+
+    if dest.exists() {
+        fs::remove_dir_all(dest).unwrap();
+    }
+
+    for entry in WalkDir::new(src).into_iter().filter_entry(|e| !is_cache_dir(e)) {
+        let entry = entry.unwrap();
+        let entry_path = entry.path();
+        let relative_entry_path = entry_path.strip_prefix(src).unwrap();
+        let dest_path = dest.join(relative_entry_path);
+        let metadata = entry_path.symlink_metadata().unwrap();
+
+        if metadata.is_dir() {
+            fs::create_dir_all(dest_path).unwrap();
+        } else if metadata.is_file() {
+            fs::copy(entry_path, dest_path).unwrap();
+        }
+    }
+}
+
 /// Create necessary directories to run the lintcheck tool.
 ///
 /// # Panics
@@ -346,3 +564,13 @@ fn create_dirs(krate_download_dir: &Path, extract_dir: &Path) {
         );
     });
 }
+
+#[test]
+fn wildcard() {
+    assert!(wildcard_match("*", "serde"));
+    assert!(wildcard_match("serde*", "serde_derive"));
+    assert!(wildcard_match("*-core", "tokio-core"));
+    assert!(wildcard_match("a*b*c", "axxbyyc"));
+    assert!(!wildcard_match("serde*", "ser"));
+    assert!(!wildcard_match("*-core", "tokio-util"));
+}
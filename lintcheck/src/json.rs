@@ -11,6 +11,7 @@ use itertools::{EitherOrBoth, Itertools as _};
 use serde::{Deserialize, Serialize};
 
 use crate::ClippyWarning;
+use crate::diagnostic_key::DiagnosticKey;
 
 /// This is the total number. 300 warnings results in 100 messages per section.
 const DEFAULT_LIMIT_PER_LINT: usize = 300;
@@ -19,8 +20,10 @@ const TRUNCATION_TOTAL_TARGET: usize = 1000;
 
 #[derive(Debug, Deserialize, Serialize)]
 struct LintJson {
-    /// The lint name e.g. `clippy::bytes_nth`
-    name: String,
+    /// The lint name e.g. `clippy::bytes_nth`, the filename e.g. `anyhow-1.0.86/src/error.rs` and
+    /// the message, which identify the warning across runs as in the baselines of `cargo clippy`
+    #[serde(flatten)]
+    key: DiagnosticKey,
     /// The filename and line number e.g. `anyhow-1.0.86/src/error.rs:42`
     file_line: String,
     file_url: String,
@@ -28,13 +31,12 @@ struct LintJson {
 }
 
 impl LintJson {
-    fn key(&self) -> impl Ord + '_ {
-        (self.name.as_str(), self.file_line.as_str())
-    }
-
     /// Formats the warning information with an action verb for display.
     fn info_text(&self, action: &str) -> String {
-        format!("{action} `{}` at [`{}`]({})", self.name, self.file_line, self.file_url)
+        format!(
+            "{action} `{}` at [`{}`]({})",
+            self.key.lint, self.file_line, self.file_url
+        )
     }
 }
 
@@ -101,14 +103,14 @@ pub(crate) fn output(clippy_warnings: Vec<ClippyWarning>) -> String {
                 .unwrap_or(&span.file_name);
             let file_line = format!("{file_name}:{}", span.line_start);
             LintJson {
-                name: warning.name,
+                key: DiagnosticKey::new(&warning.name, file_name, &warning.diag.message),
                 file_line,
                 file_url: warning.url,
                 rendered: warning.diag.rendered.unwrap().trim().to_string(),
             }
         })
         .collect();
-    lints.sort_by(|a, b| a.key().cmp(&b.key()));
+    lints.sort_by(|a, b| (&a.key, &a.file_line).cmp(&(&b.key, &b.file_line)));
     serde_json::to_string(&lints).unwrap()
 }
 
@@ -129,8 +131,8 @@ pub(crate) fn diff(old_path: &Path, new_path: &Path, truncate: bool, write_summa
 
     let mut lint_warnings = vec![];
 
-    for (name, changes) in &itertools::merge_join_by(old_warnings, new_warnings, |old, new| old.key().cmp(&new.key()))
-        .chunk_by(|change| change.as_ref().into_left().name.clone())
+    for (name, changes) in &itertools::merge_join_by(old_warnings, new_warnings, |old, new| old.key.cmp(&new.key))
+        .chunk_by(|change| change.as_ref().into_left().key.lint.clone())
     {
         let mut added = Vec::new();
         let mut removed = Vec::new();
@@ -219,7 +221,7 @@ fn print_warnings(title: &str, warnings: &[LintJson], truncate_after: usize) {
         return;
     }
 
-    print_h3(&warnings[0].name, title);
+    print_h3(&warnings[0].key.lint, title);
     println!();
 
     let warnings = truncate(warnings, truncate_after);
@@ -240,7 +242,7 @@ fn print_changed_diff(changed: &[(LintJson, LintJson)], truncate_after: usize) {
         return;
     }
 
-    print_h3(&changed[0].0.name, "Changed");
+    print_h3(&changed[0].0.key.lint, "Changed");
     println!();
 
     let changed = truncate(changed, truncate_after);
@@ -21,16 +21,25 @@
 )]
 
 mod config;
+#[path = "../../clippy_utils/src/diagnostic_key.rs"]
+mod diagnostic_key;
 mod driver;
+mod html;
 mod input;
 mod json;
 mod output;
+mod pack;
+mod pass_times;
+mod perf;
 mod popular_crates;
 mod recursive;
+mod reduce;
+mod triage;
 
 use crate::config::{Commands, LintcheckConfig, OutputFormat};
 use crate::recursive::LintcheckServer;
 
+use std::collections::{BTreeMap, HashSet};
 use std::env::consts::EXE_SUFFIX;
 use std::io::{self};
 use std::path::{Path, PathBuf};
@@ -60,13 +69,16 @@ struct Crate {
     // path to the extracted sources that clippy can check
     path: PathBuf,
     options: Option<Vec<String>>,
+    // the feature sets and workspace members to check, see `input::TomlCrate`
+    features: Option<Vec<Vec<String>>>,
+    members: Option<Vec<String>>,
     base_url: String,
 }
 
 impl Crate {
     /// Run `cargo clippy` on the `Crate` and collect and return all the lint warnings that clippy
     /// issued
-    #[expect(clippy::too_many_lines)]
+    #[expect(clippy::too_many_arguments, clippy::too_many_lines)]
     fn run_clippy_lints(
         &self,
         clippy_driver_path: &Path,
@@ -75,6 +87,7 @@ impl Crate {
         config: &LintcheckConfig,
         lint_levels_args: &[String],
         server: Option<&LintcheckServer>,
+        qualifier: &str,
     ) -> Vec<ClippyCheckOutput> {
         // advance the atomic index by one
         let index = target_dir_index.fetch_add(1, Ordering::SeqCst);
@@ -121,122 +134,198 @@ impl Crate {
 
         clippy_args.extend(lint_levels_args.iter().map(String::as_str));
 
-        let mut cmd;
-
-        if config.perf {
-            cmd = Command::new("perf");
-            let perf_data_filename = get_perf_data_filename(&self.path);
-            cmd.args(&[
-                "record",
-                "-e",
-                "instructions", // Only count instructions
-                "-g",           // Enable call-graph, useful for flamegraphs and produces richer reports
-                "--quiet",      // Do not tamper with lintcheck's normal output
-                "--compression-level=22",
-                "--freq=3000", // Slow down program to capture all events
-                "-o",
-                &perf_data_filename,
-                "--",
-                "cargo",
-            ]);
-        } else {
-            cmd = Command::new("cargo");
-        }
+        let configurations = self.configurations();
+        let mut entries: Vec<ClippyCheckOutput> = Vec::new();
+        for (index, (dir, cargo_args)) in configurations.iter().enumerate() {
+            if configurations.len() > 1 {
+                println!(
+                    "Linting {} {} in `{}` with `{}`",
+                    self.name,
+                    self.version,
+                    dir.display(),
+                    cargo_args.join(" ")
+                );
+            }
 
-        cmd.arg(if config.fix { "fix" } else { "check" })
-            .arg("--quiet")
-            .current_dir(&self.path)
-            .env("CLIPPY_ARGS", clippy_args.join("__CLIPPY_HACKERY__"))
-            .env("CLIPPY_DISABLE_DOCS_LINKS", "1");
-
-        if let Some(server) = server {
-            // `cargo clippy` is a wrapper around `cargo check` that mainly sets `RUSTC_WORKSPACE_WRAPPER` to
-            // `clippy-driver`. We do the same thing here with a couple changes:
-            //
-            // `RUSTC_WRAPPER` is used instead of `RUSTC_WORKSPACE_WRAPPER` so that we can lint all crate
-            // dependencies rather than only workspace members
-            //
-            // The wrapper is set to `lintcheck` itself so we can force enable linting and ignore certain crates
-            // (see `crate::driver`)
-            let status = cmd
-                .env("CARGO_TARGET_DIR", shared_target_dir("recursive"))
-                .env("RUSTC_WRAPPER", env::current_exe().unwrap())
-                // Pass the absolute path so `crate::driver` can find `clippy-driver`, as it's executed in various
-                // different working directories
-                .env("CLIPPY_DRIVER", clippy_driver_path)
-                .env("LINTCHECK_SERVER", server.local_addr.to_string())
-                .status()
-                .expect("failed to run cargo");
-
-            assert_eq!(status.code(), Some(0));
-
-            return Vec::new();
-        }
+            let mut cmd;
+
+            if config.perf {
+                cmd = Command::new("perf");
+                cmd.args([
+                    "record",
+                    "-e",
+                    "instructions", // Only count instructions
+                    "-g",           // Enable call-graph, useful for flamegraphs and produces richer reports
+                    "--quiet",      // Do not tamper with lintcheck's normal output
+                    "--compression-level=22",
+                    "--freq=3000", // Slow down program to capture all events
+                    "-o",
+                ])
+                .arg(self.perf_data_path(qualifier, index))
+                .args(["--", "cargo"]);
+            } else {
+                cmd = Command::new("cargo");
+            }
 
-        if !config.fix && !config.perf {
-            cmd.arg("--message-format=json");
-        }
+            cmd.arg(if config.fix { "fix" } else { "check" })
+                .arg("--quiet")
+                .args(cargo_args)
+                .current_dir(dir)
+                .env("CLIPPY_ARGS", clippy_args.join("__CLIPPY_HACKERY__"))
+                .env("CLIPPY_DISABLE_DOCS_LINKS", "1");
 
-        let shared_target_dir = shared_target_dir(&format!("_{thread_index:?}"));
-        let all_output = cmd
-            // use the looping index to create individual target dirs
-            .env("CARGO_TARGET_DIR", shared_target_dir.as_os_str())
-            // Roughly equivalent to `cargo clippy`/`cargo clippy --fix`
-            .env("RUSTC_WORKSPACE_WRAPPER", clippy_driver_path)
-            .output()
-            .unwrap();
-        let stdout = String::from_utf8_lossy(&all_output.stdout);
-        let stderr = String::from_utf8_lossy(&all_output.stderr);
-        let status = &all_output.status;
-
-        if !status.success() {
-            eprintln!(
-                "\nWARNING: bad exit status after checking {} {} \n",
-                self.name, self.version
-            );
-        }
+            if config.pass_times {
+                cmd.env("CLIPPY_PASS_TIMES", pass_times::dir(self, qualifier));
+            }
 
-        if config.fix {
-            if let Some(stderr) = stderr
-                .lines()
-                .find(|line| line.contains("failed to automatically apply fixes suggested by rustc to crate"))
-            {
-                let subcrate = &stderr[63..];
-                println!(
-                    "ERROR: failed to apply some suggestion to {} / to (sub)crate {subcrate}",
-                    self.name
+            if let Some(server) = server {
+                // `cargo clippy` is a wrapper around `cargo check` that mainly sets `RUSTC_WORKSPACE_WRAPPER` to
+                // `clippy-driver`. We do the same thing here with a couple changes:
+                //
+                // `RUSTC_WRAPPER` is used instead of `RUSTC_WORKSPACE_WRAPPER` so that we can lint all crate
+                // dependencies rather than only workspace members
+                //
+                // The wrapper is set to `lintcheck` itself so we can force enable linting and ignore certain crates
+                // (see `crate::driver`)
+                let status = cmd
+                    .env("CARGO_TARGET_DIR", shared_target_dir("recursive"))
+                    .env("RUSTC_WRAPPER", env::current_exe().unwrap())
+                    // Pass the absolute path so `crate::driver` can find `clippy-driver`, as it's executed in various
+                    // different working directories
+                    .env("CLIPPY_DRIVER", clippy_driver_path)
+                    .env("LINTCHECK_SERVER", server.local_addr.to_string())
+                    .status()
+                    .expect("failed to run cargo");
+
+                assert_eq!(status.code(), Some(0));
+
+                continue;
+            }
+
+            if !config.fix && (!config.perf || config.baseline_driver.is_some()) {
+                cmd.arg("--message-format=json");
+            }
+
+            let shared_target_dir = shared_target_dir(&format!("{qualifier}_{thread_index:?}"));
+            let all_output = cmd
+                // use the looping index to create individual target dirs
+                .env("CARGO_TARGET_DIR", shared_target_dir.as_os_str())
+                // Roughly equivalent to `cargo clippy`/`cargo clippy --fix`
+                .env("RUSTC_WORKSPACE_WRAPPER", clippy_driver_path)
+                .output()
+                .unwrap();
+            let stdout = String::from_utf8_lossy(&all_output.stdout);
+            let stderr = String::from_utf8_lossy(&all_output.stderr);
+            let status = &all_output.status;
+
+            if !status.success() {
+                eprintln!(
+                    "\nWARNING: bad exit status after checking {} {} \n",
+                    self.name, self.version
                 );
             }
-            // fast path, we don't need the warnings anyway
-            return Vec::new();
-        }
 
-        // We don't want to keep target directories if benchmarking
-        if config.perf {
-            let _ = fs::remove_dir_all(&shared_target_dir);
-        }
+            if config.fix {
+                if let Some(stderr) = stderr
+                    .lines()
+                    .find(|line| line.contains("failed to automatically apply fixes suggested by rustc to crate"))
+                {
+                    let subcrate = &stderr[63..];
+                    println!(
+                        "ERROR: failed to apply some suggestion to {} / to (sub)crate {subcrate}",
+                        self.name
+                    );
+                }
+                // fast path, we don't need the warnings anyway
+                continue;
+            }
 
-        // get all clippy warnings and ICEs
-        let mut entries: Vec<ClippyCheckOutput> = Message::parse_stream(stdout.as_bytes())
-            .filter_map(|msg| match msg {
-                Ok(Message::CompilerMessage(message)) => ClippyWarning::new(
-                    normalize_diag(message.message, shared_target_dir.to_str().unwrap()),
-                    &self.base_url,
-                    &self.name,
-                ),
-                _ => None,
-            })
-            .map(ClippyCheckOutput::ClippyWarning)
-            .collect();
+            // We don't want to keep target directories if benchmarking
+            if config.perf {
+                let _ = fs::remove_dir_all(&shared_target_dir);
+            }
+
+            // get all clippy warnings and ICEs
+            entries.extend(
+                Message::parse_stream(stdout.as_bytes())
+                    .filter_map(|msg| match msg {
+                        Ok(Message::CompilerMessage(message)) => ClippyWarning::new(
+                            normalize_diag(message.message, shared_target_dir.to_str().unwrap()),
+                            &self.base_url,
+                            &self.name,
+                        ),
+                        _ => None,
+                    })
+                    .map(ClippyCheckOutput::ClippyWarning),
+            );
 
-        if let Some(ice) = RustcIce::from_stderr_and_status(&self.name, *status, &stderr) {
-            entries.push(ClippyCheckOutput::RustcIce(ice));
-        } else if !status.success() {
-            println!("non-ICE bad exit status for {} {}: {}", self.name, self.version, stderr);
+            if let Some(ice) = RustcIce::from_stderr_and_status(&self.name, *status, &stderr) {
+                entries.push(ClippyCheckOutput::RustcIce(ice));
+            } else if !status.success() {
+                println!("non-ICE bad exit status for {} {}: {}", self.name, self.version, stderr);
+            }
         }
 
+        // The same code is checked by each configuration
+        let mut seen = HashSet::new();
+        entries.retain(|entry| match entry {
+            ClippyCheckOutput::ClippyWarning(warning) => {
+                seen.insert((warning.name.clone(), warning.diag.rendered.clone()))
+            },
+            ClippyCheckOutput::RustcIce(_) => true,
+        });
         entries
     }
+
+    /// Returns the directories to run cargo in, one for each workspace member to check, with the
+    /// arguments selecting each feature set to check them with. The features a member does not
+    /// have are left out of its feature sets.
+    fn configurations(&self) -> Vec<(PathBuf, Vec<String>)> {
+        let dirs = match &self.members {
+            Some(globs) => input::workspace_members(&self.path, globs),
+            None => vec![self.path.clone()],
+        };
+        let mut configurations = Vec::new();
+        for dir in dirs {
+            let Some(sets) = &self.features else {
+                configurations.push((dir, Vec::new()));
+                continue;
+            };
+            let features = input::package_features(&dir);
+            let mut sets: Vec<Vec<&str>> = sets
+                .iter()
+                .map(|set| {
+                    set.iter()
+                        .map(String::as_str)
+                        .filter(|feature| features.contains(*feature) || feature.contains('/'))
+                        .collect()
+                })
+                .collect();
+            sets.sort();
+            sets.dedup();
+            for set in sets {
+                let args = vec![
+                    "--no-default-features".to_string(),
+                    format!("--features={}", set.join(",")),
+                ];
+                configurations.push((dir.clone(), args));
+            }
+        }
+        configurations
+    }
+
+    /// Returns the absolute path of the `perf.data` file recorded for the configuration `index` of
+    /// [`Self::configurations`], which is checked from the directory of a workspace member. With
+    /// a `qualifier`, each driver of a comparison gets its own file.
+    fn perf_data_path(&self, qualifier: &str, index: usize) -> PathBuf {
+        let dir = std::path::absolute(&self.path).unwrap();
+        let file_name = if qualifier.is_empty() {
+            get_perf_data_filename(&dir)
+        } else {
+            format!("perf.data.{qualifier}.{index}")
+        };
+        dir.join(file_name)
+    }
 }
 
 /// The target directory can sometimes be stored in the file name of spans.
@@ -309,6 +398,10 @@ fn main() {
             truncate,
             write_summary,
         }) => json::diff(&old, &new, truncate, write_summary),
+        Some(Commands::Pack {
+            output,
+            sources_toml_path,
+        }) => pack::pack(&sources_toml_path, &output),
         Some(Commands::Popular { output, number }) => popular_crates::fetch(output, number).unwrap(),
         None => lintcheck(config),
     }
@@ -336,7 +429,7 @@ fn lintcheck(config: LintcheckConfig) {
     // download and extract the crates, then run clippy on them and collect clippy's warnings
     // flatten into one big list of warnings
 
-    let (crates, recursive_options) = read_crates(&config.sources_toml_path);
+    let (crates, recursive_options) = read_crates(&config.sources_toml_path, config.vendor_dir.as_deref());
 
     let counter = AtomicUsize::new(1);
     let mut lint_level_args: Vec<String> = vec!["--cap-lints=allow".into()];
@@ -380,6 +473,12 @@ fn lintcheck(config: LintcheckConfig) {
         .map(|krate| krate.download_and_prepare())
         .collect();
 
+    if let Some(vendor_dir) = &config.vendor_dir {
+        for krate in &crates {
+            input::use_vendored_dependencies(&krate.path, vendor_dir);
+        }
+    }
+
     if crates.is_empty() {
         eprintln!(
             "ERROR: could not find crate '{}' in lintcheck/lintcheck_crates.toml",
@@ -398,6 +497,22 @@ fn lintcheck(config: LintcheckConfig) {
         .build_global()
         .unwrap();
 
+    if let Some(baseline_driver_path) = &config.baseline_driver {
+        compare_drivers(
+            &crates,
+            baseline_driver_path,
+            &clippy_driver_path,
+            &config,
+            &lint_level_args,
+        );
+        return;
+    }
+
+    if config.fix_triage {
+        fix_triage(&crates, &clippy_driver_path, &config, &lint_level_args);
+        return;
+    }
+
     let server = config.recursive.then(|| {
         let _: io::Result<()> = fs::remove_dir_all(format!("{}/lintcheck/shared_target_dir/recursive", target_dir()));
 
@@ -414,6 +529,7 @@ fn lintcheck(config: LintcheckConfig) {
                 &config,
                 &lint_level_args,
                 server.as_ref(),
+                "",
             )
         })
         .collect();
@@ -429,6 +545,14 @@ fn lintcheck(config: LintcheckConfig) {
         return;
     }
 
+    if config.pass_times {
+        let path = config.lintcheck_results_path.with_extension("pass_times.json");
+        let times = pass_times::read(&crates, "");
+        println!("{}", pass_times::report(&times, &pass_times::load(&path)));
+        println!("Writing pass times to {}", path.display());
+        pass_times::save(&path, &times);
+    }
+
     // split up warnings and ices
     let mut warnings: Vec<ClippyWarning> = vec![];
     let mut raw_ices: Vec<RustcIce> = vec![];
@@ -440,6 +564,16 @@ fn lintcheck(config: LintcheckConfig) {
         }
     }
 
+    if config.reduce_ices {
+        reduce::reduce_ices(
+            &raw_ices,
+            &crates,
+            &clippy_driver_path,
+            &lint_level_args,
+            &config.lintcheck_results_path,
+        );
+    }
+
     let text = match config.format {
         OutputFormat::Text | OutputFormat::Markdown => {
             output::summarize_and_print_changes(&warnings, &raw_ices, clippy_ver, &config)
@@ -454,6 +588,7 @@ fn lintcheck(config: LintcheckConfig) {
 
             json::output(warnings)
         },
+        OutputFormat::Html => html::output(&warnings, &raw_ices, &clippy_ver),
     };
 
     println!("Writing logs to {}", config.lintcheck_results_path.display());
@@ -461,6 +596,112 @@ fn lintcheck(config: LintcheckConfig) {
     fs::write(&config.lintcheck_results_path, text).unwrap();
 }
 
+/// Runs the `clippy-driver` at `baseline_driver_path` and the one of the repo on each crate, writes
+/// the logs of both in JSON format and prints the markdown diff of their warnings.
+fn compare_drivers(
+    crates: &[Crate],
+    baseline_driver_path: &Path,
+    clippy_driver_path: &Path,
+    config: &LintcheckConfig,
+    lint_level_args: &[String],
+) {
+    let baseline_driver_path = fs::canonicalize(baseline_driver_path)
+        .unwrap_or_else(|e| panic!("baseline driver {} not found: {e}", baseline_driver_path.display()));
+
+    let counter = AtomicUsize::new(1);
+    let (baseline_entries, entries): (Vec<_>, Vec<_>) = crates
+        .par_iter()
+        .map(|krate| {
+            let run = |driver: &Path, qualifier| {
+                krate.run_clippy_lints(
+                    driver,
+                    &counter,
+                    crates.len() * 2,
+                    config,
+                    lint_level_args,
+                    None,
+                    qualifier,
+                )
+            };
+            (run(&baseline_driver_path, "baseline"), run(clippy_driver_path, "new"))
+        })
+        .unzip();
+
+    let baseline_path = config.lintcheck_results_path.with_extension("baseline.json");
+    let new_path = config.lintcheck_results_path.with_extension("json");
+    fs::create_dir_all(new_path.parent().unwrap()).unwrap();
+    for (entries, path) in [(baseline_entries, &baseline_path), (entries, &new_path)] {
+        let mut warnings = Vec::new();
+        for entry in entries.into_iter().flatten() {
+            match entry {
+                ClippyCheckOutput::ClippyWarning(warning) => warnings.push(warning),
+                ClippyCheckOutput::RustcIce(ice) => println!("{ice}"),
+            }
+        }
+        eprintln!("Writing logs to {}", path.display());
+        fs::write(path, json::output(warnings)).unwrap();
+    }
+
+    json::diff(&baseline_path, &new_path, false, None);
+
+    if config.pass_times {
+        println!(
+            "{}",
+            pass_times::report(&pass_times::read(crates, "new"), &pass_times::read(crates, "baseline"))
+        );
+    }
+
+    if config.perf {
+        let instructions = |qualifier: &str| {
+            crates
+                .iter()
+                .flat_map(|krate| {
+                    (0..krate.configurations().len()).map(move |index| krate.perf_data_path(qualifier, index))
+                })
+                .map(|path| perf::lint_instructions(&path))
+                .fold(BTreeMap::new(), |mut total, instructions| {
+                    for (lint, count) in instructions {
+                        *total.entry(lint).or_default() += count;
+                    }
+                    total
+                })
+        };
+        perf::print_diff(&instructions("baseline"), &instructions("new"));
+    }
+}
+
+/// Runs Clippy on each crate and triages the machine-applicable suggestions of the warnings, see
+/// [`triage`].
+fn fix_triage(crates: &[Crate], clippy_driver_path: &Path, config: &LintcheckConfig, lint_level_args: &[String]) {
+    let counter = AtomicUsize::new(1);
+    let triage = crates
+        .par_iter()
+        .flat_map(|krate| {
+            let mut warnings = Vec::new();
+            for entry in krate.run_clippy_lints(
+                clippy_driver_path,
+                &counter,
+                crates.len(),
+                config,
+                lint_level_args,
+                None,
+                "",
+            ) {
+                match entry {
+                    ClippyCheckOutput::ClippyWarning(warning) => warnings.push(warning),
+                    ClippyCheckOutput::RustcIce(ice) => println!("{ice}"),
+                }
+            }
+            triage::triage_crate(krate, &warnings)
+        })
+        .collect();
+
+    let path = config.lintcheck_results_path.with_extension("triage.md");
+    println!("Writing triage report to {}", path.display());
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, triage::report(triage)).unwrap();
+}
+
 /// Traverse a directory looking for `perf.data.<number>` files, and adds one
 /// to the most recent of those files, returning the new most recent `perf.data`
 /// file name.
@@ -121,6 +121,7 @@ impl ClippyWarning {
                 output
             },
             OutputFormat::Json => unreachable!("JSON output is handled via serde"),
+            OutputFormat::Html => unreachable!("HTML output is handled by `html::output`"),
         }
     }
 }
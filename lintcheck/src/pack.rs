@@ -0,0 +1,54 @@
+//! `lintcheck pack`: creates a directory containing the crates of a sources TOML and all their
+//! dependencies, so that lintcheck can run without network access with `--vendor-dir`.
+//!
+//! The directory is the output of `cargo vendor --versioned-dirs` for all the crates, which cargo
+//! reads the dependencies from. The `.crate` files of the crates.io sources are added to it, and
+//! the git sources are copied to `git/<name>-<commit>/`, both of which cargo ignores.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::input::{CrateSource, copy_dir, crate_download_path, read_crates};
+
+pub(crate) fn pack(toml_path: &Path, output: &Path) {
+    let (crates, _) = read_crates(toml_path, None);
+    fs::create_dir_all(output).unwrap();
+
+    let mut manifests = Vec::new();
+    for krate in &crates {
+        let prepared = krate.download_and_prepare();
+        match &krate.source {
+            CrateSource::CratesIo { version } => {
+                let dest = output.join(format!("{}-{version}.crate", krate.name));
+                fs::copy(crate_download_path(&krate.name, version), dest).unwrap();
+            },
+            CrateSource::Git { commit, .. } => {
+                let dest = output.join("git").join(format!("{}-{commit}", krate.name));
+                copy_dir(&prepared.path, &dest);
+                let _ = fs::remove_dir_all(dest.join(".git"));
+            },
+            CrateSource::Path { .. } | CrateSource::Vendor { .. } => {},
+        }
+        manifests.push(prepared.path.join("Cargo.toml"));
+    }
+
+    let Some((first, rest)) = manifests.split_first() else {
+        return;
+    };
+    println!("Vendoring the dependencies into {}", output.display());
+    let mut cmd = Command::new("cargo");
+    cmd.args([
+        "vendor",
+        "--versioned-dirs",
+        "--no-delete",
+        "--quiet",
+        "--manifest-path",
+    ])
+    .arg(first);
+    for manifest in rest {
+        cmd.arg("--sync").arg(manifest);
+    }
+    let status = cmd.arg(output).status().expect("failed to run cargo vendor");
+    assert!(status.success(), "failed to vendor the dependencies");
+}
@@ -0,0 +1,142 @@
+//! `--pass-times`: aggregates the self-time of each of Clippy's lint passes, which Clippy records
+//! in the directory named by `CLIPPY_PASS_TIMES`.
+//!
+//! The times of each crate are saved in a JSON log mapping each crate to the nanoseconds spent in
+//! each lint pass, and compared with the times of the previous run.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::{fs, process};
+
+use crate::{Crate, shared_target_dir};
+
+/// The nanoseconds spent in each lint pass, for each crate.
+pub(crate) type CrateTimes = BTreeMap<String, BTreeMap<String, u64>>;
+
+/// Number of lint passes in the table of the slowest ones.
+const MAX_ROWS: usize = 30;
+/// Number of lint passes in the table of the slowest ones of each crate.
+const MAX_CRATE_ROWS: usize = 5;
+
+/// Returns the directory Clippy writes the times of the passes to when it checks `krate`. It is
+/// specific to this run of lintcheck, so that cargo re-runs Clippy on the crates it already
+/// checked.
+pub(crate) fn dir(krate: &Crate, qualifier: &str) -> PathBuf {
+    run_dir(qualifier).join(format!("{}-{}", krate.name, krate.version))
+}
+
+fn run_dir(qualifier: &str) -> PathBuf {
+    shared_target_dir("pass_times").join(format!("{}{qualifier}", process::id()))
+}
+
+/// Reads the times recorded for each of the `crates`, and removes them.
+pub(crate) fn read(crates: &[Crate], qualifier: &str) -> CrateTimes {
+    let mut times = CrateTimes::new();
+    for krate in crates {
+        let dir = dir(krate, qualifier);
+        let crate_times = times.entry(format!("{} {}", krate.name, krate.version)).or_default();
+        for file in fs::read_dir(&dir).into_iter().flatten().filter_map(Result::ok) {
+            let contents = fs::read(file.path()).unwrap();
+            let passes: BTreeMap<String, u64> = serde_json::from_slice(&contents)
+                .unwrap_or_else(|e| panic!("failed to deserialize {}: {e}", file.path().display()));
+            for (pass, nanos) in passes {
+                *crate_times.entry(pass).or_default() += nanos;
+            }
+        }
+    }
+    let _ = fs::remove_dir_all(run_dir(qualifier));
+    times
+}
+
+/// Loads the times saved by [`save`], if any.
+pub(crate) fn load(path: &Path) -> CrateTimes {
+    fs::read(path)
+        .ok()
+        .and_then(|contents| serde_json::from_slice(&contents).ok())
+        .unwrap_or_default()
+}
+
+pub(crate) fn save(path: &Path, times: &CrateTimes) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, serde_json::to_string(times).unwrap()).unwrap();
+}
+
+/// Creates the markdown tables of the slowest lint passes overall and of each crate, comparing
+/// them with the `previous` times.
+pub(crate) fn report(times: &CrateTimes, previous: &CrateTimes) -> String {
+    let previous_total = total(previous);
+
+    let mut text = String::from(
+        "\
+### Slowest lint passes
+
+| Lint pass | Time | Previous | Change |
+| --------- | ---: | -------: | -----: |
+",
+    );
+    for (pass, nanos) in slowest(&total(times), MAX_ROWS) {
+        let previous = previous_total.get(pass).copied();
+        writeln!(
+            text,
+            "| `{pass}` | {} | {} | {} |",
+            millis(nanos),
+            previous.map_or_else(|| "-".to_string(), millis),
+            change(previous, nanos)
+        )
+        .unwrap();
+    }
+
+    text.push_str(
+        "
+### Slowest lint passes per crate
+
+| Crate | Lint pass | Time | Previous | Change |
+| ----- | --------- | ---: | -------: | -----: |
+",
+    );
+    for (krate, passes) in times {
+        let passes: BTreeMap<&str, u64> = passes.iter().map(|(pass, &nanos)| (pass.as_str(), nanos)).collect();
+        for (pass, nanos) in slowest(&passes, MAX_CRATE_ROWS) {
+            let previous = previous.get(krate).and_then(|passes| passes.get(pass)).copied();
+            writeln!(
+                text,
+                "| {krate} | `{pass}` | {} | {} | {} |",
+                millis(nanos),
+                previous.map_or_else(|| "-".to_string(), millis),
+                change(previous, nanos)
+            )
+            .unwrap();
+        }
+    }
+    text
+}
+
+/// Sums the times of each lint pass over all the crates.
+fn total(times: &CrateTimes) -> BTreeMap<&str, u64> {
+    let mut total: BTreeMap<&str, u64> = BTreeMap::new();
+    for (pass, nanos) in times.values().flatten() {
+        *total.entry(pass).or_default() += nanos;
+    }
+    total
+}
+
+fn slowest<'a>(passes: &BTreeMap<&'a str, u64>, n: usize) -> Vec<(&'a str, u64)> {
+    let mut passes: Vec<(&str, u64)> = passes.iter().map(|(&pass, &nanos)| (pass, nanos)).collect();
+    passes.sort_by_key(|&(_, nanos)| std::cmp::Reverse(nanos));
+    passes.truncate(n);
+    passes
+}
+
+#[expect(clippy::cast_precision_loss)]
+fn millis(nanos: u64) -> String {
+    format!("{:.1} ms", nanos as f64 / 1e6)
+}
+
+#[expect(clippy::cast_precision_loss)]
+fn change(previous: Option<u64>, nanos: u64) -> String {
+    match previous {
+        Some(previous) if previous > 0 => format!("{:+.1}%", (nanos as f64 - previous as f64) * 100. / previous as f64),
+        _ => "-".to_string(),
+    }
+}
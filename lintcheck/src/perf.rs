@@ -0,0 +1,103 @@
+//! Attribution of the instructions recorded in `--perf` mode to Clippy's lint passes.
+//!
+//! The symbols of `perf report` are grouped by their module in `clippy_lints`, as the module of a
+//! lint pass contains the code of its lints.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::Command;
+
+/// Maximum number of lint passes in the timing table.
+const MAX_ROWS: usize = 50;
+
+/// Returns the number of instructions spent in each module of `clippy_lints`, excluding the
+/// functions it calls in other crates, according to the `perf.data` file at `path`.
+pub(crate) fn lint_instructions(path: &Path) -> BTreeMap<String, u64> {
+    let output = Command::new("perf")
+        .args([
+            "report",
+            "--stdio",
+            "--quiet",
+            "--no-children",
+            "--call-graph=none",
+            "--sort=sym",
+            "--fields=period,sym",
+            "--input",
+        ])
+        .arg(path)
+        .output()
+        .expect("failed to run perf report");
+    if !output.status.success() {
+        eprintln!(
+            "WARNING: failed to read {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    let mut instructions = BTreeMap::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        if let Some((period, symbol)) = line.trim_start().split_once(' ')
+            && let Ok(period) = period.parse::<u64>()
+            && let Some(module) = lint_module(symbol)
+        {
+            *instructions.entry(module.to_owned()).or_default() += period;
+        }
+    }
+    instructions
+}
+
+/// Returns the module of `clippy_lints` that contains `symbol`, e.g. `methods` for
+/// `<clippy_lints::methods::Methods as rustc_lint::passes::LateLintPass>::check_expr`.
+fn lint_module(symbol: &str) -> Option<&str> {
+    let (_, path) = symbol.split_once("clippy_lints::")?;
+    let end = path
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .unwrap_or(path.len());
+    Some(&path[..end])
+}
+
+/// Prints a markdown table of the lint passes whose instructions changed the most between the
+/// `baseline` and `new` drivers.
+pub(crate) fn print_diff(baseline: &BTreeMap<String, u64>, new: &BTreeMap<String, u64>) {
+    let mut rows: Vec<(&str, u64, u64)> = baseline
+        .keys()
+        .chain(new.keys())
+        .map(|module| {
+            let count = |instructions: &BTreeMap<String, u64>| instructions.get(module).copied().unwrap_or(0);
+            (module.as_str(), count(baseline), count(new))
+        })
+        .filter(|(_, baseline, new)| baseline != new)
+        .collect();
+    rows.sort_unstable();
+    rows.dedup();
+    rows.sort_by_key(|&(_, baseline, new)| std::cmp::Reverse(baseline.abs_diff(new)));
+
+    if rows.is_empty() {
+        return;
+    }
+
+    println!("## Instructions per lint pass");
+    println!();
+    println!("| Lint pass | Baseline | New | Change |");
+    println!("| --------- | -------: | --: | -----: |");
+    for (module, baseline, new) in rows.into_iter().take(MAX_ROWS) {
+        #[expect(clippy::cast_precision_loss)]
+        let change = if baseline == 0 {
+            "new".to_string()
+        } else {
+            format!("{:+.1}%", (new as f64 - baseline as f64) * 100. / baseline as f64)
+        };
+        println!("| `{module}` | {baseline} | {new} | {change} |");
+    }
+}
+
+#[test]
+fn lint_module_of_symbol() {
+    assert_eq!(
+        lint_module("<clippy_lints::methods::Methods as rustc_lint::passes::LateLintPass>::check_expr"),
+        Some("methods")
+    );
+    assert_eq!(lint_module("clippy_lints::loops::never_loop::check"), Some("loops"));
+    assert_eq!(lint_module("rustc_lint::late::late_lint_crate"), None);
+}
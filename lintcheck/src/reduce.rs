@@ -0,0 +1,246 @@
+//! `--reduce-ices`: reduces each crate that made Clippy ICE to a standalone reproducer.
+//!
+//! The lint causing the ICE is first found by bisecting the lints of Clippy, enabling part of them
+//! in each run with `-A clippy::all -W clippy::<lint>`. The source files of a copy of the crate
+//! are then shrunk with a delta debugging loop over their lines, keeping the removals after which
+//! Clippy still panics at the same place. The remaining source is written as a
+//! `tests/ui/crashes/` test.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use walkdir::WalkDir;
+
+use crate::input::copy_dir;
+use crate::output::RustcIce;
+use crate::{Crate, shared_target_dir, target_dir};
+
+/// Reduces each of the `ices` and writes the reproducers next to the `results_path`.
+pub(crate) fn reduce_ices(
+    ices: &[RustcIce],
+    crates: &[Crate],
+    clippy_driver_path: &Path,
+    lint_level_args: &[String],
+    results_path: &Path,
+) {
+    for ice in ices {
+        let Some(krate) = crates.iter().find(|krate| krate.name == ice.crate_name) else {
+            continue;
+        };
+        if let Some(reproducer) = reduce(krate, clippy_driver_path, lint_level_args) {
+            let path = results_path.with_extension(format!("ice.{}.rs", krate.name));
+            println!("Writing the reproducer of the ICE to {}", path.display());
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, reproducer).unwrap();
+        }
+    }
+}
+
+/// Runs Clippy on a copy of a crate.
+struct Reducer<'a> {
+    dir: PathBuf,
+    target: PathBuf,
+    clippy_driver_path: &'a Path,
+    options: &'a [String],
+    runs: usize,
+}
+
+impl Reducer<'_> {
+    /// Runs Clippy with the `args` and returns where it panicked, if it did.
+    fn panic(&mut self, args: &[String]) -> Option<String> {
+        self.runs += 1;
+        let clippy_args: Vec<&str> = self.options.iter().chain(args).map(String::as_str).collect();
+        let output = Command::new("cargo")
+            .args(["check", "--quiet"])
+            .current_dir(&self.dir)
+            .env("CARGO_TARGET_DIR", &self.target)
+            .env("RUSTC_WORKSPACE_WRAPPER", self.clippy_driver_path)
+            .env("CLIPPY_ARGS", clippy_args.join("__CLIPPY_HACKERY__"))
+            .env("CLIPPY_DISABLE_DOCS_LINKS", "1")
+            .output()
+            .expect("failed to run cargo");
+        String::from_utf8_lossy(&output.stderr).lines().find_map(|line| {
+            let location = line
+                .split_once("panicked at ")
+                .map(|(_, location)| location)
+                .or_else(|| line.strip_prefix("error: internal compiler error: "))?;
+            Some(location.trim_end_matches(':').to_string())
+        })
+    }
+}
+
+/// Returns the source of a test reproducing the ICE of `krate`, if it can be reproduced.
+fn reduce(krate: &Crate, clippy_driver_path: &Path, lint_level_args: &[String]) -> Option<String> {
+    let mut reducer = Reducer {
+        dir: PathBuf::from(format!("{}/lintcheck/reduce/{}", target_dir(), krate.name)),
+        target: shared_target_dir(&format!("reduce_{}", krate.name)),
+        clippy_driver_path,
+        options: krate.options.as_deref().unwrap_or_default(),
+        runs: 0,
+    };
+    copy_dir(&krate.path, &reducer.dir);
+
+    let Some(panic) = reducer.panic(lint_level_args) else {
+        println!(
+            "WARNING: could not reproduce the ICE of {} {}, skipping its reduction",
+            krate.name, krate.version
+        );
+        return None;
+    };
+    println!("Reducing the ICE of {} {} at {panic}", krate.name, krate.version);
+
+    let lints = failing_lints(&mut reducer, &panic);
+    let args = match &lints {
+        Some(lints) => lint_args(lints),
+        None => lint_level_args.to_vec(),
+    };
+    if let Some(lints) = &lints {
+        println!("The ICE is caused by `{}`", lints.join("`, `"));
+    }
+
+    let mut files: Vec<PathBuf> = WalkDir::new(&reducer.dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .map(walkdir::DirEntry::into_path)
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .collect();
+    files.sort();
+    for file in &files {
+        let source = fs::read_to_string(file).unwrap();
+        let source_lines: Vec<&str> = source.lines().collect();
+        let shrunk = ddmin(&source_lines, |kept| {
+            fs::write(file, kept.join("\n")).unwrap();
+            reducer.panic(&args).as_ref() == Some(&panic)
+        });
+        fs::write(file, shrunk.join("\n")).unwrap();
+    }
+    println!("Reduced the ICE of {} in {} runs", krate.name, reducer.runs);
+
+    let mut reproducer = format!(
+        "// Reduced from the ICE of `{} {}` by lintcheck:\n// {panic}\n\n",
+        krate.name, krate.version
+    );
+    if let Some(lints) = &lints {
+        writeln!(reproducer, "#![warn({})]\n", lints.join(", ")).unwrap();
+    }
+    let sources: Vec<(&Path, String)> = files
+        .iter()
+        .map(|file| {
+            (
+                file.strip_prefix(&reducer.dir).unwrap(),
+                fs::read_to_string(file).unwrap(),
+            )
+        })
+        .filter(|(_, source)| !source.trim().is_empty())
+        .collect();
+    for (file, source) in &sources {
+        // The test needs a single file, the files left have to be merged manually
+        if sources.len() > 1 {
+            writeln!(reproducer, "// {}", file.display()).unwrap();
+        }
+        reproducer.push_str(source.trim());
+        reproducer.push_str("\n\n");
+    }
+    if !sources.iter().any(|(_, source)| source.contains("fn main(")) {
+        reproducer.push_str("fn main() {}\n");
+    }
+
+    let _ = fs::remove_dir_all(&reducer.dir);
+    let _ = fs::remove_dir_all(&reducer.target);
+    Some(reproducer.trim_end().to_string() + "\n")
+}
+
+/// Returns the lints that need to be enabled for Clippy to panic at `panic`, or `None` if they
+/// could not be narrowed down from the lint levels of the lintcheck run.
+fn failing_lints(reducer: &mut Reducer<'_>, panic: &str) -> Option<Vec<String>> {
+    let clippy_driver_path = reducer.clippy_driver_path;
+    let mut panics = |lints: &[String]| reducer.panic(&lint_args(lints)).as_deref() == Some(panic);
+
+    if panics(&[]) {
+        return Some(Vec::new());
+    }
+    let mut lints = clippy_lints(clippy_driver_path);
+    if !panics(&lints) {
+        return None;
+    }
+    while lints.len() > 1 {
+        let (first, second) = lints.split_at(lints.len() / 2);
+        if panics(first) {
+            lints = first.to_vec();
+        } else if panics(second) {
+            lints = second.to_vec();
+        } else {
+            // Lints of both halves are needed
+            break;
+        }
+    }
+    Some(lints)
+}
+
+/// Returns the lint level arguments enabling only the `lints` of Clippy.
+fn lint_args(lints: &[String]) -> Vec<String> {
+    let mut args = vec!["-A".to_string(), "clippy::all".to_string()];
+    for lint in lints {
+        args.push("-W".to_string());
+        args.push(lint.clone());
+    }
+    args
+}
+
+/// Returns the names of the lints of Clippy, e.g. `clippy::len_zero`, as listed by `-W help`.
+fn clippy_lints(clippy_driver_path: &Path) -> Vec<String> {
+    let output = Command::new(clippy_driver_path)
+        .args(["-W", "help"])
+        .output()
+        .expect("failed to run clippy-driver");
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut words = line.split_whitespace();
+            let (name, level) = (words.next()?, words.next()?);
+            (name.starts_with("clippy::") && matches!(level, "allow" | "warn" | "deny" | "forbid"))
+                .then(|| name.replace('-', "_"))
+        })
+        .collect()
+}
+
+/// Returns a subset of `lines` for which `test` holds, from which no chunk of lines can be removed
+/// at the smallest granularity tried, assuming `test` holds for `lines`.
+fn ddmin<'a>(lines: &[&'a str], mut test: impl FnMut(&[&str]) -> bool) -> Vec<&'a str> {
+    if test(&[]) {
+        return Vec::new();
+    }
+    let mut lines = lines.to_vec();
+    let mut chunks = 2;
+    while lines.len() >= 2 {
+        let chunk_len = lines.len().div_ceil(chunks);
+        let reduced = (0..lines.len()).step_by(chunk_len).find_map(|start| {
+            let end = (start + chunk_len).min(lines.len());
+            let complement = [&lines[..start], &lines[end..]].concat();
+            test(&complement).then_some(complement)
+        });
+        if let Some(reduced) = reduced {
+            lines = reduced;
+            chunks = (chunks - 1).max(2);
+        } else if chunks < lines.len() {
+            chunks = (chunks * 2).min(lines.len());
+        } else {
+            break;
+        }
+    }
+    lines
+}
+
+#[test]
+fn ddmin_lines() {
+    let lines = ["a", "b", "c", "d", "e", "f", "g"];
+    let mut runs = 0;
+    let reduced = ddmin(&lines, |lines| {
+        runs += 1;
+        lines.contains(&"b") && lines.contains(&"f")
+    });
+    assert_eq!(reduced, ["b", "f"]);
+    assert!(runs < 20);
+}
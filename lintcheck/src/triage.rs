@@ -0,0 +1,299 @@
+//! `--fix-triage`: applies the machine-applicable suggestions of each lint in isolation to a copy
+//! of a crate, and reports the suggestions that break `cargo check --tests` or change the set of
+//! public items of the crate.
+//!
+//! The suggestions of a lint are first applied all at once, they are only applied one by one to
+//! find the faulty ones when the crate no longer builds or its public items changed.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use cargo_metadata::Message;
+use cargo_metadata::diagnostic::{Applicability, DiagnosticLevel};
+use serde_json::Value;
+
+use crate::input::copy_dir;
+use crate::output::ClippyWarning;
+use crate::{Crate, shared_target_dir, target_dir};
+
+/// A replacement of the bytes `start..end` of `file`, relative to the crate root, by `text`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Replacement {
+    file: PathBuf,
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+/// A warning with its machine-applicable suggestions.
+struct Suggestion<'a> {
+    warning: &'a ClippyWarning,
+    replacements: Vec<Replacement>,
+}
+
+impl<'a> Suggestion<'a> {
+    fn new(warning: &'a ClippyWarning, crate_root: &Path) -> Option<Self> {
+        let replacements: Vec<Replacement> = warning
+            .diag
+            .children
+            .iter()
+            .flat_map(|child| &child.spans)
+            .filter(|span| span.suggestion_applicability == Some(Applicability::MachineApplicable))
+            .filter_map(|span| {
+                // The files of the crate are either relative to its root or to the working directory
+                let file = Path::new(&span.file_name);
+                let file = file.strip_prefix(crate_root).unwrap_or(file);
+                if file.is_absolute() || span.file_name.starts_with('$') || !crate_root.join(file).is_file() {
+                    return None;
+                }
+                Some(Replacement {
+                    file: file.to_path_buf(),
+                    start: span.byte_start as usize,
+                    end: span.byte_end as usize,
+                    text: span.suggested_replacement.clone()?,
+                })
+            })
+            .collect();
+        (!replacements.is_empty()).then_some(Self { warning, replacements })
+    }
+
+    fn link(&self) -> String {
+        let span = self.warning.span();
+        format!("[`{}:{}`]({})", span.file_name, span.line_start, self.warning.url)
+    }
+}
+
+/// The result of checking a copy of the crate.
+#[derive(PartialEq, Eq)]
+struct Check {
+    /// The rendered compile errors of `cargo check --tests`.
+    errors: Vec<String>,
+    /// The public items of the library, as `<kind> <path>`.
+    public_items: BTreeSet<String>,
+}
+
+/// The suggestions of a lint in a crate that broke it.
+pub(crate) struct LintTriage {
+    lint: String,
+    krate: String,
+    suggestions: usize,
+    /// Each suggestion that broke the build, with its errors.
+    broken: Vec<(String, Vec<String>)>,
+    /// Each suggestion that changed the public items, with the removed and added items.
+    changed: Vec<(String, Vec<String>, Vec<String>)>,
+}
+
+/// Triages the suggestions of the `warnings` emitted for `krate`.
+pub(crate) fn triage_crate(krate: &Crate, warnings: &[ClippyWarning]) -> Vec<LintTriage> {
+    let mut suggestions: BTreeMap<&str, Vec<Suggestion<'_>>> = BTreeMap::new();
+    for warning in warnings {
+        if let Some(suggestion) = Suggestion::new(warning, &krate.path) {
+            suggestions.entry(&warning.name).or_default().push(suggestion);
+        }
+    }
+    if suggestions.is_empty() {
+        return Vec::new();
+    }
+
+    let dir = PathBuf::from(format!("{}/lintcheck/triage/{}", target_dir(), krate.name));
+    let target = shared_target_dir(&format!("triage_{}", krate.name));
+    let check_with = |replacements: &[&Replacement]| {
+        copy_dir(&krate.path, &dir);
+        apply(&dir, replacements);
+        check(&dir, &target)
+    };
+
+    let original = check_with(&[]);
+    if !original.errors.is_empty() {
+        println!(
+            "WARNING: {} {} does not build with `cargo check --tests`, skipping triage",
+            krate.name, krate.version
+        );
+        return Vec::new();
+    }
+
+    let mut triage = Vec::new();
+    for (lint, suggestions) in suggestions {
+        println!(
+            "Triaging the suggestions of `{lint}` in {} {}",
+            krate.name, krate.version
+        );
+        let mut lint_triage = LintTriage {
+            lint: lint.to_string(),
+            krate: format!("{} {}", krate.name, krate.version),
+            suggestions: suggestions.len(),
+            broken: Vec::new(),
+            changed: Vec::new(),
+        };
+        let all: Vec<&Replacement> = suggestions.iter().flat_map(|s| &s.replacements).collect();
+        if check_with(&all) != original {
+            for suggestion in &suggestions {
+                let fixed = check_with(&suggestion.replacements.iter().collect::<Vec<_>>());
+                if !fixed.errors.is_empty() {
+                    lint_triage.broken.push((suggestion.link(), fixed.errors));
+                } else if fixed.public_items != original.public_items {
+                    lint_triage.changed.push((
+                        suggestion.link(),
+                        original.public_items.difference(&fixed.public_items).cloned().collect(),
+                        fixed.public_items.difference(&original.public_items).cloned().collect(),
+                    ));
+                }
+            }
+        }
+        triage.push(lint_triage);
+    }
+
+    let _ = fs::remove_dir_all(&dir);
+    let _ = fs::remove_dir_all(&target);
+    triage
+}
+
+/// Applies the `replacements` to the crate at `dir`, skipping those overlapping a previous one.
+fn apply(dir: &Path, replacements: &[&Replacement]) {
+    let mut by_file: BTreeMap<&Path, Vec<&Replacement>> = BTreeMap::new();
+    for &replacement in replacements {
+        by_file.entry(&replacement.file).or_default().push(replacement);
+    }
+    for (file, mut replacements) in by_file {
+        let path = dir.join(file);
+        let Ok(mut contents) = fs::read(&path) else {
+            continue;
+        };
+        replacements.sort();
+        replacements.dedup();
+        let mut end = usize::MAX;
+        // Applied back to front so the offsets of the remaining ones stay valid
+        for replacement in replacements.into_iter().rev() {
+            if replacement.end <= end && replacement.end <= contents.len() {
+                contents.splice(replacement.start..replacement.end, replacement.text.bytes());
+                end = replacement.start;
+            }
+        }
+        fs::write(&path, contents).unwrap();
+    }
+}
+
+/// Runs `cargo check --tests` and documents the library of the crate at `dir`.
+fn check(dir: &Path, target: &Path) -> Check {
+    let output = Command::new("cargo")
+        .args(["check", "--tests", "--quiet", "--message-format=json"])
+        .current_dir(dir)
+        .env("CARGO_TARGET_DIR", target)
+        .output()
+        .expect("failed to run cargo");
+    let mut errors: Vec<String> = Message::parse_stream(output.stdout.as_slice())
+        .filter_map(|message| match message {
+            Ok(Message::CompilerMessage(message)) if message.message.level == DiagnosticLevel::Error => {
+                message.message.rendered
+            },
+            _ => None,
+        })
+        .collect();
+    if errors.is_empty() && !output.status.success() {
+        errors.push(String::from_utf8_lossy(&output.stderr).into_owned());
+    }
+
+    Check {
+        errors,
+        public_items: public_items(dir, target),
+    }
+}
+
+/// Returns the public items of the library of the crate at `dir`, from its rustdoc JSON output.
+fn public_items(dir: &Path, target: &Path) -> BTreeSet<String> {
+    let doc_dir = target.join("doc");
+    let _ = fs::remove_dir_all(&doc_dir);
+    let documented = Command::new("cargo")
+        .args([
+            "rustdoc",
+            "--lib",
+            "--quiet",
+            "--",
+            "-Zunstable-options",
+            "--output-format=json",
+        ])
+        .current_dir(dir)
+        .env("CARGO_TARGET_DIR", target)
+        .output()
+        .is_ok_and(|output| output.status.success());
+    let json = fs::read_dir(&doc_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .find(|entry| entry.path().extension().is_some_and(|ext| ext == "json"));
+    let (true, Some(json)) = (documented, json) else {
+        return BTreeSet::new();
+    };
+    let Ok(doc) = fs::read(json.path())
+        .map_err(|_| ())
+        .and_then(|doc| serde_json::from_slice::<Value>(&doc).map_err(|_| ()))
+    else {
+        return BTreeSet::new();
+    };
+
+    doc["index"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(_, item)| item["crate_id"] == 0 && item["visibility"] == "public")
+        .filter_map(|(id, item)| {
+            let kind = item["inner"].as_object()?.keys().next()?;
+            let path = match doc["paths"][id]["path"].as_array() {
+                Some(path) => path.iter().filter_map(Value::as_str).collect::<Vec<_>>().join("::"),
+                None => item["name"].as_str()?.to_string(),
+            };
+            Some(format!("{kind} {path}"))
+        })
+        .collect()
+}
+
+/// Creates the markdown report of the triage of the suggestions.
+pub(crate) fn report(mut triage: Vec<LintTriage>) -> String {
+    triage.sort_by(|a, b| (&a.lint, &a.krate).cmp(&(&b.lint, &b.krate)));
+
+    let mut text = String::from(
+        "\
+| Lint | Suggestions | Broke the build | Changed the public items |
+| ---- | ----------: | --------------: | -----------------------: |
+",
+    );
+    let mut lints: BTreeMap<&str, (usize, usize, usize)> = BTreeMap::new();
+    for lint in &triage {
+        let (suggestions, broken, changed) = lints.entry(&lint.lint).or_default();
+        *suggestions += lint.suggestions;
+        *broken += lint.broken.len();
+        *changed += lint.changed.len();
+    }
+    for (lint, (suggestions, broken, changed)) in lints {
+        writeln!(text, "| `{lint}` | {suggestions} | {broken} | {changed} |").unwrap();
+    }
+
+    for lint in triage
+        .iter()
+        .filter(|lint| !lint.broken.is_empty() || !lint.changed.is_empty())
+    {
+        writeln!(text, "\n## `{}` in {}\n", lint.lint, lint.krate).unwrap();
+        for (link, errors) in &lint.broken {
+            writeln!(
+                text,
+                "Broke the build at {link}\n\n```\n{}\n```\n",
+                errors.concat().trim_end()
+            )
+            .unwrap();
+        }
+        for (link, removed, added) in &lint.changed {
+            writeln!(text, "Changed the public items at {link}\n").unwrap();
+            for item in removed {
+                writeln!(text, "- removed `{item}`").unwrap();
+            }
+            for item in added {
+                writeln!(text, "- added `{item}`").unwrap();
+            }
+            text.push('\n');
+        }
+    }
+    text
+}
@@ -8,6 +8,7 @@
 // (Currently there is no way to opt into sysroot crates without `extern crate`.)
 extern crate rustc_driver;
 extern crate rustc_interface;
+extern crate rustc_middle;
 extern crate rustc_session;
 extern crate rustc_span;
 
@@ -22,9 +23,12 @@ extern crate tikv_jemalloc_sys as _;
 
 use clippy_utils::sym;
 use declare_clippy_lint::LintListBuilder;
+use rustc_driver::Compilation;
 use rustc_interface::interface;
+use rustc_middle::ty::TyCtxt;
 use rustc_session::config::ErrorOutputType;
 use rustc_session::{EarlyDiagCtxt, Session};
+use rustc_span::edit_distance::find_best_match_for_name;
 use rustc_span::symbol::Symbol;
 
 use std::env;
@@ -138,6 +142,7 @@ impl rustc_driver::Callbacks for RustcCallbacks {
 
 struct ClippyCallbacks {
     clippy_args_var: Option<String>,
+    baseline: Option<String>,
 }
 
 impl rustc_driver::Callbacks for ClippyCallbacks {
@@ -146,16 +151,27 @@ impl rustc_driver::Callbacks for ClippyCallbacks {
         let conf_path = clippy_config::lookup_conf_file();
         let previous = config.register_lints.take();
         let clippy_args_var = self.clippy_args_var.take();
+        let baseline = self.baseline.take();
         config.track_state = Some(Box::new(move |sess| {
             track_clippy_args(sess, clippy_args_var.as_deref());
             track_files(sess);
 
+            // Re-run clippy when the baseline changes, as the diagnostics it contains are not emitted
+            if let Some(baseline) = &baseline {
+                sess.file_depinfo.borrow_mut().insert(Symbol::intern(baseline));
+            }
+
             // Trigger a rebuild if CLIPPY_CONF_DIR changes. The value must be a valid string so
             // changes between dirs that are invalid UTF-8 will not trigger rebuilds
             sess.env_depinfo.borrow_mut().insert((
                 sym::CLIPPY_CONF_DIR,
                 env::var("CLIPPY_CONF_DIR").ok().map(|dir| Symbol::intern(&dir)),
             ));
+            // Re-run clippy to record the time of the lint passes in a new directory
+            sess.env_depinfo.borrow_mut().insert((
+                sym::CLIPPY_PASS_TIMES,
+                env::var("CLIPPY_PASS_TIMES").ok().map(|dir| Symbol::intern(&dir)),
+            ));
         }));
         config.register_lints = Some(Box::new(move |sess, lint_store| {
             // technically we're ~guaranteed that this is none but might as well call anything that
@@ -169,6 +185,15 @@ impl rustc_driver::Callbacks for ClippyCallbacks {
             list_builder.register(lint_store);
 
             let conf = clippy_config::Conf::read(sess, &conf_path);
+            let lints: Vec<Symbol> = clippy_lints::declared_lints::LINTS
+                .iter()
+                .map(|info| Symbol::intern(&info.lint.name_lower()))
+                .collect();
+            let shared_conf = conf_path
+                .as_ref()
+                .is_ok_and(|(path, _)| path.as_deref().is_some_and(clippy_config::is_shared_conf_file));
+            track_budgets(sess, conf, &lints, shared_conf);
+            check_allowed_lints(sess, &lints);
             clippy_lints::register_lint_passes(lint_store, conf);
 
             #[cfg(feature = "internal")]
@@ -176,6 +201,10 @@ impl rustc_driver::Callbacks for ClippyCallbacks {
         }));
         config.extra_symbols = sym::EXTRA_SYMBOLS.into();
 
+        // The lints allowed by the `[[overrides]]` entries of the configuration file are allowed
+        // through the lint levels, like the lints allowed by attributes
+        config.override_queries = Some(|_, providers| clippy_utils::allowed_lints::provide(providers));
+
         // FIXME: #4825; This is required, because Clippy lints that are based on MIR have to be
         // run on the unoptimized MIR. On the other hand this results in some false negatives. If
         // MIR passes can be enabled / disabled separately, we should figure out, what passes to
@@ -187,6 +216,78 @@ impl rustc_driver::Callbacks for ClippyCallbacks {
         // Disable flattening and inlining of format_args!(), so the HIR matches with the AST.
         config.opts.unstable_opts.flatten_format_args = false;
     }
+
+    fn after_analysis(&mut self, _: &interface::Compiler, tcx: TyCtxt<'_>) -> Compilation {
+        clippy_utils::budgets::check(tcx);
+        Compilation::Continue
+    }
+}
+
+/// Counts the warnings of the lints in the `budgets` of `conf`, warning about the names that are
+/// not lints of Clippy. `shared_conf` tells whether the configuration file may be shared by
+/// several packages.
+fn track_budgets(sess: &Session, conf: &clippy_config::Conf, lints: &[Symbol], shared_conf: bool) {
+    let mut budgets = Vec::new();
+    for (name, budget) in &conf.budgets {
+        let lint = name.replace('-', "_");
+        let lint = if lint.starts_with("clippy::") {
+            lint
+        } else {
+            format!("clippy::{lint}")
+        };
+        let lint = Symbol::intern(&lint);
+        if lints.contains(&lint) {
+            budgets.push((lint.to_string(), budget.budget, budget.span));
+        } else {
+            let mut diag = sess
+                .dcx()
+                .struct_span_warn(budget.span, format!("unknown lint `{name}` in `budgets`"));
+            if let Some(suggestion) = find_best_match_for_name(lints, lint, None) {
+                diag.help(format!("did you mean `{suggestion}`?"));
+            }
+            diag.emit();
+        }
+    }
+    // When run by `cargo clippy`, the diagnostics are recorded to be merged with the ones of the other
+    // crates, so the crates with budgets are checked again when switching to `clippy-driver` alone
+    let record = env::var(clippy_utils::budgets::RECORD_VAR).ok();
+    if !budgets.is_empty() {
+        sess.env_depinfo
+            .borrow_mut()
+            .insert((sym::CLIPPY_BUDGETS_RECORD, record.as_deref().map(Symbol::intern)));
+    }
+    clippy_utils::budgets::track(budgets, shared_conf, record.is_some());
+}
+
+/// Warns about the names in the `allow` keys of the configuration file that are not lints of
+/// Clippy, or whose lint can't be allowed by them.
+fn check_allowed_lints(sess: &Session, lints: &[Symbol]) {
+    let names = clippy_utils::allowed_lints::names();
+    let early_lints: Vec<Symbol> = if names.is_empty() {
+        Vec::new()
+    } else {
+        clippy_lints::early_lints()
+            .iter()
+            .map(|lint| Symbol::intern(&lint.name_lower()))
+            .collect()
+    };
+    for (name, span) in names {
+        let lint = Symbol::intern(name);
+        if early_lints.contains(&lint) {
+            sess.dcx()
+                .struct_span_warn(*span, format!("`{name}` can't be allowed by `[[overrides]]` entries"))
+                .with_note("it is checked on the syntax tree, where only the lint attributes apply")
+                .emit();
+        } else if !lints.contains(&lint) {
+            let mut diag = sess
+                .dcx()
+                .struct_span_warn(*span, format!("unknown lint `{name}` in `allow`"));
+            if let Some(suggestion) = find_best_match_for_name(lints, lint, None) {
+                diag.help(format!("did you mean `{suggestion}`?"));
+            }
+            diag.emit();
+        }
+    }
 }
 
 fn display_help() -> ExitCode {
@@ -279,6 +380,8 @@ fn main() -> ExitCode {
         pass_sysroot_env_if_given(&mut args, sys_root_env);
 
         let mut no_deps = false;
+        let mut baseline = None;
+        let mut write_baseline = None;
         let clippy_args_var = env::var("CLIPPY_ARGS").ok();
         let clippy_args = clippy_args_var
             .as_deref()
@@ -290,6 +393,14 @@ fn main() -> ExitCode {
                     no_deps = true;
                     None
                 },
+                _ if let Some(path) = s.strip_prefix("--baseline=") => {
+                    baseline = Some(path.to_string());
+                    None
+                },
+                _ if let Some(dir) = s.strip_prefix("--write-baseline=") => {
+                    write_baseline = Some(dir.to_string());
+                    None
+                },
                 _ => Some(s.to_string()),
             })
             .chain(vec!["--cfg".into(), "clippy".into()])
@@ -310,7 +421,7 @@ fn main() -> ExitCode {
         let clippy_enabled = !cap_lints_allow && relevant_package && !info_query;
         if clippy_enabled {
             args.extend(clippy_args);
-            rustc_driver::run_compiler(&args, &mut ClippyCallbacks { clippy_args_var });
+            run_clippy(&early_dcx, &args, clippy_args_var, baseline, write_baseline.as_deref());
         } else {
             rustc_driver::run_compiler(&args, &mut RustcCallbacks { clippy_args_var });
         }
@@ -318,6 +429,37 @@ fn main() -> ExitCode {
     })
 }
 
+/// Runs Clippy, only emitting the diagnostics not in the `baseline` file if given, or recording
+/// them to the `write_baseline` directory.
+fn run_clippy(
+    early_dcx: &EarlyDiagCtxt,
+    args: &[String],
+    clippy_args_var: Option<String>,
+    baseline: Option<String>,
+    write_baseline: Option<&str>,
+) {
+    if let Some(baseline) = &baseline
+        && let Err(e) = clippy_utils::baseline::read(Path::new(baseline))
+    {
+        early_dcx.early_fatal(e);
+    }
+    if let Some(dir) = write_baseline {
+        clippy_utils::baseline::record(Path::new(dir));
+    }
+
+    rustc_driver::run_compiler(
+        args,
+        &mut ClippyCallbacks {
+            clippy_args_var,
+            baseline,
+        },
+    );
+
+    if let Err(e) = clippy_utils::baseline::write() {
+        early_dcx.early_fatal(e);
+    }
+}
+
 #[must_use]
 fn help_message() -> &'static str {
     color_print::cstr!(
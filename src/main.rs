@@ -6,10 +6,14 @@
 
 extern crate rustc_driver;
 
-use std::env;
-use std::io::Write as _;
-use std::path::PathBuf;
-use std::process::{self, Command, exit};
+mod sarif;
+
+use cargo_metadata::Message;
+use clippy_utils::budgets::Count;
+use std::io::{self, BufRead as _, BufReader, Write as _};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio, exit};
+use std::{env, fs};
 
 fn show_help() {
     if writeln!(&mut anstream::stdout().lock(), "{}", help_message()).is_err() {
@@ -52,22 +56,45 @@ pub fn main() {
     }
 }
 
+enum Baseline {
+    /// Only report the diagnostics not in the baseline.
+    Read(PathBuf),
+    /// Write the diagnostics to the baseline instead of reporting them.
+    Write(PathBuf),
+}
+
+/// The messages cargo prints to its standard output.
+#[derive(Debug, PartialEq)]
+enum Messages {
+    /// The human-readable messages asked for by the user, with nothing printed to the standard
+    /// output.
+    Human,
+    /// The JSON messages asked for by the user.
+    Json,
+    /// The JSON messages asked for by `cargo clippy`, which are not printed.
+    Internal,
+}
+
 struct ClippyCmd {
     cargo_subcommand: &'static str,
     args: Vec<String>,
     clippy_args: Vec<String>,
+    baseline: Option<Baseline>,
+    output_format: Option<String>,
 }
 
 impl ClippyCmd {
-    fn new<I>(mut old_args: I) -> Self
+    fn new<I>(mut old_args: I) -> Result<Self, String>
     where
         I: Iterator<Item = String>,
     {
         let mut cargo_subcommand = "check";
         let mut args = vec![];
         let mut clippy_args: Vec<String> = vec![];
+        let mut baseline = None;
+        let mut output_format = None;
 
-        for arg in old_args.by_ref() {
+        while let Some(arg) = old_args.next() {
             match arg.as_str() {
                 "--fix" => {
                     cargo_subcommand = "fix";
@@ -77,9 +104,34 @@ impl ClippyCmd {
                     clippy_args.push("--no-deps".into());
                     continue;
                 },
+                "--baseline" | "--write-baseline" => {
+                    let Some(path) = old_args.next() else {
+                        return Err(format!("`{arg}` requires the path of the baseline file"));
+                    };
+                    baseline = Some(Baseline::new(&arg, path));
+                    continue;
+                },
+                "--output-format" => {
+                    let Some(format) = old_args.next() else {
+                        return Err("`--output-format` requires a format, `human` or `sarif`".into());
+                    };
+                    output_format = Some(format);
+                    continue;
+                },
                 "--" => break,
                 _ => {},
             }
+            match arg.split_once('=') {
+                Some((key @ ("--baseline" | "--write-baseline"), path)) => {
+                    baseline = Some(Baseline::new(key, path.into()));
+                    continue;
+                },
+                Some(("--output-format", format)) => {
+                    output_format = Some(format.into());
+                    continue;
+                },
+                _ => {},
+            }
 
             args.push(arg);
         }
@@ -89,11 +141,49 @@ impl ClippyCmd {
             clippy_args.push("--no-deps".into());
         }
 
-        Self {
+        Ok(Self {
             cargo_subcommand,
             args,
             clippy_args,
+            baseline,
+            output_format,
+        })
+    }
+
+    /// Asks cargo for JSON messages, which the SARIF log is converted from and which list the files
+    /// of the crates, unless the user asked for a message format, e.g. with
+    /// `--message-format=json-diagnostic-short`. A human-readable message format is rejected for
+    /// the SARIF log.
+    fn message_format(&mut self, sarif: bool) -> Result<Messages, String> {
+        let mut formats = Vec::new();
+        let mut args = self.args.iter();
+        while let Some(arg) = args.next() {
+            if arg == "--message-format" {
+                formats.extend(args.next().map(String::as_str));
+            } else if let Some(format) = arg.strip_prefix("--message-format=") {
+                formats.push(format);
+            }
+        }
+        if let Some(format) = formats
+            .iter()
+            .flat_map(|formats| formats.split(','))
+            .find(|format| !format.starts_with("json"))
+        {
+            return if sarif {
+                Err(format!(
+                    "`--output-format=sarif` requires a JSON message format, found `--message-format={format}`"
+                ))
+            } else {
+                Ok(Messages::Human)
+            };
+        }
+        if !formats.is_empty() {
+            return Ok(Messages::Json);
         }
+        // Without the SARIF log, cargo still renders the diagnostics
+        let format = if sarif { "json" } else { "json-render-diagnostics" };
+        self.args.push(format!("--message-format={format}"));
+        Ok(Messages::Internal)
     }
 
     fn path() -> PathBuf {
@@ -128,25 +218,171 @@ impl ClippyCmd {
     }
 }
 
+impl Baseline {
+    fn new(arg: &str, path: String) -> Self {
+        // `clippy-driver` is run from the directory of each package
+        let path = std::path::absolute(&path).unwrap_or_else(|_| path.into());
+        if arg == "--baseline" {
+            Self::Read(path)
+        } else {
+            Self::Write(path)
+        }
+    }
+
+    /// The directory the crates write their diagnostics to, next to the baseline. It is unique to
+    /// each run so that cargo does not skip the crates checked by the previous run.
+    fn parts_dir(path: &Path) -> PathBuf {
+        let mut name = path.file_name().unwrap_or_default().to_os_string();
+        name.push(format!(".{}.tmp", process::id()));
+        path.with_file_name(name)
+    }
+}
+
 fn process<I>(old_args: I) -> Result<(), i32>
 where
     I: Iterator<Item = String>,
 {
-    let cmd = ClippyCmd::new(old_args);
+    let mut cmd = ClippyCmd::new(old_args).map_err(|e| {
+        eprintln!("{e}");
+        rustc_driver::EXIT_FAILURE
+    })?;
+
+    let sarif = match cmd.output_format.as_deref() {
+        None | Some("human") => false,
+        Some("sarif") => true,
+        Some(format) => {
+            eprintln!("unknown output format `{format}`, expected `human` or `sarif`");
+            return Err(rustc_driver::EXIT_FAILURE);
+        },
+    };
+    let messages = cmd.message_format(sarif).map_err(|e| {
+        eprintln!("{e}");
+        rustc_driver::EXIT_FAILURE
+    })?;
+
+    let write_baseline = match &cmd.baseline {
+        Some(Baseline::Read(path)) => {
+            cmd.clippy_args.push(format!("--baseline={}", path.display()));
+            None
+        },
+        Some(Baseline::Write(path)) => {
+            let dir = Baseline::parts_dir(path);
+            if let Err(e) = fs::create_dir_all(&dir) {
+                eprintln!("failed to create `{}`: {e}", dir.display());
+                return Err(rustc_driver::EXIT_FAILURE);
+            }
+            cmd.clippy_args.push(format!("--write-baseline={}", dir.display()));
+            Some((dir, path.clone()))
+        },
+        None => None,
+    };
 
     let mut cmd = cmd.into_std_cmd();
+    // The crates with budgets record their diagnostics next to their metadata, to be merged once
+    // all the crates are checked. The files of the crates are listed by the JSON messages of cargo,
+    // including the crates which were not checked again as they did not change.
+    if messages != Messages::Human {
+        cmd.env(clippy_utils::budgets::RECORD_VAR, "1").stdout(Stdio::piped());
+    }
+    let mut child = cmd.spawn().expect("could not run cargo");
+    let mut artifacts = Vec::new();
+    let mut sarif_messages = String::new();
+    let mut io_failed = false;
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else {
+                io_failed = true;
+                break;
+            };
+            if let Ok(Message::CompilerArtifact(artifact)) = serde_json::from_str(&line) {
+                artifacts.extend(artifact.filenames);
+            }
+            if sarif {
+                sarif_messages.push_str(&line);
+                sarif_messages.push('\n');
+            } else if messages == Messages::Json && writeln!(io::stdout().lock(), "{line}").is_err() {
+                io_failed = true;
+                break;
+            }
+        }
+    }
+    let exit_status = child.wait().expect("failed to wait for cargo?");
+    if io_failed {
+        return Err(rustc_driver::EXIT_FAILURE);
+    }
+    if sarif
+        && writeln!(
+            &mut anstream::stdout().lock(),
+            "{:#}",
+            sarif::convert(sarif_messages.as_bytes())
+        )
+        .is_err()
+    {
+        return Err(rustc_driver::EXIT_FAILURE);
+    }
 
-    let exit_status = cmd
-        .spawn()
-        .expect("could not run cargo")
-        .wait()
-        .expect("failed to wait for cargo?");
+    if let Some((dir, path)) = write_baseline {
+        let merged = if exit_status.success() {
+            clippy_utils::baseline::merge(&dir, &path)
+        } else {
+            fs::remove_dir_all(&dir).map_err(|e| e.to_string())
+        };
+        if let Err(e) = merged {
+            eprintln!("{e}");
+            return Err(rustc_driver::EXIT_FAILURE);
+        }
+    }
 
-    if exit_status.success() {
-        Ok(())
-    } else {
+    let record_files = artifacts
+        .iter()
+        .flat_map(|artifact| clippy_utils::budgets::record_files(artifact.as_std_path()));
+    let budgets_exceeded = match clippy_utils::budgets::merge(record_files) {
+        Ok(counts) => report_budgets(&counts, exit_status.success()),
+        Err(e) => {
+            eprintln!("{e}");
+            return Err(rustc_driver::EXIT_FAILURE);
+        },
+    };
+
+    if !exit_status.success() {
         Err(exit_status.code().unwrap_or(-1))
+    } else if budgets_exceeded {
+        Err(rustc_driver::EXIT_FAILURE)
+    } else {
+        Ok(())
+    }
+}
+
+/// Reports the lints which emitted more warnings than their budget, and the ones which can lower
+/// it when all the crates were checked and the configuration file is not shared. Returns whether a
+/// budget was exceeded.
+fn report_budgets(counts: &[Count], complete: bool) -> bool {
+    let mut exceeded = false;
+    for Count {
+        lint,
+        budget,
+        emitted,
+        location,
+        shared,
+    } in counts
+    {
+        let warnings = if *emitted == 1 { "warning" } else { "warnings" };
+        if emitted > budget {
+            exceeded = true;
+            eprintln!(
+                "error: `{lint}` emitted {emitted} {warnings}, exceeding its budget of {budget}\n  \
+                 --> {location}\n   \
+                 = help: fix {} of them, or raise the budget of `{lint}` in `clippy.toml`\n",
+                emitted - budget
+            );
+        } else if emitted < budget && complete && !shared {
+            eprintln!(
+                "note: `{lint}` emitted {emitted} {warnings}, lower its budget in `clippy.toml` from {budget} to {emitted}\n  \
+                 --> {location}\n"
+            );
+        }
     }
+    exceeded
 }
 
 #[must_use]
@@ -160,6 +396,9 @@ pub fn help_message() -> &'static str {
 <green,bold>Common options:</>
     <cyan,bold>--no-deps</>                Run Clippy only on the given crate, without linting the dependencies
     <cyan,bold>--fix</>                    Automatically apply lint suggestions. This flag implies <cyan>--no-deps</> and <cyan>--all-targets</>
+    <cyan,bold>--write-baseline</> <cyan><<PATH>></> Record the current diagnostics in a baseline file instead of reporting them
+    <cyan,bold>--baseline</> <cyan><<PATH>></>       Only report the diagnostics which are not in the baseline file
+    <cyan,bold>--output-format</> <cyan><<FMT>></>  Print the diagnostics in the given format: <cyan>human</> (default) or <cyan>sarif</>
     <cyan,bold>-h</>, <cyan,bold>--help</>               Print this message
     <cyan,bold>-V</>, <cyan,bold>--version</>            Print version info and exit
     <cyan,bold>--explain [LINT]</>         Print the documentation for a given lint
@@ -188,12 +427,12 @@ You can use tool lints to allow or deny lints from your code, e.g.:
 }
 #[cfg(test)]
 mod tests {
-    use super::ClippyCmd;
+    use super::{Baseline, ClippyCmd, Messages};
 
     #[test]
     fn fix() {
         let args = "cargo clippy --fix".split_whitespace().map(ToString::to_string);
-        let cmd = ClippyCmd::new(args);
+        let cmd = ClippyCmd::new(args).unwrap();
         assert_eq!("fix", cmd.cargo_subcommand);
         assert!(!cmd.args.iter().any(|arg| arg.ends_with("unstable-options")));
     }
@@ -201,7 +440,7 @@ mod tests {
     #[test]
     fn fix_implies_no_deps() {
         let args = "cargo clippy --fix".split_whitespace().map(ToString::to_string);
-        let cmd = ClippyCmd::new(args);
+        let cmd = ClippyCmd::new(args).unwrap();
         assert!(cmd.clippy_args.iter().any(|arg| arg == "--no-deps"));
     }
 
@@ -210,14 +449,80 @@ mod tests {
         let args = "cargo clippy --fix -- --no-deps"
             .split_whitespace()
             .map(ToString::to_string);
-        let cmd = ClippyCmd::new(args);
+        let cmd = ClippyCmd::new(args).unwrap();
         assert_eq!(cmd.clippy_args.iter().filter(|arg| *arg == "--no-deps").count(), 1);
     }
 
+    #[test]
+    fn baseline() {
+        let args = "--baseline baseline.json --all-targets"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args).unwrap();
+        assert!(matches!(cmd.baseline, Some(Baseline::Read(path)) if path.ends_with("baseline.json")));
+        assert_eq!(cmd.args, ["--all-targets"]);
+    }
+
+    #[test]
+    fn write_baseline() {
+        let args = "--write-baseline=baseline.json -- -W clippy::pedantic"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args).unwrap();
+        assert!(matches!(cmd.baseline, Some(Baseline::Write(path)) if path.is_absolute()));
+        assert!(cmd.args.is_empty());
+        assert_eq!(cmd.clippy_args, ["-W", "clippy::pedantic"]);
+    }
+
+    #[test]
+    fn baseline_without_path() {
+        let args = ["--baseline".to_string()].into_iter();
+        assert!(ClippyCmd::new(args).is_err());
+    }
+
+    #[test]
+    fn output_format() {
+        let args = "--output-format sarif --all-targets"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args).unwrap();
+        assert_eq!(cmd.output_format.as_deref(), Some("sarif"));
+        assert_eq!(cmd.args, ["--all-targets"]);
+    }
+
+    #[test]
+    fn message_format() {
+        let mut cmd = ClippyCmd::new("--all-targets".split_whitespace().map(ToString::to_string)).unwrap();
+        assert_eq!(cmd.message_format(false), Ok(Messages::Internal));
+        assert_eq!(cmd.args, ["--all-targets", "--message-format=json-render-diagnostics"]);
+
+        let args = "--message-format=short".split_whitespace().map(ToString::to_string);
+        let mut cmd = ClippyCmd::new(args).unwrap();
+        assert_eq!(cmd.message_format(false), Ok(Messages::Human));
+        assert_eq!(cmd.args, ["--message-format=short"]);
+    }
+
+    #[test]
+    fn sarif_message_format() {
+        let mut cmd = ClippyCmd::new("--all-targets".split_whitespace().map(ToString::to_string)).unwrap();
+        assert_eq!(cmd.message_format(true), Ok(Messages::Internal));
+        assert_eq!(cmd.args, ["--all-targets", "--message-format=json"]);
+
+        let args = "--message-format json-diagnostic-short"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let mut cmd = ClippyCmd::new(args).unwrap();
+        assert_eq!(cmd.message_format(true), Ok(Messages::Json));
+        assert_eq!(cmd.args, ["--message-format", "json-diagnostic-short"]);
+
+        let args = "--message-format=short".split_whitespace().map(ToString::to_string);
+        assert!(ClippyCmd::new(args).unwrap().message_format(true).is_err());
+    }
+
     #[test]
     fn check() {
         let args = "cargo clippy".split_whitespace().map(ToString::to_string);
-        let cmd = ClippyCmd::new(args);
+        let cmd = ClippyCmd::new(args).unwrap();
         assert_eq!("check", cmd.cargo_subcommand);
     }
 }
@@ -0,0 +1,246 @@
+//! Conversion of the JSON diagnostics of `cargo --message-format=json` to the [SARIF] format, for
+//! `cargo clippy --output-format=sarif`.
+//!
+//! [SARIF]: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+use cargo_metadata::Message;
+use cargo_metadata::diagnostic::{Diagnostic, DiagnosticLevel, DiagnosticSpan};
+use clippy_config::{ClippyConfiguration, get_configuration_metadata, sanitize_explanation};
+use clippy_lints::declared_lints::LINTS;
+use declare_clippy_lint::LintCategory;
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::BufRead;
+
+/// The id of the rule of the diagnostics without a lint name or an error code.
+const UNCODED_RULE_ID: &str = "rustc";
+
+/// Reads the messages of cargo from `input` and returns the SARIF log of their diagnostics.
+pub fn convert(input: impl BufRead) -> Value {
+    let configuration = get_configuration_metadata();
+    let mut rules = Vec::new();
+    let mut rule_indices = HashMap::new();
+    let mut results = Vec::new();
+    // The same diagnostic is emitted once per crate a file is part of, e.g. the library and its
+    // unit tests
+    let mut seen = HashSet::new();
+
+    for message in Message::parse_stream(input) {
+        let Ok(Message::CompilerMessage(message)) = message else {
+            continue;
+        };
+        let diag = message.message;
+        let level = match diag.level {
+            DiagnosticLevel::Ice | DiagnosticLevel::Error => "error",
+            DiagnosticLevel::Warning => "warning",
+            _ => continue,
+        };
+        if !seen.insert(diag.rendered.clone()) {
+            continue;
+        }
+        // Many compiler errors, e.g. syntax errors, have no code
+        let id = diag.code.as_ref().map_or(UNCODED_RULE_ID, |code| code.code.as_str());
+        let rule_index = *rule_indices.entry(id.to_owned()).or_insert_with(|| {
+            rules.push(rule(id, &configuration));
+            rules.len() - 1
+        });
+        results.push(json!({
+            "ruleId": id,
+            "ruleIndex": rule_index,
+            "level": level,
+            "message": { "text": diag.message },
+            "locations": diag.spans.iter().filter(|span| span.is_primary).map(location).collect::<Vec<_>>(),
+            "fixes": fixes(&diag),
+        }));
+    }
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "clippy",
+                    "informationUri": "https://github.com/rust-lang/rust-clippy",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+/// Returns the SARIF rule of the lint or error code `id`, described by the declaration of the lint
+/// for Clippy lints.
+fn rule(id: &str, configuration: &[ClippyConfiguration]) -> Value {
+    let Some((name, info)) = id
+        .strip_prefix("clippy::")
+        .and_then(|name| Some((name, LINTS.iter().find(|info| info.name_lower() == name)?)))
+    else {
+        return json!({ "id": id });
+    };
+    let explanation = sanitize_explanation(info.explanation);
+    let configuration: Vec<&str> = configuration
+        .iter()
+        .filter(|conf| conf.lints.contains(&name))
+        .map(|conf| conf.name.as_str())
+        .collect();
+    json!({
+        "id": id,
+        "name": name,
+        "shortDescription": { "text": short_description(&explanation) },
+        "fullDescription": { "text": explanation },
+        "help": { "text": explanation, "markdown": explanation },
+        "helpUri": format!("https://rust-lang.github.io/rust-clippy/master/index.html#{name}"),
+        "defaultConfiguration": { "level": default_level(info.category) },
+        "properties": {
+            "tags": [info.category.name()],
+            "configuration": configuration,
+        },
+    })
+}
+
+/// Returns the paragraph of the `### What it does` section of the explanation of a lint.
+fn short_description(explanation: &str) -> String {
+    explanation
+        .lines()
+        .skip_while(|line| line.starts_with('#') || line.trim().is_empty())
+        .take_while(|line| !line.starts_with('#') && !line.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns the SARIF level of the default lint level of the lints of `category`.
+fn default_level(category: LintCategory) -> &'static str {
+    match category {
+        LintCategory::Correctness => "error",
+        LintCategory::Complexity | LintCategory::Perf | LintCategory::Style | LintCategory::Suspicious => "warning",
+        LintCategory::Cargo | LintCategory::Nursery | LintCategory::Pedantic | LintCategory::Restriction => "none",
+    }
+}
+
+fn location(span: &DiagnosticSpan) -> Value {
+    json!({
+        "physicalLocation": {
+            "artifactLocation": artifact_location(span),
+            "region": region(span),
+        },
+    })
+}
+
+fn artifact_location(span: &DiagnosticSpan) -> Value {
+    json!({ "uri": span.file_name.replace('\\', "/"), "uriBaseId": "%SRCROOT%" })
+}
+
+fn region(span: &DiagnosticSpan) -> Value {
+    json!({
+        "startLine": span.line_start,
+        "startColumn": span.column_start,
+        "endLine": span.line_end,
+        "endColumn": span.column_end,
+    })
+}
+
+/// Returns a SARIF fix for each suggestion of `diag`.
+fn fixes(diag: &Diagnostic) -> Vec<Value> {
+    diag.children
+        .iter()
+        .filter_map(|child| {
+            let mut changes: BTreeMap<&str, (Value, Vec<Value>)> = BTreeMap::new();
+            for span in &child.spans {
+                if let Some(replacement) = &span.suggested_replacement {
+                    changes
+                        .entry(&span.file_name)
+                        .or_insert_with(|| (artifact_location(span), Vec::new()))
+                        .1
+                        .push(json!({
+                            "deletedRegion": region(span),
+                            "insertedContent": { "text": replacement },
+                        }));
+                }
+            }
+            (!changes.is_empty()).then(|| {
+                json!({
+                    "description": { "text": child.message },
+                    "artifactChanges": changes
+                        .into_values()
+                        .map(|(location, replacements)| json!({
+                            "artifactLocation": location,
+                            "replacements": replacements,
+                        }))
+                        .collect::<Vec<_>>(),
+                })
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::convert;
+    use serde_json::json;
+
+    const MESSAGES: &str = r#"{"reason":"compiler-message","package_id":"path+file:///tmp/sarif_test#0.1.0","manifest_path":"/tmp/sarif_test/Cargo.toml","target":{"kind":["bin"],"crate_types":["bin"],"name":"sarif_test","src_path":"/tmp/sarif_test/src/main.rs","edition":"2024","doc":true,"doctest":false,"test":true},"message":{"rendered":"warning: unneeded `return` statement","$message_type":"diagnostic","children":[{"children":[],"code":null,"level":"help","message":"for further information visit https://rust-lang.github.io/rust-clippy/master/index.html#needless_return","rendered":null,"spans":[]},{"children":[],"code":null,"level":"help","message":"remove `return`","rendered":null,"spans":[{"byte_end":74,"byte_start":66,"column_end":13,"column_start":5,"expansion":null,"file_name":"src/main.rs","is_primary":true,"label":null,"line_end":7,"line_start":7,"suggested_replacement":"1","suggestion_applicability":"MachineApplicable","text":[]},{"byte_end":75,"byte_start":74,"column_end":14,"column_start":13,"expansion":null,"file_name":"src/main.rs","is_primary":true,"label":null,"line_end":7,"line_start":7,"suggested_replacement":"","suggestion_applicability":"MachineApplicable","text":[]}]}],"level":"warning","message":"unneeded `return` statement","spans":[{"byte_end":74,"byte_start":66,"column_end":13,"column_start":5,"expansion":null,"file_name":"src/main.rs","is_primary":true,"label":null,"line_end":7,"line_start":7,"suggested_replacement":null,"suggestion_applicability":null,"text":[]}],"code":{"code":"clippy::needless_return","explanation":null}}}
+{"reason":"compiler-message","package_id":"path+file:///tmp/sarif_test#0.1.0","manifest_path":"/tmp/sarif_test/Cargo.toml","target":{"kind":["bin"],"crate_types":["bin"],"name":"syntax","src_path":"/tmp/sarif_test/src/bin/syntax.rs","edition":"2024","doc":true,"doctest":false,"test":true},"message":{"rendered":"error: expected pattern, found `=`","$message_type":"diagnostic","children":[],"level":"error","message":"expected pattern, found `=`","spans":[{"byte_end":18,"byte_start":17,"column_end":10,"column_start":9,"expansion":null,"file_name":"src/bin/syntax.rs","is_primary":true,"label":"expected pattern","line_end":2,"line_start":2,"suggested_replacement":null,"suggestion_applicability":null,"text":[]}],"code":null}}
+{"reason":"build-finished","success":false}
+"#;
+
+    #[test]
+    fn convert_messages() {
+        let log = convert(MESSAGES.as_bytes());
+        let run = &log["runs"][0];
+
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0]["id"], "clippy::needless_return");
+        assert_eq!(
+            rules[0]["helpUri"],
+            "https://rust-lang.github.io/rust-clippy/master/index.html#needless_return"
+        );
+        assert_eq!(rules[0]["defaultConfiguration"]["level"], "warning");
+        assert_eq!(rules[1], json!({ "id": "rustc" }));
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            results[0],
+            json!({
+                "ruleId": "clippy::needless_return",
+                "ruleIndex": 0,
+                "level": "warning",
+                "message": { "text": "unneeded `return` statement" },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": "src/main.rs", "uriBaseId": "%SRCROOT%" },
+                        "region": { "startLine": 7, "startColumn": 5, "endLine": 7, "endColumn": 13 },
+                    },
+                }],
+                "fixes": [{
+                    "description": { "text": "remove `return`" },
+                    "artifactChanges": [{
+                        "artifactLocation": { "uri": "src/main.rs", "uriBaseId": "%SRCROOT%" },
+                        "replacements": [
+                            {
+                                "deletedRegion": { "startLine": 7, "startColumn": 5, "endLine": 7, "endColumn": 13 },
+                                "insertedContent": { "text": "1" },
+                            },
+                            {
+                                "deletedRegion": { "startLine": 7, "startColumn": 13, "endLine": 7, "endColumn": 14 },
+                                "insertedContent": { "text": "" },
+                            },
+                        ],
+                    }],
+                }],
+            })
+        );
+        assert_eq!(results[1]["ruleId"], "rustc");
+        assert_eq!(results[1]["ruleIndex"], 1);
+        assert_eq!(results[1]["level"], "error");
+        assert_eq!(results[1]["message"]["text"], "expected pattern, found `=`");
+        assert_eq!(
+            results[1]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "src/bin/syntax.rs"
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use test_utils::{CARGO_CLIPPY_PATH, IS_RUSTC_TEST_SUITE};
+
+mod test_utils;
+
+const MANIFEST: &str = r#"[package]
+name = "baseline_test"
+version = "0.1.0"
+edition = "2024"
+publish = false
+
+[workspace]
+"#;
+
+const LIB: &str = "pub fn lib() -> u32 {
+    return 1;
+}
+";
+
+const MAIN: &str = "fn main() {
+    println!(\"{}\", baseline_test::lib());
+}
+
+fn bin() -> u32 {
+    return 2;
+}
+";
+
+fn clippy(cwd: &Path, target_dir: &Path, arg: &str) -> Output {
+    let output = Command::new(&*CARGO_CLIPPY_PATH)
+        .current_dir(cwd)
+        .env("CARGO_INCREMENTAL", "0")
+        .env("CARGO_TARGET_DIR", target_dir)
+        .arg("clippy")
+        .arg(arg)
+        .arg("--")
+        .args(["-A", "dead_code"])
+        .arg("-Cdebuginfo=0") // disable debuginfo to generate less data in the target dir
+        .output()
+        .unwrap();
+    println!("status: {}", output.status);
+    println!("stdout: {}", String::from_utf8_lossy(&output.stdout));
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(output.status.success());
+    output
+}
+
+#[test]
+fn test_baseline() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = root.join("target").join("baseline_test");
+    let cwd = target_dir.join("package");
+
+    // Make sure we start with a clean state
+    let _ = fs::remove_dir_all(&cwd);
+    fs::create_dir_all(cwd.join("src")).unwrap();
+    fs::write(cwd.join("Cargo.toml"), MANIFEST).unwrap();
+    fs::write(cwd.join("src/lib.rs"), LIB).unwrap();
+    fs::write(cwd.join("src/main.rs"), MAIN).unwrap();
+
+    // The library and the binary each write a part of the baseline, merged into `baseline.json`
+    let output = clippy(&cwd, &target_dir, "--write-baseline=baseline.json");
+    assert!(
+        !String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("warning: unneeded `return`")
+    );
+    let baseline = fs::read_to_string(cwd.join("baseline.json")).unwrap();
+    let baseline: Vec<serde_json::Value> = serde_json::from_str(&baseline).unwrap();
+    let mut files: Vec<&str> = baseline
+        .iter()
+        .filter(|entry| entry["lint"] == "clippy::needless_return")
+        .map(|entry| entry["file"].as_str().unwrap())
+        .collect();
+    files.sort_unstable();
+    assert_eq!(files, ["src/lib.rs", "src/main.rs"]);
+    let parts_left = fs::read_dir(&cwd)
+        .unwrap()
+        .any(|entry| entry.unwrap().file_name().to_string_lossy().ends_with(".tmp"));
+    assert!(!parts_left, "the parts of the baseline were not removed");
+
+    // The diagnostics of the baseline are not emitted, even if the code around them moves
+    fs::write(cwd.join("src/lib.rs"), format!("\n\n{LIB}")).unwrap();
+    let output = clippy(&cwd, &target_dir, "--baseline=baseline.json");
+    assert!(
+        !String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("warning: unneeded `return`")
+    );
+
+    // New diagnostics are emitted
+    fs::write(
+        cwd.join("src/lib.rs"),
+        format!("{LIB}\npub fn new() -> u32 {{\n    return 3;\n}}\n"),
+    )
+    .unwrap();
+    let output = clippy(&cwd, &target_dir, "--baseline=baseline.json");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(stderr.matches("warning: unneeded `return`").count(), 1);
+    assert!(stderr.contains("return 3;"));
+}
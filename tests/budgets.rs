@@ -0,0 +1,86 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use test_utils::{CARGO_CLIPPY_PATH, IS_RUSTC_TEST_SUITE};
+
+mod test_utils;
+
+const MANIFEST: &str = r#"[package]
+name = "budgets_test"
+version = "0.1.0"
+edition = "2024"
+publish = false
+
+[workspace]
+"#;
+
+const CONF: &str = r#"[budgets]
+"clippy::unwrap_used" = 1
+"#;
+
+const LIB: &str = "pub fn lib(x: Option<u32>) -> u32 {
+    x.unwrap()
+}
+";
+
+const MAIN: &str = "fn main() {
+    println!(\"{}\", budgets_test::lib(Some(1)) + Some(2).unwrap());
+}
+";
+
+fn clippy(cwd: &Path, target_dir: &Path) -> Output {
+    let output = Command::new(&*CARGO_CLIPPY_PATH)
+        .current_dir(cwd)
+        .env("CARGO_INCREMENTAL", "0")
+        .env("CARGO_TARGET_DIR", target_dir)
+        .arg("clippy")
+        .arg("--")
+        .args(["-W", "clippy::unwrap_used"])
+        .arg("-Cdebuginfo=0") // disable debuginfo to generate less data in the target dir
+        .output()
+        .unwrap();
+    println!("status: {}", output.status);
+    println!("stdout: {}", String::from_utf8_lossy(&output.stdout));
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+    output
+}
+
+#[test]
+fn test_budgets() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = root.join("target").join("budgets_test");
+    let cwd = target_dir.join("package");
+
+    // Make sure we start with a clean state
+    let _ = fs::remove_dir_all(&target_dir);
+    fs::create_dir_all(cwd.join("src")).unwrap();
+    fs::write(cwd.join("Cargo.toml"), MANIFEST).unwrap();
+    fs::write(cwd.join("clippy.toml"), CONF).unwrap();
+    fs::write(cwd.join("src/lib.rs"), LIB).unwrap();
+    fs::write(cwd.join("src/main.rs"), MAIN).unwrap();
+
+    // The warnings of the library and of the binary are counted together, nothing is printed to the
+    // standard output
+    let output = clippy(&cwd, &target_dir);
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("`clippy::unwrap_used` emitted 2 warnings, exceeding its budget of 1"));
+
+    // The crates which did not change are not checked again, but their warnings are still counted
+    let output = clippy(&cwd, &target_dir);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!stderr.contains("Checking budgets_test"));
+    assert!(stderr.contains("`clippy::unwrap_used` emitted 2 warnings, exceeding its budget of 1"));
+
+    // The budget is met once a warning is fixed
+    fs::write(cwd.join("src/main.rs"), MAIN.replace("Some(2).unwrap()", "2")).unwrap();
+    let output = clippy(&cwd, &target_dir);
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!stderr.contains("exceeding its budget"));
+}
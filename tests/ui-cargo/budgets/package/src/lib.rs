@@ -0,0 +1,5 @@
+#![warn(warnings, clippy::unwrap_used, clippy::expect_used)]
+
+pub fn f(x: Option<u32>) -> u32 {
+    x.unwrap() + x.expect("x")
+}
@@ -0,0 +1,7 @@
+#![warn(warnings, clippy::unwrap_used, clippy::expect_used)]
+
+fn main() {
+    let x = std::env::args().next();
+    let _ = x.unwrap();
+    package::f(None);
+}
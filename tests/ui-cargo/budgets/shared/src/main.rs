@@ -0,0 +1,3 @@
+#![warn(clippy::unwrap_used)]
+
+fn main() {}
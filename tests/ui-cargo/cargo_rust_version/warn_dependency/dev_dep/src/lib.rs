@@ -0,0 +1 @@
+pub fn dev_dep() {}
@@ -0,0 +1,5 @@
+#![warn(clippy::incompatible_dependency_rust_version)]
+
+fn main() {
+    dep::dep();
+}
@@ -0,0 +1,8 @@
+#![deny(clippy::incompatible_dependency_version)]
+
+fn main() {
+    dep::old();
+    dep::new();
+    let added: dep::Added = dep::Added;
+    added.method();
+}
@@ -0,0 +1,9 @@
+pub fn old() {}
+
+pub fn new() {}
+
+pub struct Added;
+
+impl Added {
+    pub fn method(&self) {}
+}
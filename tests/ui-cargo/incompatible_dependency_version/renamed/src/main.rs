@@ -0,0 +1,6 @@
+#![deny(clippy::incompatible_dependency_version)]
+
+fn main() {
+    dep2::old();
+    dep2::new();
+}
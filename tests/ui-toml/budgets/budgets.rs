@@ -0,0 +1,14 @@
+//@error-in-other-file: `clippy::unwrap_used` emitted 2 warnings, exceeding its budget of 1
+//@error-in-other-file: unknown lint `clippy::unwrap_usd` in `budgets`
+#![warn(clippy::unwrap_used, clippy::expect_used)]
+
+fn f(x: Option<u32>) {
+    let _ = x.unwrap();
+    //~^ unwrap_used
+    let _ = x.unwrap();
+    //~^ unwrap_used
+    let _ = x.expect("x");
+    //~^ expect_used
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+#![warn(clippy::disallowed_names, clippy::too_many_arguments)]
+
+// Lists are merged with the ones from the extended configuration file
+fn disallowed_names() {
+    let toto = 1;
+    //~^ disallowed_names
+    let tata = 2;
+    //~^ disallowed_names
+    // `foo` is part of the default configuration, which has been replaced
+    let foo = 3;
+}
+
+// Scalar values override the ones from the extended configuration file
+fn below_threshold(_: u8, _: u8, _: u8) {}
+
+fn above_threshold(_: u8, _: u8, _: u8, _: u8) {}
+//~^ too_many_arguments
+
+fn main() {}
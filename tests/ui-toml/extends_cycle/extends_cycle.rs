@@ -0,0 +1,3 @@
+//@error-in-other-file: `extends` cycle detected
+
+fn main() {}
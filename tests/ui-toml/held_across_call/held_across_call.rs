@@ -0,0 +1,70 @@
+#![warn(clippy::held_across_call)]
+
+use std::sync::Mutex;
+use std::sync::mpsc::Receiver;
+use std::thread::sleep;
+use std::time::Duration;
+
+struct Entered;
+
+impl Drop for Entered {
+    fn drop(&mut self) {}
+}
+
+mod blocking {
+    pub fn get() {}
+    pub fn post() {}
+}
+
+fn sleep_while_locked(m: &Mutex<u32>) {
+    let mut guard = m.lock().unwrap();
+    *guard += 1;
+    sleep(Duration::from_secs(1));
+    //~^ held_across_call
+}
+
+fn sleep_after_drop(m: &Mutex<u32>) {
+    let mut guard = m.lock().unwrap();
+    *guard += 1;
+    drop(guard);
+    sleep(Duration::from_secs(1));
+}
+
+fn sleep_after_scope(m: &Mutex<u32>) {
+    {
+        let mut guard = m.lock().unwrap();
+        *guard += 1;
+    }
+    sleep(Duration::from_secs(1));
+}
+
+fn temporary_guard(m: &Mutex<u32>) {
+    *m.lock().unwrap() += 1;
+    sleep(Duration::from_secs(1));
+}
+
+fn recv_while_locked(m: &Mutex<u32>, rx: &Receiver<u32>) {
+    let guard = m.lock().unwrap();
+    let _ = rx.recv_timeout(Duration::from_secs(1));
+    //~^ held_across_call
+    let _ = rx.try_recv();
+    drop(guard);
+    let _ = rx.recv();
+}
+
+fn optional_guard(m: &Mutex<u32>, lock: bool) {
+    let guard = if lock { Some(m.lock().unwrap()) } else { None };
+    sleep(Duration::from_secs(1));
+    //~^ held_across_call
+    drop(guard);
+}
+
+fn entered() {
+    let _entered = Entered;
+    blocking::get();
+    //~^ held_across_call
+    blocking::post();
+    //~^ held_across_call
+}
+
+fn main() {}
@@ -0,0 +1,82 @@
+#![warn(clippy::missing_panics_doc)]
+
+fn parse(input: &str) -> u32 {
+    input.parse().unwrap()
+}
+
+fn parse_twice(input: &str) -> u32 {
+    parse(input) * 2
+}
+
+fn parse_thrice(input: &str) -> u32 {
+    parse_twice(input) + parse(input)
+}
+
+fn deeply_nested(input: &str) -> u32 {
+    parse_twice(input)
+}
+
+fn checked(input: &str) -> Option<u32> {
+    input.parse().ok()
+}
+
+fn recursive(n: u32) -> u32 {
+    if n == 0 { 0 } else { recursive(n / 2) }
+}
+
+pub struct Parser;
+
+impl Parser {
+    fn parse(&self, input: &str) -> u32 {
+        parse(input)
+    }
+}
+
+pub fn direct(input: &str) -> u32 {
+    //~^ missing_panics_doc
+    parse(input)
+}
+
+pub fn nested(input: &str) -> u32 {
+    //~^ missing_panics_doc
+    parse_twice(input)
+}
+
+pub fn method(parser: &Parser, input: &str) -> u32 {
+    //~^ missing_panics_doc
+    parser.parse(input)
+}
+
+pub fn second_path(input: &str) -> u32 {
+    //~^ missing_panics_doc
+    // `parse_twice` is too deep to panic through `deeply_nested`, but not when called directly
+    deeply_nested(input) + parse_twice(input)
+}
+
+// Not linted
+
+/// # Panics
+///
+/// Panics if `input` is not a number.
+pub fn documented(input: &str) -> u32 {
+    parse(input)
+}
+
+pub fn too_deep(input: &str) -> u32 {
+    deeply_nested(input)
+}
+
+pub fn no_panic(input: &str) -> Option<u32> {
+    checked(input)
+}
+
+pub fn calls_recursive(n: u32) -> u32 {
+    recursive(n)
+}
+
+pub fn expected(input: &str) -> u32 {
+    #[expect(clippy::missing_panics_doc, reason = "the input is validated")]
+    parse(input)
+}
+
+fn main() {}
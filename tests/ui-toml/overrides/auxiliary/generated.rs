@@ -0,0 +1,23 @@
+// The lints of the entry whose `paths` match this file are allowed as if by `#![allow]` at the top
+// of the file
+pub fn generated(x: Option<u8>) -> u8 {
+    dbg!(x).unwrap()
+}
+
+// The lint attributes of the file take precedence
+#[warn(clippy::unwrap_used)]
+pub fn explicit(x: Option<u8>) -> u8 {
+    x.unwrap()
+}
+
+// The values of the entries whose `paths` match this file replace the ones of the crate
+pub fn below_threshold(_: u8, _: u8, _: u8) {}
+
+pub fn above_threshold(_: u8, _: u8, _: u8, _: u8) {}
+
+pub fn names() -> Vec<u8> {
+    let foo = 0;
+    let bar = foo;
+    let _ = bar;
+    Vec::new()
+}
@@ -0,0 +1,35 @@
+//@no-rustfix
+//@error-in-other-file: used `unwrap()` on an `Option` value
+//@error-in-other-file: this function has too many arguments (4/3)
+//@error-in-other-file: use of a disallowed/placeholder name `bar`
+//@error-in-other-file: use of a disallowed method `std::vec::Vec::new`
+#![warn(clippy::too_many_arguments, clippy::dbg_macro, clippy::unwrap_used)]
+
+fn below_threshold(_: u8, _: u8) {}
+
+fn above_threshold(_: u8, _: u8, _: u8) {}
+//~^ too_many_arguments
+
+fn not_generated(x: Option<u8>) {
+    // The `paths` entry only matches the file of the `generated` module
+    dbg!(x);
+    //~^ dbg_macro
+
+    // The `cfg-test` entry doesn't apply without `--test`
+    x.unwrap();
+    //~^ unwrap_used
+}
+
+fn names() -> Vec<u8> {
+    // The `paths` entries only set values in the files they match
+    let foo = 0;
+    //~^ disallowed_names
+    let bar = foo;
+    let _ = bar;
+    Vec::new()
+}
+
+#[path = "auxiliary/generated.rs"]
+mod generated;
+
+fn main() {}
@@ -0,0 +1,29 @@
+//@compile-flags: --test
+#![warn(clippy::unwrap_used)]
+
+fn outside_tests(x: Option<u8>) {
+    x.unwrap();
+    //~^ unwrap_used
+}
+
+#[test]
+fn in_test() {
+    // Allowed in test code by the `cfg-test` entry
+    std::hint::black_box(Some(1)).unwrap();
+}
+
+fn main() {}
+
+#[cfg(test)]
+mod tests {
+    fn helper(x: Option<u8>) -> u8 {
+        x.unwrap()
+    }
+
+    // The lint attributes of the test code take precedence
+    #[warn(clippy::unwrap_used)]
+    fn explicit(x: Option<u8>) -> u8 {
+        x.unwrap()
+        //~^ unwrap_used
+    }
+}
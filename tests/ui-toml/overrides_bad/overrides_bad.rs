@@ -0,0 +1,12 @@
+//@error-in-other-file: `[[overrides]]` entries must set `paths` or `cfg-test`
+//@error-in-other-file: invalid type: string "two"
+//@error-in-other-file: `too-large-for-stack` cannot be set per file in `[[overrides]]` entries
+//@error-in-other-file: cannot be set in `[[overrides]]` entries setting `cfg-test`, only `allow`
+//@error-in-other-file: `paths` cannot be empty
+//@error-in-other-file: `extends` cannot be set in `[[overrides]]` entries
+//@error-in-other-file: `paths` and `cfg-test` can only be set in `[[overrides]]` entries
+//@error-in-other-file: `allow` can only be set in `[[overrides]]` entries
+//@error-in-other-file: unknown lint `clippy::unwarp_used` in `allow`
+//@error-in-other-file: `clippy::excessive_nesting` can't be allowed by `[[overrides]]` entries
+
+fn main() {}
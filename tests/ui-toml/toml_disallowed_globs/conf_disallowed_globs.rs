@@ -0,0 +1,38 @@
+//@error-in-other-file: `std::process::nothing_*` does not match any function
+#![warn(clippy::disallowed_methods, clippy::disallowed_types, clippy::disallowed_macros)]
+
+use std::collections::HashMap;
+//~^ disallowed_types
+use std::collections::{BTreeMap, HashSet};
+//~^ disallowed_types
+
+mod helpers {
+    pub fn one() {}
+    pub fn two() {}
+}
+
+fn main() {
+    let _ = std::process::id();
+    //~^ disallowed_methods
+    let _ = std::process::Command::new("ls");
+
+    let v = [1, 2, 3];
+    let _ = unsafe { v.get_unchecked(0) };
+    //~^ disallowed_methods
+    let _ = v.get(1);
+
+    let _ = " a ".trim_start();
+    //~^ disallowed_methods
+    let _ = " a ".trim();
+
+    helpers::one();
+    //~^ disallowed_methods
+    helpers::two();
+    //~^ disallowed_methods
+
+    debug_assert!(v.len() > 1);
+    //~^ disallowed_macros
+    assert!(v.len() > 1);
+
+    let _: BTreeMap<u8, u8> = BTreeMap::new();
+}
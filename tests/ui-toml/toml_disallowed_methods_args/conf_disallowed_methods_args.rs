@@ -0,0 +1,44 @@
+#![warn(clippy::disallowed_methods)]
+
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+const LIMIT: u32 = 10;
+const SHELL: &str = "sh";
+
+fn set(_: bool, _: u32) {}
+
+struct Flag(AtomicBool);
+
+impl Flag {
+    fn store(&self, value: bool, order: Ordering) {
+        self.0.store(value, order);
+    }
+}
+
+fn main() {
+    let _ = Command::new("sh");
+    //~^ disallowed_methods
+    let _ = Command::new(SHELL);
+    //~^ disallowed_methods
+    let _ = Command::new("ls");
+
+    let flag = Flag(AtomicBool::new(false));
+    flag.store(true, Ordering::Relaxed);
+    //~^ disallowed_methods
+    flag.store(true, Ordering::SeqCst);
+
+    let _: Vec<u8> = Vec::with_capacity(0);
+    //~^ disallowed_methods
+    let _: Vec<u8> = Vec::with_capacity(1);
+
+    set(true, LIMIT);
+    //~^ disallowed_methods
+    set(false, LIMIT);
+    set(true, 10);
+
+    let _ = [true].map(|_| Command::new("sh"));
+    //~^ disallowed_methods
+    // The arguments are unknown
+    let _ = ["sh"].map(Command::new);
+}
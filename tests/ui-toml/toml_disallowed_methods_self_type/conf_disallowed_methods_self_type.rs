@@ -0,0 +1,76 @@
+#![warn(clippy::disallowed_methods)]
+#![allow(clippy::unused_io_amount)]
+
+use std::fs::File;
+use std::io::{Read, Stdin, Write};
+use std::net::TcpStream;
+
+fn read(file: &mut File, stream: &mut TcpStream, stdin: &mut Stdin, buf: &mut [u8]) {
+    file.read(buf).unwrap();
+    //~^ disallowed_methods
+    stream.read(buf).unwrap();
+    //~^ disallowed_methods
+    Read::read(file, buf).unwrap();
+    //~^ disallowed_methods
+    (&*file).read(buf).unwrap();
+    //~^ disallowed_methods
+
+    // `Stdin` is not disallowed
+    stdin.read(buf).unwrap();
+}
+
+fn write(file: &mut File, stream: &mut TcpStream, buf: &[u8]) {
+    stream.write(buf).unwrap();
+    //~^ disallowed_methods
+
+    // `File` is not disallowed
+    file.write(buf).unwrap();
+}
+
+fn read_generic<R: Read>(reader: &mut R, buf: &mut [u8]) {
+    // The `Self` type is not known here
+    reader.read(buf).unwrap();
+}
+
+fn push(bytes: &mut Vec<u8>, ints: &mut Vec<i32>) {
+    bytes.push(1);
+    //~^ disallowed_methods
+    ints.push(1);
+}
+
+fn take(vec: &mut Option<Vec<char>>, string: &mut Option<String>) {
+    let _ = vec.take();
+    //~^ disallowed_methods
+    let _ = string.take();
+}
+
+fn flush(file: &mut File, stream: &mut TcpStream) {
+    file.flush().unwrap();
+    //~^ disallowed_methods
+    stream.flush().unwrap();
+}
+
+struct Struct;
+struct Other;
+
+trait Trait {
+    fn implemented_method(&self);
+}
+
+impl Trait for Struct {
+    fn implemented_method(&self) {}
+}
+
+impl Trait for Other {
+    fn implemented_method(&self) {}
+}
+
+fn trait_impl(s: Struct, o: Other) {
+    s.implemented_method();
+    //~^ disallowed_methods
+    Trait::implemented_method(&s);
+    //~^ disallowed_methods
+    o.implemented_method();
+}
+
+fn main() {}
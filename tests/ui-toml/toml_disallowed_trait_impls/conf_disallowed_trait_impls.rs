@@ -0,0 +1,49 @@
+#![feature(negative_impls)]
+#![warn(clippy::disallowed_trait_impls)]
+
+use std::ops::{Deref, Drop};
+
+struct Handle(*mut u8);
+
+unsafe impl Send for Handle {}
+//~^ disallowed_trait_impls
+
+struct Guard;
+
+impl Drop for Guard {
+    //~^ disallowed_trait_impls
+    fn drop(&mut self) {}
+}
+
+struct NotSend;
+
+// Opting out is allowed
+impl !Send for NotSend {}
+
+struct Meters(f64);
+
+impl Deref for Meters {
+    //~^ disallowed_trait_impls
+    type Target = f64;
+    fn deref(&self) -> &f64 {
+        &self.0
+    }
+}
+
+struct Wrapper<T>(T);
+
+impl<T> Deref for Wrapper<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+trait Local {}
+
+impl Local for Vec<u8> {}
+//~^ disallowed_trait_impls
+
+impl Local for Vec<i32> {}
+
+fn main() {}
@@ -2,5 +2,13 @@
 //@error-in-other-file: `std::process::current_exe` does not refer to a reachable function
 //@error-in-other-file: `` does not refer to a reachable function
 //@error-in-other-file: expected a type, found a variant
+//@error-in-other-file: `self-type` is not supported for a type
+//@error-in-other-file: expected `,` or `>` after the generic arguments of `std::vec::Vec`
+//@error-in-other-file: `std::vec::Element` does not refer to a reachable type
+//@error-in-other-file: `std::vec::MAX` does not refer to a reachable value
+//@error-in-other-file: `std::fs::File::flush` does not refer to a reachable function
+//@error-in-other-file: `std::sync::Guard` does not refer to a reachable type
+//@error-in-other-file: `std::thread::wait` does not refer to a reachable function
+//@error-in-other-file: `std::thread::wait_*` does not match any function
 
 fn main() {}
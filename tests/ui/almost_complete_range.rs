@@ -4,6 +4,9 @@
 #![feature(stmt_expr_attributes)]
 #![warn(clippy::almost_complete_range)]
 #![allow(ellipsis_inclusive_range_patterns, clippy::double_parens)]
+// The exclusive range patterns of `_under_msrv` and `_meets_msrv` are only stable since 1.80,
+// which `incompatible_msrv` reports under their `#[clippy::msrv]` attributes
+#![allow(clippy::incompatible_msrv)]
 #![expect(clippy::needless_parens_on_range_literals)]
 
 extern crate proc_macros;
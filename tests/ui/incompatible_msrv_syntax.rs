@@ -0,0 +1,98 @@
+//@edition:2024
+#![warn(clippy::incompatible_msrv)]
+#![feature(custom_inner_attributes)]
+#![clippy::msrv = "1.12.0"]
+#![allow(clippy::manual_let_else, clippy::question_mark, clippy::unnecessary_operation)]
+
+use std::ffi::CStr;
+
+fn let_chain(x: Option<u8>) {
+    if let Some(x) = x
+        //~^ incompatible_msrv
+        //~| NOTE: `-D clippy::incompatible-msrv` implied by `-D warnings`
+        //~| HELP: to override `-D warnings` add `#[allow(clippy::incompatible_msrv)]`
+        && x > 0
+    {}
+    while let Some(_) = x
+        //~^ incompatible_msrv
+        && false
+    {}
+    if let Some(y) = x {
+        let _ = y;
+    }
+    if x.is_some() && x.is_none() {}
+}
+
+fn let_else(x: Option<u8>) -> u8 {
+    let Some(x) = x else { return 0 };
+    //~^ incompatible_msrv
+    x
+}
+
+fn raw_borrow() {
+    let x = 0;
+    let _ = &raw const x;
+    //~^ incompatible_msrv
+}
+
+fn c_str() -> &'static CStr {
+    c"foo"
+    //~^ incompatible_msrv
+}
+
+fn const_block() -> u8 {
+    const { 1 + 1 }
+    //~^ incompatible_msrv
+}
+
+fn range_pattern(x: u8) -> bool {
+    match x {
+        0..10 => true,
+        //~^ incompatible_msrv
+        10..=20 => false,
+        30.. => false,
+        _ => false,
+    }
+}
+
+fn async_closure() {
+    let _ = async || {};
+    //~^ incompatible_msrv
+    let _ = || async {};
+}
+
+fn try_operator(x: Option<u8>) -> Option<u8> {
+    let x = x?;
+    //~^ incompatible_msrv
+    Some(x)
+}
+
+#[clippy::msrv = "1.88.0"]
+fn newer_msrv(x: Option<u8>) -> Option<u8> {
+    if let Some(x) = x
+        && x > 0
+    {}
+    let Some(x) = x else { return None };
+    let _ = const { 1 };
+    Some(x)
+}
+
+#[cfg(any(test, not(test)))]
+fn under_cfg(x: Option<u8>) -> Option<u8> {
+    let x = x?;
+    //~^ incompatible_msrv
+    //~| NOTE: you may want to conditionally increase the MSRV
+    Some(x)
+}
+
+macro_rules! try_in_macro {
+    ($e:expr) => {
+        $e?
+    };
+}
+
+fn in_macro(x: Option<u8>) -> Option<u8> {
+    Some(try_in_macro!(x) + 1)
+}
+
+fn main() {}
@@ -0,0 +1,80 @@
+#![warn(clippy::incomplete_safety_comment)]
+
+/// Reads `len` bytes from `ptr`.
+///
+/// # Safety
+///
+/// - `ptr` must be valid for reads of `len` bytes.
+/// - `ptr` must be properly aligned.
+/// - The memory must not be mutated while it is read.
+unsafe fn read_bytes(ptr: *const u8, len: usize) -> Vec<u8> {
+    // SAFETY: the preconditions are forwarded to the caller
+    unsafe { std::slice::from_raw_parts(ptr, len) }.to_vec()
+}
+
+/// # Safety
+///
+/// * The buffer must be initialized.
+/// * The process must own the lock.
+unsafe fn flush() {}
+
+/// # Safety
+///
+/// The caller must uphold the invariants.
+unsafe fn unlisted() {}
+
+struct Device;
+
+impl Device {
+    /// # Safety
+    ///
+    /// - `self` must be powered on.
+    unsafe fn reset(&self) {}
+}
+
+fn main() {
+    let buffer = [0u8; 4];
+
+    // SAFETY: fine
+    let _ = unsafe { read_bytes(buffer.as_ptr(), 4) };
+    //~^ incomplete_safety_comment
+
+    // SAFETY: `ptr` comes from a reference
+    let _ = unsafe { read_bytes(buffer.as_ptr(), 4) };
+    //~^ incomplete_safety_comment
+
+    // SAFETY: the buffer was just filled
+    unsafe { flush() };
+    //~^ incomplete_safety_comment
+
+    let device = Device;
+    unsafe {
+        // SAFETY: trust me
+        device.reset();
+    }
+    //~^^^^ incomplete_safety_comment
+
+    // Not linted
+
+    // SAFETY: `ptr` comes from a reference to `buffer`, which is not mutated, and `len` is its length
+    let _ = unsafe { read_bytes(buffer.as_ptr(), 4) };
+
+    // SAFETY:
+    // - the pointer is valid
+    // - it is aligned
+    // - nothing writes to it
+    let _ = unsafe { read_bytes(buffer.as_ptr(), 4) };
+
+    // SAFETY: the buffer is initialized and this thread owns the lock
+    unsafe { flush() };
+
+    /* SAFETY: the buffer was initialized above,
+     * and the lock is owned by the process */
+    unsafe { flush() };
+
+    // SAFETY: nothing to check
+    unsafe { unlisted() };
+
+    // SAFETY: `self` was powered on by `Device::new`
+    unsafe { device.reset() };
+}
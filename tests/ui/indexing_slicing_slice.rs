@@ -174,3 +174,9 @@ fn main() {
     let z = Z::<i32>(1);
     z[0];
 }
+
+fn array_of_generic_length<const N: usize>(a: [u8; N]) {
+    // The length of the array is not known, so the range may be out of bounds
+    &a[1..2];
+    //~^ indexing_slicing
+}
@@ -0,0 +1,82 @@
+#![warn(clippy::lock_order_cycle)]
+
+use std::sync::{Mutex, RwLock};
+
+static LOG: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static STATS: Mutex<u64> = Mutex::new(0);
+
+struct Bank {
+    accounts: Mutex<Vec<u64>>,
+    log: Mutex<Vec<String>>,
+}
+
+struct Cache {
+    entries: RwLock<Vec<u32>>,
+    hits: RwLock<u64>,
+    misses: Mutex<u64>,
+}
+
+fn deposit(bank: &Bank, amount: u64) {
+    let mut accounts = bank.accounts.lock().unwrap();
+    accounts[0] += amount;
+    bank.log.lock().unwrap().push(format!("deposit {amount}"));
+    //~^ lock_order_cycle
+}
+
+fn audit(bank: &Bank) {
+    let log = bank.log.lock().unwrap();
+    let accounts = bank.accounts.lock().unwrap();
+    println!("{} entries, {} accounts", log.len(), accounts.len());
+}
+
+fn log(message: &str) {
+    LOG.lock().unwrap().push(message.to_string());
+}
+
+fn record_stats() {
+    let mut stats = STATS.lock().unwrap();
+    *stats += 1;
+    log("stats recorded");
+    //~^ lock_order_cycle
+}
+
+fn dump_log() {
+    let log = LOG.lock().unwrap();
+    *STATS.lock().unwrap() = log.len() as u64;
+}
+
+fn read_entries(cache: &Cache) {
+    let entries = cache.entries.read().unwrap();
+    let hits = cache.hits.read().unwrap();
+    //~^ lock_order_cycle
+    println!("{} {hits}", entries.len());
+}
+
+fn read_hits(cache: &Cache) {
+    let hits = cache.hits.read().unwrap();
+    let entries = cache.entries.read().unwrap();
+    println!("{} {hits}", entries.len());
+}
+
+// Not linted
+
+fn consistent_first(bank: &Bank) {
+    let accounts = bank.accounts.lock().unwrap();
+    let log = bank.log.lock().unwrap();
+    println!("{} {}", accounts.len(), log.len());
+}
+
+fn miss(cache: &Cache) {
+    let misses = cache.misses.lock().unwrap();
+    drop(misses);
+    cache.entries.write().unwrap().push(0);
+}
+
+fn clear(cache: &Cache) {
+    let mut entries = cache.entries.write().unwrap();
+    entries.clear();
+    drop(entries);
+    *cache.misses.lock().unwrap() = 0;
+}
+
+fn main() {}
@@ -0,0 +1,138 @@
+//@compile-flags: -C overflow-checks=off
+#![warn(clippy::panic_in_no_panic_fn)]
+#![allow(clippy::ptr_arg, clippy::redundant_slicing, clippy::unnecessary_literal_unwrap)]
+
+use std::ops::Index;
+
+struct Table([u8; 4]);
+
+impl Index<usize> for Table {
+    type Output = u8;
+
+    fn index(&self, index: usize) -> &u8 {
+        self.0.get(index).unwrap_or(&0)
+    }
+}
+
+fn helper(bytes: &[u8]) -> u8 {
+    bytes[0]
+}
+
+fn nested(bytes: &[u8]) -> u8 {
+    helper(bytes)
+}
+
+fn checked(bytes: &[u8]) -> u8 {
+    bytes.first().copied().unwrap_or(0)
+}
+
+#[clippy::no_panic]
+fn contract_helper(bytes: &[u8]) -> u8 {
+    bytes.get(1).copied().unwrap_or(0)
+}
+
+fn recursive(n: u32) -> u32 {
+    if n == 0 { 0 } else { recursive(n / 2) }
+}
+
+fn allowed(value: Option<u8>) -> u8 {
+    #[allow(clippy::panic_in_no_panic_fn)]
+    value.unwrap()
+}
+
+#[clippy::no_panic]
+fn panics(bytes: &[u8], x: u32, y: u32, value: Option<u8>, result: Result<u8, ()>) -> u32 {
+    let first = bytes[0];
+    //~^ panic_in_no_panic_fn
+    let sum = x + y;
+    //~^ panic_in_no_panic_fn
+    let quotient = x / y;
+    //~^ panic_in_no_panic_fn
+    let unwrapped = value.unwrap();
+    //~^ panic_in_no_panic_fn
+    let expected = result.expect("ok");
+    //~^ panic_in_no_panic_fn
+    assert!(x > 0);
+    //~^ panic_in_no_panic_fn
+    if y == 0 {
+        panic!("zero");
+        //~^ panic_in_no_panic_fn
+    }
+    sum.wrapping_add(quotient) ^ u32::from(first ^ unwrapped ^ expected)
+}
+
+#[clippy::no_panic]
+fn indexing(vec: &Vec<u8>, bytes: &[u8], table: &Table, i: usize) -> u8 {
+    let element = vec[i];
+    //~^ panic_in_no_panic_fn
+    let tail = &bytes[1..];
+    //~^ panic_in_no_panic_fn
+    let entry = table[i];
+    //~^ panic_in_no_panic_fn
+    let explicit = *table.index(i);
+    //~^ panic_in_no_panic_fn
+    element ^ tail.len() as u8 ^ entry ^ explicit
+}
+
+#[clippy::no_panic]
+fn arithmetic(x: u32, y: u32, z: i32) -> u32 {
+    let shifted = x << y;
+    //~^ panic_in_no_panic_fn
+    let negated = -z;
+    //~^ panic_in_no_panic_fn
+    let mut product = x;
+    product *= y;
+    //~^ panic_in_no_panic_fn
+    let remainder = z % z;
+    //~^ panic_in_no_panic_fn
+    shifted ^ negated.unsigned_abs() ^ product ^ remainder.unsigned_abs()
+}
+
+#[clippy::no_panic]
+fn calls(bytes: &[u8]) -> u8 {
+    let a = helper(bytes);
+    //~^ panic_in_no_panic_fn
+    let b = nested(bytes);
+    //~^ panic_in_no_panic_fn
+    a ^ b
+}
+
+#[clippy::no_panic]
+fn closures(v: &[&[u8]], value: Option<u8>) -> u32 {
+    let first = || value.unwrap();
+    //~^ panic_in_no_panic_fn
+    let sum: u32 = v.iter().map(|x| u32::from(x[0])).sum();
+    //~^ panic_in_no_panic_fn
+    sum ^ u32::from(first())
+}
+
+// Not linted
+
+#[clippy::no_panic]
+fn no_panics(bytes: &[u8], x: u32, y: u32, value: Option<u8>) -> u32 {
+    let first = bytes.first().copied().unwrap_or(0);
+    let sum = x.wrapping_add(y);
+    let quotient = x.checked_div(y).unwrap_or(0);
+    let array = [1, 2, 3];
+    let value = value.unwrap_or(array[1]);
+    let shifted = x >> 3;
+    let signed = (x as i32) / 2;
+    let whole = &bytes[..];
+    sum ^ quotient
+        ^ shifted
+        ^ signed.unsigned_abs()
+        ^ u32::from(checked(bytes) ^ contract_helper(bytes) ^ first ^ value)
+        ^ recursive(x)
+        ^ whole.len() as u32
+}
+
+#[clippy::no_panic]
+fn allowed_in_callee(value: Option<u8>) -> u8 {
+    allowed(value)
+}
+
+fn not_marked(bytes: &[u8]) -> u8 {
+    bytes[0]
+}
+
+fn main() {}
@@ -0,0 +1,141 @@
+#![warn(clippy::self_deadlock)]
+#![allow(clippy::readonly_write_lock)]
+
+use std::sync::{Arc, Mutex, RwLock};
+
+static COUNTER: Mutex<u32> = Mutex::new(0);
+
+struct State {
+    items: Mutex<Vec<u32>>,
+    config: RwLock<String>,
+}
+
+fn helper(m: &Mutex<u32>) {
+    *m.lock().unwrap() += 1;
+}
+
+fn nested_helper(m: &Mutex<u32>) {
+    helper(m);
+}
+
+fn bump_counter() {
+    *COUNTER.lock().unwrap() += 1;
+}
+
+fn lock_twice(m: &Mutex<u32>) {
+    let a = m.lock().unwrap();
+    let b = m.lock().unwrap();
+    //~^ self_deadlock
+    println!("{a} {b}");
+}
+
+fn lock_in_helper(m: &Mutex<u32>) {
+    let a = m.lock().unwrap();
+    helper(m);
+    //~^ self_deadlock
+    nested_helper(m);
+    //~^ self_deadlock
+    println!("{a}");
+}
+
+fn read_then_write(rw: &RwLock<String>) {
+    let config = rw.read().unwrap();
+    rw.write().unwrap().push_str(&config);
+    //~^ self_deadlock
+}
+
+fn fields(state: &State) {
+    let items = state.items.lock().unwrap();
+    state.items.lock().unwrap().clear();
+    //~^ self_deadlock
+    println!("{}", items.len());
+}
+
+fn arc(m: Arc<Mutex<u32>>) {
+    let a = m.lock().unwrap();
+    *m.lock().unwrap() += *a;
+    //~^ self_deadlock
+}
+
+fn local() {
+    let m = Mutex::new(1);
+    let a = m.lock().unwrap();
+    helper(&m);
+    //~^ self_deadlock
+    println!("{a}");
+}
+
+fn statics() {
+    let a = COUNTER.lock().unwrap();
+    bump_counter();
+    //~^ self_deadlock
+    println!("{a}");
+}
+
+fn guard_in_option(m: &Mutex<u32>) {
+    let a = Some(m.lock().unwrap());
+    helper(m);
+    //~^ self_deadlock
+    drop(a);
+}
+
+fn reads(rw: &RwLock<String>) {
+    let a = rw.read().unwrap();
+    let b = rw.read().unwrap();
+    //~^ self_deadlock
+    println!("{a} {b}");
+}
+
+// Not linted
+
+fn dropped(m: &Mutex<u32>) {
+    let a = m.lock().unwrap();
+    let value = *a;
+    drop(a);
+    *m.lock().unwrap() += value;
+    helper(m);
+}
+
+fn temporaries(m: &Mutex<u32>) {
+    let value = *m.lock().unwrap();
+    *m.lock().unwrap() += value;
+    helper(m);
+}
+
+fn scoped(m: &Mutex<u32>) {
+    {
+        let mut a = m.lock().unwrap();
+        *a += 1;
+    }
+    helper(m);
+}
+
+fn different_locks(m1: &Mutex<u32>, m2: &Mutex<u32>, state: &State) {
+    let a = m1.lock().unwrap();
+    helper(m2);
+    let b = state.config.write().unwrap();
+    state.items.lock().unwrap().clear();
+    println!("{a} {b}");
+}
+
+fn try_lock(m: &Mutex<u32>) {
+    let a = m.lock().unwrap();
+    assert!(m.try_lock().is_err());
+    println!("{a}");
+}
+
+fn copied_value(m: &Mutex<u32>) {
+    let mut values = Vec::new();
+    values.push(*m.lock().unwrap());
+    helper(m);
+    values.push(*m.lock().unwrap());
+}
+
+fn loops(ms: &[Mutex<u32>]) {
+    let mut guards = Vec::new();
+    for m in ms {
+        guards.push(m.lock().unwrap());
+    }
+}
+
+fn main() {}
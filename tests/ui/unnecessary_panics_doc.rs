@@ -0,0 +1,80 @@
+#![warn(clippy::unnecessary_panics_doc)]
+
+fn double(x: u32) -> u32 {
+    x.wrapping_mul(2)
+}
+
+/// # Panics
+///
+/// Panics if `bytes` is empty.
+pub fn first(bytes: &[u8]) -> Option<&u8> {
+    //~^ unnecessary_panics_doc
+    bytes.first()
+}
+
+/// # Panics
+///
+/// Never.
+pub fn calls_helper(x: u32) -> u32 {
+    //~^ unnecessary_panics_doc
+    double(x)
+}
+
+/// # Panics
+///
+/// Never.
+pub fn empty() {}
+//~^ unnecessary_panics_doc
+
+// Not linted
+
+/// # Panics
+///
+/// Panics if `bytes` is empty.
+pub fn index(bytes: &[u8]) -> u8 {
+    bytes[0]
+}
+
+/// # Panics
+///
+/// Panics on overflow.
+pub fn add(x: u32, y: u32) -> u32 {
+    x + y
+}
+
+/// # Panics
+///
+/// Panics if `y` is zero.
+pub fn divide(x: u32, y: u32) -> u32 {
+    x / y
+}
+
+/// # Panics
+///
+/// Panics if `x` is `None`.
+pub fn unwrap(x: Option<u32>) -> u32 {
+    x.unwrap()
+}
+
+/// # Panics
+///
+/// Panics if `f` panics.
+pub fn call(f: fn() -> u32) -> u32 {
+    f()
+}
+
+/// # Panics
+///
+/// Panics if `f` panics.
+pub fn generic<F: Fn() -> u32>(f: F) -> u32 {
+    f()
+}
+
+/// # Panics
+///
+/// Panics if `x` is zero.
+pub fn asserted(x: u32) {
+    assert!(x != 0);
+}
+
+fn main() {}